@@ -1,19 +1,94 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions},
+};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 
 // Signal Wars - AI Agent Prediction Arena
 // Fee Structure:
 // - Entry fee split: prize_pool_bps% to prize pool, (10000 - prize_pool_bps)% to treasury
 // - Lost stakes go to treasury (when prediction is wrong)
-// - Treasury can be withdrawn by authority
+// - Treasury is a program-owned PDA; the treasurer can withdraw above its rent-exempt minimum
+// - Seasons with an SPL mint hold fees, stakes and prizes in arena-owned token vaults
+// - Duels: the winner takes both stakes minus the arena duel fee, which goes to treasury
+// - Tournaments: prize_pool_bps% of the entry fees to the champion, the rest to treasury at start
+// - Spectator bets: parimutuel payout to the winning side after the arena betting fee
+// - Subscriptions: paid into the agent's revenue vault, less the arena subscription fee to treasury
 
 declare_id!("9s5gawgG2KJy7kofoxhRAve4zL6S7Y8dFuECtpbbBWJZ"); // Devnet deployment v4
 
+// Pyth oracle programs whose price accounts can back an asset feed
+pub mod pyth_mainnet {
+    anchor_lang::declare_id!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+}
+
+pub mod pyth_devnet {
+    anchor_lang::declare_id!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+}
+
+pub const PYTH_ORACLE_PROGRAM_IDS: [Pubkey; 2] = [pyth_mainnet::ID, pyth_devnet::ID];
+
+// Pyth v2 price account layout (only the fields we read)
+const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+const PYTH_VERSION_2: u32 = 2;
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_EXPO_OFFSET: usize = 20;
+const PYTH_TIMESTAMP_OFFSET: usize = 96;
+const PYTH_AGG_PRICE_OFFSET: usize = 208;
+const PYTH_AGG_CONF_OFFSET: usize = 216;
+const PYTH_AGG_STATUS_OFFSET: usize = 224;
+const PYTH_AGG_PUB_SLOT_OFFSET: usize = 232;
+pub const PYTH_PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+// Domain separator for prediction commitments
+pub const PREDICTION_COMMITMENT_DOMAIN: &[u8] = b"signal-wars:prediction:v1";
+
+// Domain separator for duel commitments, so a prediction commitment can't be replayed as a duel
+pub const DUEL_COMMITMENT_DOMAIN: &[u8] = b"signal-wars:duel:v1";
+
+// Duel timing: both sides reveal within DUEL_REVEAL_WINDOW of acceptance, and before the horizon
+pub const DUEL_REVEAL_WINDOW: i64 = 3600;
+pub const MAX_DUEL_ACCEPT_WINDOW: i64 = 7 * 86400;
+
+// Tournament brackets: entrants per bracket, and the entrant index marking an empty match side
+pub const MAX_TOURNAMENT_ENTRANTS: usize = 16;
+pub const BRACKET_BYE: u8 = u8::MAX;
+
+// Places tracked on each season's on-chain leaderboard
+pub const LEADERBOARD_SIZE: usize = 10;
+
+// Prediction payload bounds
+pub const MAX_PREDICTION_HORIZON: i64 = 30 * 86400;
+pub const MIN_PRICE_EXPO: i32 = -18;
+pub const MAX_PRICE_EXPO: i32 = 18;
+
+// Agent profile limits
+pub const MAX_AGENT_NAME_LEN: usize = 32;
+pub const MAX_AGENT_ENDPOINT_LEN: usize = 128;
+pub const AGENT_UPDATE_COOLDOWN: i64 = 3600;
+pub const MAX_AGENTS_PER_OWNER: usize = 16;
+pub const MAX_SUBSCRIPTION_PERIOD: i64 = 365 * 86400;
+
+// Agent delegate scopes, one per instruction the delegate may sign
+pub const DELEGATE_SUBMIT: u8 = 1 << 0; // submit_prediction
+pub const DELEGATE_REVEAL: u8 = 1 << 1; // reveal_prediction
+pub const DELEGATE_ALL: u8 = DELEGATE_SUBMIT | DELEGATE_REVEAL;
+
+// Arena pause flags, one per instruction class
+// Oracle resolution is never paused: its price window keeps running through a pause
+pub const PAUSE_ENTRIES: u8 = 1 << 0;     // enter_season, join_tournament, place_bet and subscribe
+pub const PAUSE_PREDICTIONS: u8 = 1 << 1; // submit_prediction, duel creation and acceptance
+pub const PAUSE_PAYOUTS: u8 = 1 << 2;     // manual resolution, forfeits, prize distribution, prize claims and bet settlement
+pub const PAUSE_ALL: u8 = PAUSE_ENTRIES | PAUSE_PREDICTIONS | PAUSE_PAYOUTS;
+
 #[program]
 pub mod signal_wars {
     use super::*;
 
-    /// Initialize the global arena state with treasury
+    /// Initialize the global arena state with its treasury vault
     pub fn initialize_arena(ctx: Context<InitializeArena>) -> Result<()> {
         let arena = &mut ctx.accounts.arena;
         arena.authority = ctx.accounts.authority.key();
@@ -21,7 +96,136 @@ pub mod signal_wars {
         arena.total_seasons = 0;
         arena.total_agents = 0;
         arena.total_fees_collected = 0;
+        arena.total_withdrawn = 0;
+        arena.total_refunded = 0;
+        arena.pending_authority = Pubkey::default();
+        arena.resolver = arena.authority;
+        arena.season_operator = arena.authority;
+        arena.achievement_issuer = arena.authority;
+        arena.treasurer = arena.authority;
+        arena.pause_flags = 0;
+        arena.total_duels = 0;
+        arena.duel_fee_bps = 0;
+        arena.total_tournaments = 0;
+        arena.betting_fee_bps = 0;
+        arena.subscription_fee_bps = 0;
+        arena.refunds_owed = 0;
         arena.bump = *ctx.bumps.get("arena").unwrap();
+        
+        ctx.accounts.treasury.bump = *ctx.bumps.get("treasury").unwrap();
+        Ok(())
+    }
+
+    /// Propose a new arena authority (authority only)
+    /// Takes effect once the proposed key accepts; proposing again replaces the pending key
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        require!(new_authority != Pubkey::default(), ErrorCode::InvalidAuthority);
+        
+        let arena = &mut ctx.accounts.arena;
+        arena.pending_authority = new_authority;
+        
+        emit!(AuthorityProposed {
+            authority: arena.authority,
+            pending_authority: new_authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Accept a proposed authority transfer (signed by the pending authority)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let arena = &mut ctx.accounts.arena;
+        let previous_authority = arena.authority;
+        
+        arena.authority = ctx.accounts.pending_authority.key();
+        arena.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferred {
+            previous_authority,
+            new_authority: arena.authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw a pending authority proposal (authority only)
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        let arena = &mut ctx.accounts.arena;
+        require!(
+            arena.pending_authority != Pubkey::default(),
+            ErrorCode::NoPendingAuthority
+        );
+        
+        let cancelled_authority = arena.pending_authority;
+        arena.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferCancelled {
+            authority: arena.authority,
+            cancelled_authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Assign the key holding an operational role (authority only)
+    pub fn set_role(ctx: Context<SetRole>, role: Role, key: Pubkey) -> Result<()> {
+        require!(key != Pubkey::default(), ErrorCode::InvalidAuthority);
+        
+        let arena = &mut ctx.accounts.arena;
+        let slot = match role {
+            Role::Resolver => &mut arena.resolver,
+            Role::SeasonOperator => &mut arena.season_operator,
+            Role::AchievementIssuer => &mut arena.achievement_issuer,
+            Role::Treasurer => &mut arena.treasurer,
+        };
+        let previous = *slot;
+        *slot = key;
+        
+        emit!(RoleUpdated {
+            role,
+            previous,
+            key,
+        });
+        
+        Ok(())
+    }
+
+    /// Pause or resume instruction classes (authority only)
+    /// Refunds and treasury withdrawals are never paused
+    pub fn set_pause(ctx: Context<SetPause>, pause_flags: u8) -> Result<()> {
+        require!(pause_flags & !PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags);
+        
+        let arena = &mut ctx.accounts.arena;
+        let previous = arena.pause_flags;
+        arena.pause_flags = pause_flags;
+        
+        emit!(PauseUpdated {
+            previous,
+            pause_flags,
+        });
+        
+        Ok(())
+    }
+
+    /// Set a platform fee (authority only)
+    pub fn set_fee(ctx: Context<SetFee>, kind: FeeKind, bps: u16) -> Result<()> {
+        require!(bps <= 10000, ErrorCode::InvalidFeeBps);
+        
+        let arena = &mut ctx.accounts.arena;
+        let slot = match kind {
+            FeeKind::Duel => &mut arena.duel_fee_bps,
+            FeeKind::Betting => &mut arena.betting_fee_bps,
+            FeeKind::Subscription => &mut arena.subscription_fee_bps,
+        };
+        let previous = *slot;
+        *slot = bps;
+        
+        emit!(FeeUpdated {
+            kind,
+            previous,
+            bps,
+        });
+        
         Ok(())
     }
 
@@ -31,43 +235,279 @@ pub mod signal_wars {
         name: String,
         endpoint: String,
     ) -> Result<()> {
-        require!(name.len() <= 32, ErrorCode::NameTooLong);
-        require!(endpoint.len() <= 128, ErrorCode::EndpointTooLong);
+        require!(name.len() <= MAX_AGENT_NAME_LEN, ErrorCode::NameTooLong);
+        require!(endpoint.len() <= MAX_AGENT_ENDPOINT_LEN, ErrorCode::EndpointTooLong);
 
+        let registry = &mut ctx.accounts.agent_registry;
         let agent = &mut ctx.accounts.agent;
         let arena = &mut ctx.accounts.arena;
         
+        // Fresh registry: first agent for this wallet
+        if registry.owner == Pubkey::default() {
+            registry.owner = ctx.accounts.owner.key();
+            registry.bump = *ctx.bumps.get("agent_registry").unwrap();
+        }
+        require!(
+            registry.agents.len() < MAX_AGENTS_PER_OWNER,
+            ErrorCode::TooManyAgents
+        );
+        
         agent.owner = ctx.accounts.owner.key();
+        agent.creator = ctx.accounts.owner.key();
+        agent.pending_owner = Pubkey::default();
+        agent.index = registry.next_index;
+        agent.status = AgentStatus::Active;
+        agent.retired_at = 0;
         agent.name = name;
         agent.endpoint = endpoint;
         agent.total_predictions = 0;
+        agent.predictions_submitted = 0;
         agent.correct_predictions = 0;
         agent.streak = 0;
         agent.best_streak = 0;
         agent.rank = Rank::Bronze;
         agent.reputation_score = 0;
         agent.joined_at = Clock::get()?.unix_timestamp;
+        agent.open_predictions = 0;
+        agent.last_updated_at = 0;
         agent.bump = *ctx.bumps.get("agent").unwrap();
         
+        // Indices only grow, so every agent keeps a stable PDA
+        registry.agents.push(agent.key());
+        registry.next_index = registry.next_index
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
         arena.total_agents += 1;
         
         emit!(AgentRegistered {
             agent: agent.key(),
             name: agent.name.clone(),
             owner: agent.owner,
+            index: agent.index,
+        });
+        
+        Ok(())
+    }
+
+    /// Change an agent's name and/or endpoint (owner only)
+    /// Rate limited by AGENT_UPDATE_COOLDOWN; renames wait until every open prediction settles
+    pub fn update_agent(
+        ctx: Context<UpdateAgent>,
+        name: Option<String>,
+        endpoint: Option<String>,
+    ) -> Result<()> {
+        require!(
+            name.is_some() || endpoint.is_some(),
+            ErrorCode::EmptyAgentUpdate
+        );
+        
+        let agent = &mut ctx.accounts.agent;
+        agent.require_active()?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= agent.last_updated_at + AGENT_UPDATE_COOLDOWN,
+            ErrorCode::AgentUpdateCooldown
+        );
+        
+        let old_name = agent.name.clone();
+        let old_endpoint = agent.endpoint.clone();
+        
+        if let Some(name) = name {
+            require!(name.len() <= MAX_AGENT_NAME_LEN, ErrorCode::NameTooLong);
+            // The name identifies the agent on leaderboards while its predictions are live
+            require!(agent.open_predictions == 0, ErrorCode::AgentHasOpenPredictions);
+            agent.name = name;
+        }
+        if let Some(endpoint) = endpoint {
+            require!(endpoint.len() <= MAX_AGENT_ENDPOINT_LEN, ErrorCode::EndpointTooLong);
+            agent.endpoint = endpoint;
+        }
+        agent.last_updated_at = now;
+        
+        emit!(AgentUpdated {
+            agent: agent.key(),
+            old_name,
+            new_name: agent.name.clone(),
+            old_endpoint,
+            new_endpoint: agent.endpoint.clone(),
+        });
+        
+        Ok(())
+    }
+
+    /// Grant or update a delegate key that may sign predictions for an agent (owner only)
+    /// Delegates never receive payouts; those still go to the agent owner
+    pub fn set_delegate(
+        ctx: Context<SetDelegate>,
+        key: Pubkey,
+        scopes: u8,
+        max_stake: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        ctx.accounts.agent.require_active()?;
+        require!(
+            scopes != 0 && scopes & !DELEGATE_ALL == 0,
+            ErrorCode::InvalidDelegateScopes
+        );
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            ErrorCode::InvalidDelegateExpiry
+        );
+        
+        let delegate = &mut ctx.accounts.delegate;
+        delegate.agent = ctx.accounts.agent.key();
+        delegate.key = key;
+        delegate.granted_by = ctx.accounts.owner.key();
+        delegate.scopes = scopes;
+        delegate.max_stake = max_stake;
+        delegate.expires_at = expires_at;
+        delegate.bump = *ctx.bumps.get("delegate").unwrap();
+        
+        emit!(DelegateSet {
+            agent: delegate.agent,
+            delegate: key,
+            scopes,
+            max_stake,
+            expires_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Revoke a delegate key, returning its rent to the owner
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        emit!(DelegateRevoked {
+            agent: ctx.accounts.agent.key(),
+            delegate: ctx.accounts.delegate.key,
+        });
+        
+        Ok(())
+    }
+
+    /// Offer an agent to another wallet (step 1 of 2, owner only)
+    pub fn propose_agent_transfer(
+        ctx: Context<ProposeAgentTransfer>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+        agent.require_active()?;
+        require!(
+            new_owner != Pubkey::default() && new_owner != agent.owner,
+            ErrorCode::InvalidAgentRecipient
+        );
+        
+        agent.pending_owner = new_owner;
+        
+        emit!(AgentTransferProposed {
+            agent: agent.key(),
+            owner: agent.owner,
+            pending_owner: new_owner,
+        });
+        
+        Ok(())
+    }
+
+    /// Take ownership of an offered agent (step 2 of 2, signed by the recipient)
+    /// The agent PDA is seeded by its creator, so stats, achievements and entries stay put;
+    /// only `owner` and the owners' registries change
+    pub fn accept_agent_transfer(ctx: Context<AcceptAgentTransfer>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+        require!(agent.open_predictions == 0, ErrorCode::AgentHasOpenPredictions);
+        
+        let new_registry = &mut ctx.accounts.new_registry;
+        if new_registry.owner == Pubkey::default() {
+            new_registry.owner = ctx.accounts.new_owner.key();
+            new_registry.bump = *ctx.bumps.get("new_registry").unwrap();
+        }
+        require!(
+            new_registry.agents.len() < MAX_AGENTS_PER_OWNER,
+            ErrorCode::TooManyAgents
+        );
+        new_registry.agents.push(agent.key());
+        ctx.accounts.old_registry.agents.retain(|a| *a != agent.key());
+        
+        let previous_owner = agent.owner;
+        agent.owner = ctx.accounts.new_owner.key();
+        agent.pending_owner = Pubkey::default();
+        
+        emit!(AgentTransferred {
+            agent: agent.key(),
+            previous_owner,
+            new_owner: agent.owner,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw a pending agent transfer (owner only)
+    pub fn cancel_agent_transfer(ctx: Context<CancelAgentTransfer>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+        require!(
+            agent.pending_owner != Pubkey::default(),
+            ErrorCode::NoPendingAgentTransfer
+        );
+        
+        let cancelled_owner = agent.pending_owner;
+        agent.pending_owner = Pubkey::default();
+        
+        emit!(AgentTransferCancelled {
+            agent: agent.key(),
+            cancelled_owner,
+        });
+        
+        Ok(())
+    }
+
+    /// Retire an agent for good (owner only)
+    /// The account shrinks to a tombstone keeping its track record; the endpoint's
+    /// rent goes back to the creator, who paid for the account
+    pub fn retire_agent(ctx: Context<RetireAgent>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+        agent.require_active()?;
+        require!(agent.open_predictions == 0, ErrorCode::AgentHasOpenPredictions);
+        
+        agent.status = AgentStatus::Retired;
+        agent.retired_at = Clock::get()?.unix_timestamp;
+        agent.endpoint = String::new();
+        agent.pending_owner = Pubkey::default();
+        ctx.accounts.agent_registry.agents.retain(|a| *a != agent.key());
+        
+        emit!(AgentRetired {
+            agent: agent.key(),
+            owner: agent.owner,
+            name: agent.name.clone(),
+            total_predictions: agent.total_predictions,
+            correct_predictions: agent.correct_predictions,
+            best_streak: agent.best_streak,
+            rank: agent.rank,
+            reputation_score: agent.reputation_score,
         });
         
         Ok(())
     }
 
-    /// Create a new season
+    /// Create a new season (season operator only)
     pub fn create_season(
         ctx: Context<CreateSeason>,
         entry_fee: u64,
         duration_days: u8,
         prize_pool_bps: u16, // Basis points (e.g., 9000 = 90%)
+        reveal_window: i64,  // Seconds after submission to reveal
+        forfeit_policy: ForfeitPolicy,
+        payout: PayoutSchedule,
     ) -> Result<()> {
+        let PayoutSchedule { payout_bps, remainder_policy } = payout;
         require!(prize_pool_bps <= 10000, ErrorCode::InvalidPrizeSplit);
+        require!(reveal_window > 0, ErrorCode::InvalidRevealWindow);
+        require!(
+            !payout_bps.is_empty() && payout_bps.len() <= LEADERBOARD_SIZE,
+            ErrorCode::InvalidPayoutSchedule
+        );
+        require!(
+            payout_bps.iter().all(|&bps| bps > 0)
+                && payout_bps.iter().map(|&bps| bps as u32).sum::<u32>() == 10000,
+            ErrorCode::InvalidPayoutSchedule
+        );
         
         let season = &mut ctx.accounts.season;
         let arena = &mut ctx.accounts.arena;
@@ -77,11 +517,32 @@ pub mod signal_wars {
         season.start_time = Clock::get()?.unix_timestamp;
         season.end_time = season.start_time + (duration_days as i64 * 86400);
         season.prize_pool_bps = prize_pool_bps;
+        season.reveal_window = reveal_window;
+        season.forfeit_policy = forfeit_policy;
+        season.payout_bps = [0; LEADERBOARD_SIZE];
+        season.payout_bps[..payout_bps.len()].copy_from_slice(&payout_bps);
+        season.payout_places = payout_bps.len() as u8;
+        season.remainder_policy = remainder_policy;
         season.total_entries = 0;
         season.total_pool = 0;
+        season.total_platform_fees = 0;
+        season.refund_platform_fee = false;
+        season.mint = ctx.accounts.mint.as_ref().map(|mint| mint.key()).unwrap_or_default();
         season.status = SeasonStatus::Active;
         season.bump = *ctx.bumps.get("season").unwrap();
-        season.authority = ctx.accounts.authority.key();
+        season.authority = ctx.accounts.season_operator.key();
+        
+        ctx.accounts.season_vault.bump = *ctx.bumps.get("season_vault").unwrap();
+        
+        // SPL seasons create their pool's token vault alongside the lamport vault
+        require!(
+            ctx.accounts.mint.is_some() == ctx.accounts.season_token_vault.is_some(),
+            ErrorCode::MissingTokenAccount
+        );
+        
+        let mut leaderboard = ctx.accounts.leaderboard.load_init()?;
+        leaderboard.season = season.key();
+        leaderboard.bump = *ctx.bumps.get("leaderboard").unwrap();
         
         arena.total_seasons += 1;
         
@@ -90,6 +551,7 @@ pub mod signal_wars {
             entry_fee,
             start_time: season.start_time,
             end_time: season.end_time,
+            mint: season.mint,
         });
         
         Ok(())
@@ -102,6 +564,8 @@ pub mod signal_wars {
         let agent = &mut ctx.accounts.agent;
         let arena = &mut ctx.accounts.arena;
         
+        arena.require_unpaused(PAUSE_ENTRIES)?;
+        agent.require_active()?;
         require!(season.status == SeasonStatus::Active, ErrorCode::SeasonNotActive);
         require!(
             Clock::get()?.unix_timestamp < season.end_time,
@@ -112,27 +576,54 @@ pub mod signal_wars {
         // If entry already exists, the init constraint will fail
         
         // Calculate fee split
-        let platform_fee = (season.entry_fee * (10000 - season.prize_pool_bps) as u64) / 10000;
-        let prize_contribution = season.entry_fee - platform_fee;
-        
-        // Transfer entry fee from player
-        let transfer_amount = season.entry_fee;
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.player.to_account_info(),
-                    to: ctx.accounts.season_vault.to_account_info(),
-                },
-            ),
-            transfer_amount,
-        )?;
+        let mut platform_fee = (season.entry_fee * (10000 - season.prize_pool_bps) as u64) / 10000;
+        let mut prize_contribution = season.entry_fee - platform_fee;
         
-        // Move platform fee to treasury
-        if platform_fee > 0 {
-            **ctx.accounts.season_vault.to_account_info().lamports.borrow_mut() -= platform_fee;
-            **ctx.accounts.treasury.to_account_info().lamports.borrow_mut() += platform_fee;
-            arena.total_fees_collected += platform_fee;
+        if season.mint == Pubkey::default() {
+            // Transfer entry fee from player
+            let transfer_amount = season.entry_fee;
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.player.to_account_info(),
+                        to: ctx.accounts.season_vault.to_account_info(),
+                    },
+                ),
+                transfer_amount,
+            )?;
+            
+            // Move platform fee to treasury
+            if platform_fee > 0 {
+                **ctx.accounts.season_vault.to_account_info().lamports.borrow_mut() -= platform_fee;
+                **ctx.accounts.treasury.to_account_info().lamports.borrow_mut() += platform_fee;
+                arena.total_fees_collected += platform_fee;
+            }
+        } else {
+            // SPL season: pay each share straight into its token vault, recording
+            // what the vaults received net of any Token-2022 transfer fee
+            let token = SeasonToken::new(&ctx.accounts.token_program, &ctx.accounts.mint)?;
+            let player_token_account = required(&ctx.accounts.player_token_account)?.to_account_info();
+            let player = ctx.accounts.player.to_account_info();
+            
+            if prize_contribution > 0 {
+                prize_contribution = token.transfer(
+                    &player_token_account,
+                    &required(&ctx.accounts.season_token_vault)?.to_account_info(),
+                    &player,
+                    &[],
+                    prize_contribution,
+                )?;
+            }
+            if platform_fee > 0 {
+                platform_fee = token.transfer(
+                    &player_token_account,
+                    &required(&ctx.accounts.treasury_token_vault)?.to_account_info(),
+                    &player,
+                    &[],
+                    platform_fee,
+                )?;
+            }
         }
         
         entry.season_id = season.id;
@@ -142,10 +633,15 @@ pub mod signal_wars {
         entry.predictions_made = 0;
         entry.predictions_correct = 0;
         entry.rank = 0;
+        entry.prize_contribution = prize_contribution;
+        entry.platform_fee = platform_fee;
+        entry.refunded = false;
+        entry.rent_payer = ctx.accounts.player.key();
         entry.bump = *ctx.bumps.get("season_entry").unwrap();
         
         season.total_entries += 1;
         season.total_pool += prize_contribution;
+        season.total_platform_fees += platform_fee;
         
         emit!(SeasonEntered {
             season_id: season.id,
@@ -165,27 +661,23 @@ pub mod signal_wars {
         stake_amount: u64,
     ) -> Result<()> {
         let season = &ctx.accounts.season;
+        ctx.accounts.arena.require_unpaused(PAUSE_PREDICTIONS)?;
+        ctx.accounts.agent.require_active()?;
         require!(season.status == SeasonStatus::Active, ErrorCode::SeasonNotActive);
         
-        // Verify player owns the agent
-        require!(
-            ctx.accounts.agent.owner == ctx.accounts.player.key(),
-            ErrorCode::UnauthorizedAgent
-        );
-        
-        let prediction = &mut ctx.accounts.prediction;
-        let agent = &mut ctx.accounts.agent;
-        
-        prediction.agent = agent.key();
-        prediction.season_id = season.id;
-        prediction.prediction_hash = prediction_hash;
-        prediction.stake_amount = stake_amount;
-        prediction.submitted_at = Clock::get()?.unix_timestamp;
-        prediction.status = PredictionStatus::Committed;
-        prediction.bump = *ctx.bumps.get("prediction").unwrap();
+        // Signer is the agent owner or a delegate scoped for submissions
+        if ctx.accounts.agent.owner != ctx.accounts.player.key() {
+            let delegate = ctx.accounts.delegate.as_ref().ok_or(ErrorCode::UnauthorizedAgent)?;
+            delegate.authorize(&ctx.accounts.agent, DELEGATE_SUBMIT)?;
+            require!(
+                stake_amount <= delegate.max_stake,
+                ErrorCode::DelegateStakeCapExceeded
+            );
+        }
         
         // Transfer stake to program-owned vault using CPI
-        if stake_amount > 0 {
+        let mut stake_amount = stake_amount;
+        if stake_amount > 0 && season.mint == Pubkey::default() {
             anchor_lang::system_program::transfer(
                 CpiContext::new(
                     ctx.accounts.system_program.to_account_info(),
@@ -196,8 +688,38 @@ pub mod signal_wars {
                 ),
                 stake_amount,
             )?;
+        } else if stake_amount > 0 {
+            // The stake is what the vault received, net of any transfer fee
+            let token = SeasonToken::new(&ctx.accounts.token_program, &ctx.accounts.mint)?;
+            stake_amount = token.transfer(
+                &required(&ctx.accounts.player_token_account)?.to_account_info(),
+                &required(&ctx.accounts.prediction_token_vault)?.to_account_info(),
+                &ctx.accounts.player.to_account_info(),
+                &[],
+                stake_amount,
+            )?;
         }
         
+        let prediction = &mut ctx.accounts.prediction;
+        let agent = &mut ctx.accounts.agent;
+        
+        prediction.agent = agent.key();
+        prediction.season_id = season.id;
+        prediction.season = season.key();
+        prediction.prediction_hash = prediction_hash;
+        prediction.stake_amount = stake_amount;
+        prediction.mint = season.mint;
+        prediction.rent_payer = ctx.accounts.player.key();
+        prediction.betting_pool = Pubkey::default();
+        prediction.submitted_at = Clock::get()?.unix_timestamp;
+        prediction.reveal_deadline = prediction.submitted_at + season.reveal_window;
+        prediction.status = PredictionStatus::Committed;
+        prediction.bump = *ctx.bumps.get("prediction").unwrap();
+        
+        // Advance the seed nonce so the agent's next prediction gets a fresh PDA
+        agent.predictions_submitted += 1;
+        agent.open_predictions += 1;
+        
         emit!(PredictionSubmitted {
             prediction: prediction.key(),
             agent: agent.key(),
@@ -208,114 +730,428 @@ pub mod signal_wars {
         Ok(())
     }
 
-    /// Reveal prediction and verify
+    /// Reveal prediction and verify against the salted commitment
+    /// (see `prediction_commitment` for the scheme)
+    /// The reveal must land before the horizon, so the price there can still be cranked
     pub fn reveal_prediction(
         ctx: Context<RevealPrediction>,
-        prediction_data: String,
+        payload: PredictionPayload,
+        nonce: [u8; 32],
     ) -> Result<()> {
         let prediction = &mut ctx.accounts.prediction;
+        let now = Clock::get()?.unix_timestamp;
         require!(
             prediction.status == PredictionStatus::Committed,
             ErrorCode::InvalidPredictionStatus
         );
+        require!(now <= prediction.reveal_deadline, ErrorCode::RevealWindowClosed);
         
-        // Verify player owns the prediction (via agent ownership)
-        require!(
-            ctx.accounts.agent.owner == ctx.accounts.player.key(),
-            ErrorCode::UnauthorizedPrediction
-        );
+        // Verify player owns the prediction (via agent ownership) or is a reveal delegate
+        if ctx.accounts.agent.owner != ctx.accounts.player.key() {
+            let delegate = ctx.accounts.delegate.as_ref().ok_or(ErrorCode::UnauthorizedPrediction)?;
+            delegate.authorize(&ctx.accounts.agent, DELEGATE_REVEAL)?;
+        }
         
         // Verify hash matches
-        let computed_hash = hash(prediction_data.as_bytes());
+        let computed_hash =
+            prediction_commitment(&prediction.agent, &prediction.season, &nonce, &payload);
         require!(
-            computed_hash.to_bytes() == prediction.prediction_hash,
+            computed_hash == prediction.prediction_hash,
             ErrorCode::HashMismatch
         );
-        
-        prediction.prediction_data = prediction_data;
-        prediction.revealed_at = Clock::get()?.unix_timestamp;
+
+        payload.validate()?;
+
+        let resolve_after = prediction
+            .submitted_at
+            .checked_add(payload.horizon)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(now < resolve_after, ErrorCode::RevealWindowClosed);
+
+        prediction.asset_id = payload.asset_id;
+        prediction.direction = payload.direction;
+        prediction.target_price = payload.target_price;
+        prediction.target_expo = payload.target_expo;
+        prediction.confidence_bps = payload.confidence_bps;
+        prediction.resolve_after = resolve_after;
+        prediction.revealed_at = now;
         prediction.status = PredictionStatus::Revealed;
         
         emit!(PredictionRevealed {
             prediction: prediction.key(),
             agent: prediction.agent,
+            asset_id: payload.asset_id,
+            direction: payload.direction,
+            target_price: payload.target_price,
+            target_expo: payload.target_expo,
+            resolve_after: prediction.resolve_after,
+            confidence_bps: payload.confidence_bps,
         });
         
         Ok(())
     }
 
-    /// Resolve prediction (called by oracle/authority)
+    /// Resolve prediction (called by the arena resolver)
+    /// Only for assets without a registered price feed; those resolve from the oracle
     /// Fee structure: Wrong prediction = stake goes to treasury
     pub fn resolve_prediction(
         ctx: Context<ResolvePrediction>,
         was_correct: bool,
     ) -> Result<()> {
-        let prediction = &mut ctx.accounts.prediction;
-        let agent = &mut ctx.accounts.agent;
-        let entry = &mut ctx.accounts.season_entry;
-        let arena = &mut ctx.accounts.arena;
-        
+        ctx.accounts.arena.require_unpaused(PAUSE_PAYOUTS)?;
         require!(
-            prediction.status == PredictionStatus::Revealed,
-            ErrorCode::InvalidPredictionStatus
+            ctx.accounts.asset_feed.data_is_empty(),
+            ErrorCode::AssetHasPriceFeed
         );
-        
-        prediction.was_correct = was_correct;
-        prediction.status = PredictionStatus::Resolved;
-        prediction.resolved_at = Clock::get()?.unix_timestamp;
-        
-        agent.total_predictions += 1;
-        entry.predictions_made += 1;
-        
-        if was_correct {
-            agent.correct_predictions += 1;
-            agent.streak += 1;
-            entry.predictions_correct += 1;
-            entry.score += calculate_score(prediction.stake_amount, agent.streak);
-            
-            if agent.streak > agent.best_streak {
-                agent.best_streak = agent.streak;
-            }
-            
-            update_rank(agent)?;
-            
-            // Return stake to player on correct prediction
-            let stake_return = prediction.stake_amount;
-            if stake_return > 0 {
-                **ctx.accounts.prediction_vault.to_account_info().lamports.borrow_mut() -= stake_return;
-                **ctx.accounts.player.to_account_info().lamports.borrow_mut() += stake_return;
-            }
-        } else {
-            // Wrong prediction: stake goes to treasury
-            agent.streak = 0;
-            let lost_stake = prediction.stake_amount;
-            if lost_stake > 0 {
-                **ctx.accounts.prediction_vault.to_account_info().lamports.borrow_mut() -= lost_stake;
-                **ctx.accounts.treasury.to_account_info().lamports.borrow_mut() += lost_stake;
-                arena.total_fees_collected += lost_stake;
-            }
-        }
-        
-        emit!(PredictionResolved {
-            prediction: prediction.key(),
-            agent: agent.key(),
+        let stake = ctx.accounts.stake_accounts()?;
+
+        settle_prediction(
+            &mut ctx.accounts.prediction,
+            &mut ctx.accounts.agent,
+            &mut ctx.accounts.season_entry,
+            &mut ctx.accounts.arena,
+            &ctx.accounts.leaderboard,
+            stake,
             was_correct,
-            score_earned: if was_correct { entry.score } else { 0 },
-            stake_transferred: if was_correct { prediction.stake_amount } else { 0 },
-        });
-        
-        Ok(())
+        )
     }
 
-    /// Award achievement badge
-    pub fn award_achievement(
-        ctx: Context<AwardAchievement>,
-        achievement_type: AchievementType,
-    ) -> Result<()> {
-        let agent = &mut ctx.accounts.agent;
+    /// Resolve a revealed prediction against its asset's price feed
+    /// Permissionless: anyone can crank once the prediction horizon has passed
+    /// Not pausable: the horizon's price window keeps running through a pause
+    pub fn resolve_prediction_with_oracle(ctx: Context<ResolvePredictionWithOracle>) -> Result<()> {
+        let prediction = &ctx.accounts.prediction;
+        let asset_feed = &ctx.accounts.asset_feed;
+
+        require!(
+            prediction.status == PredictionStatus::Revealed,
+            ErrorCode::InvalidPredictionStatus
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= prediction.resolve_after, ErrorCode::HorizonNotReached);
+
+        let price = load_horizon_price(
+            &ctx.accounts.price_feed,
+            asset_feed,
+            prediction.resolve_after,
+        )?;
+
+        let was_correct = price_target_reached(
+            prediction.direction,
+            price.price,
+            price.expo,
+            prediction.target_price,
+            prediction.target_expo,
+        )?;
+
+        emit!(OracleResolution {
+            prediction: prediction.key(),
+            price_feed: ctx.accounts.price_feed.key(),
+            price: price.price,
+            expo: price.expo,
+            publish_time: price.publish_time,
+        });
+
+        let stake = ctx.accounts.stake_accounts()?;
+
+        settle_prediction(
+            &mut ctx.accounts.prediction,
+            &mut ctx.accounts.agent,
+            &mut ctx.accounts.season_entry,
+            &mut ctx.accounts.arena,
+            &ctx.accounts.leaderboard,
+            stake,
+            was_correct,
+        )
+    }
+
+    /// Void a revealed prediction whose horizon price was never cranked in time
+    /// Permissionless once `resolve_after + max_staleness` has passed and the feed holds
+    /// no price inside that window: the stake goes back to the player and the prediction
+    /// counts as neither a hit nor a miss
+    pub fn void_prediction(ctx: Context<VoidPrediction>) -> Result<()> {
+        let prediction = &mut ctx.accounts.prediction;
+        require!(
+            prediction.status == PredictionStatus::Revealed,
+            ErrorCode::InvalidPredictionStatus
+        );
+        require!(
+            horizon_window_missed(
+                &ctx.accounts.price_feed,
+                &ctx.accounts.asset_feed,
+                prediction.resolve_after,
+            )?,
+            ErrorCode::ResolutionWindowOpen
+        );
+        let now = Clock::get()?.unix_timestamp;
+
+        // Expired predictions void their betting pools and can be closed
+        prediction.status = PredictionStatus::Expired;
+        prediction.was_correct = false;
+        prediction.resolved_at = now;
+        ctx.accounts.agent.open_predictions -= 1;
+
+        let stake = prediction.stake_amount;
+        if stake > 0 {
+            if prediction.mint == Pubkey::default() {
+                pay_from_vault(
+                    &ctx.accounts.prediction_vault.to_account_info(),
+                    &ctx.accounts.player,
+                    stake,
+                    None,
+                )?;
+            } else {
+                let signer = TokenVaultSigner::new(
+                    &ctx.accounts.token_program,
+                    &ctx.accounts.mint,
+                    &ctx.accounts.arena,
+                )?;
+                pay_from_vault(
+                    &required(&ctx.accounts.prediction_token_vault)?.to_account_info(),
+                    &required(&ctx.accounts.player_token_account)?.to_account_info(),
+                    stake,
+                    Some(&signer),
+                )?;
+            }
+        }
+
+        emit!(PredictionVoided {
+            prediction: prediction.key(),
+            agent: prediction.agent,
+            stake_amount: stake,
+        });
+
+        Ok(())
+    }
+
+    /// Refresh an entry's rank from the season leaderboard
+    /// Permissionless: places shift as other entries score
+    pub fn sync_entry_rank(ctx: Context<SyncEntryRank>) -> Result<()> {
+        let entry = &mut ctx.accounts.season_entry;
+        entry.rank = ctx.accounts.leaderboard.load()?.place_of(&entry.key());
+        Ok(())
+    }
+
+    /// Point an active season's entry (and its leaderboard standing) at the agent's
+    /// current owner after a transfer, so stakes and prizes follow the agent
+    /// Permissionless: the entry can only move to the agent's owner
+    pub fn sync_entry_owner(ctx: Context<SyncEntryOwner>) -> Result<()> {
+        require!(
+            ctx.accounts.season.status == SeasonStatus::Active,
+            ErrorCode::SeasonNotActive
+        );
+        
+        let entry = &mut ctx.accounts.season_entry;
+        entry.player = ctx.accounts.agent.owner;
+        ctx.accounts
+            .leaderboard
+            .load_mut()?
+            .set_player(&entry.key(), entry.player);
+        Ok(())
+    }
+
+    /// Register the price feed account used to resolve predictions on an asset
+    pub fn register_asset_feed(
+        ctx: Context<RegisterAssetFeed>,
+        asset_id: [u8; 8],
+        price_feed: Pubkey,
+        max_staleness: i64,
+    ) -> Result<()> {
+        require!(max_staleness > 0, ErrorCode::InvalidStaleness);
+
+        let asset_feed = &mut ctx.accounts.asset_feed;
+        asset_feed.asset_id = asset_id;
+        asset_feed.price_feed = price_feed;
+        asset_feed.max_staleness = max_staleness;
+        asset_feed.bump = *ctx.bumps.get("asset_feed").unwrap();
+
+        emit!(AssetFeedRegistered {
+            asset_id,
+            price_feed,
+            max_staleness,
+        });
+
+        Ok(())
+    }
+
+    /// Repoint an asset at a new price feed or staleness window (authority only)
+    /// Applies to every open prediction on the asset, including its void deadline
+    pub fn update_asset_feed(
+        ctx: Context<UpdateAssetFeed>,
+        _asset_id: [u8; 8],
+        price_feed: Pubkey,
+        max_staleness: i64,
+    ) -> Result<()> {
+        require!(max_staleness > 0, ErrorCode::InvalidStaleness);
+
+        let asset_feed = &mut ctx.accounts.asset_feed;
+        asset_feed.price_feed = price_feed;
+        asset_feed.max_staleness = max_staleness;
+
+        emit!(AssetFeedUpdated {
+            asset_id: asset_feed.asset_id,
+            price_feed,
+            max_staleness,
+        });
+
+        Ok(())
+    }
+
+    /// Create a program-owned price account in the Pyth layout (localnet/testing)
+    /// Rejected unless the program is built with the `mock-oracle` feature
+    pub fn init_mock_price_feed(
+        ctx: Context<InitMockPriceFeed>,
+        asset_id: [u8; 8],
+        expo: i32,
+    ) -> Result<()> {
+        require!(cfg!(feature = "mock-oracle"), ErrorCode::MockOracleDisabled);
+        let bump = *ctx.bumps.get("mock_price_feed").unwrap();
+        let signer_seeds: &[&[u8]] = &[b"mock_price_feed", asset_id.as_ref(), &[bump]];
+
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.mock_price_feed.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            Rent::get()?.minimum_balance(PYTH_PRICE_ACCOUNT_MIN_LEN),
+            PYTH_PRICE_ACCOUNT_MIN_LEN as u64,
+            &crate::ID,
+        )?;
+
+        let mut data = ctx.accounts.mock_price_feed.try_borrow_mut_data()?;
+        data[0..4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&PYTH_VERSION_2.to_le_bytes());
+        data[8..12].copy_from_slice(&PYTH_ACCOUNT_TYPE_PRICE.to_le_bytes());
+        data[12..16].copy_from_slice(&(PYTH_PRICE_ACCOUNT_MIN_LEN as u32).to_le_bytes());
+        data[PYTH_EXPO_OFFSET..PYTH_EXPO_OFFSET + 4].copy_from_slice(&expo.to_le_bytes());
+
+        Ok(())
+    }
+
+    /// Publish a new aggregate price on a mock price account
+    pub fn set_mock_price(
+        ctx: Context<SetMockPrice>,
+        _asset_id: [u8; 8],
+        price: i64,
+        conf: u64,
+    ) -> Result<()> {
+        require!(cfg!(feature = "mock-oracle"), ErrorCode::MockOracleDisabled);
+        let clock = Clock::get()?;
+        let mut data = ctx.accounts.mock_price_feed.try_borrow_mut_data()?;
+        require!(data.len() >= PYTH_PRICE_ACCOUNT_MIN_LEN, ErrorCode::InvalidPriceFeed);
+
+        data[PYTH_TIMESTAMP_OFFSET..PYTH_TIMESTAMP_OFFSET + 8]
+            .copy_from_slice(&clock.unix_timestamp.to_le_bytes());
+        data[PYTH_AGG_PRICE_OFFSET..PYTH_AGG_PRICE_OFFSET + 8].copy_from_slice(&price.to_le_bytes());
+        data[PYTH_AGG_CONF_OFFSET..PYTH_AGG_CONF_OFFSET + 8].copy_from_slice(&conf.to_le_bytes());
+        data[PYTH_AGG_STATUS_OFFSET..PYTH_AGG_STATUS_OFFSET + 4]
+            .copy_from_slice(&PYTH_STATUS_TRADING.to_le_bytes());
+        data[PYTH_AGG_PUB_SLOT_OFFSET..PYTH_AGG_PUB_SLOT_OFFSET + 8]
+            .copy_from_slice(&clock.slot.to_le_bytes());
+
+        Ok(())
+    }
+
+    /// Move a season's end time (localnet/testing), so tests can end a season with entrants
+    /// Rejected unless the program is built with the `mock-clock` feature
+    pub fn set_mock_season_end(ctx: Context<SetMockSeasonEnd>, end_time: i64) -> Result<()> {
+        require!(cfg!(feature = "mock-clock"), ErrorCode::MockClockDisabled);
+        ctx.accounts.season.end_time = end_time;
+        Ok(())
+    }
+
+    /// Expire a prediction that was not revealed before its reveal deadline
+    /// Permissionless; counts as a miss and moves the stake per the season's forfeit policy
+    pub fn forfeit_prediction(ctx: Context<ForfeitPrediction>) -> Result<()> {
+        let prediction = &mut ctx.accounts.prediction;
+        let agent = &mut ctx.accounts.agent;
+        let entry = &mut ctx.accounts.season_entry;
+        let season = &mut ctx.accounts.season;
+        let arena = &mut ctx.accounts.arena;
+
+        arena.require_unpaused(PAUSE_PAYOUTS)?;
+        require!(
+            prediction.status == PredictionStatus::Committed,
+            ErrorCode::InvalidPredictionStatus
+        );
+        require!(
+            Clock::get()?.unix_timestamp > prediction.reveal_deadline,
+            ErrorCode::RevealWindowOpen
+        );
+
+        prediction.status = PredictionStatus::Expired;
+        prediction.was_correct = false;
+        prediction.resolved_at = Clock::get()?.unix_timestamp;
+
+        // Unrevealed prediction counts as a miss
+        agent.total_predictions += 1;
+        agent.open_predictions -= 1;
+        agent.streak = 0;
+        entry.predictions_made += 1;
+
+        let stake = prediction.stake_amount;
+        if stake > 0 {
+            let native = prediction.mint == Pubkey::default();
+            let (vault, signer) = if native {
+                (ctx.accounts.prediction_vault.to_account_info(), None)
+            } else {
+                (
+                    required(&ctx.accounts.prediction_token_vault)?.to_account_info(),
+                    Some(TokenVaultSigner::new(&ctx.accounts.token_program, &ctx.accounts.mint, arena)?),
+                )
+            };
+            match season.forfeit_policy {
+                // Only a live season's pool still pays out
+                ForfeitPolicy::PrizePool if season.status == SeasonStatus::Active => {
+                    let pool = if native {
+                        ctx.accounts.season_vault.to_account_info()
+                    } else {
+                        required(&ctx.accounts.season_token_vault)?.to_account_info()
+                    };
+                    // The pool grows by what it received, net of any transfer fee
+                    season.total_pool += pay_from_vault(&vault, &pool, stake, signer.as_ref())?;
+                }
+                _ => {
+                    let treasury = if native {
+                        ctx.accounts.treasury.to_account_info()
+                    } else {
+                        required(&ctx.accounts.treasury_token_vault)?.to_account_info()
+                    };
+                    pay_from_vault(&vault, &treasury, stake, signer.as_ref())?;
+                    if native {
+                        arena.total_fees_collected += stake;
+                    }
+                }
+            }
+        }
+
+        emit!(PredictionForfeited {
+            prediction: prediction.key(),
+            agent: agent.key(),
+            season_id: season.id,
+            stake_amount: stake,
+            forfeit_policy: season.forfeit_policy,
+        });
+
+        Ok(())
+    }
+
+    /// Award achievement badge (achievement issuer only)
+    pub fn award_achievement(
+        ctx: Context<AwardAchievement>,
+        achievement_type: AchievementType,
+    ) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
         let achievement = &mut ctx.accounts.achievement;
+        agent.require_active()?;
         
         achievement.agent = agent.key();
+        achievement.rent_payer = ctx.accounts.achievement_issuer.key();
         achievement.achievement_type = achievement_type;
         achievement.awarded_at = Clock::get()?.unix_timestamp;
         achievement.bump = *ctx.bumps.get("achievement").unwrap();
@@ -340,12 +1176,22 @@ pub mod signal_wars {
         Ok(())
     }
 
-    /// Distribute prizes at season end to top 3 performers
-    /// Prize distribution: 1st = 50%, 2nd = 30%, 3rd = 20% of prize pool
-    pub fn distribute_prizes(ctx: Context<DistributePrizes>) -> Result<()> {
+    /// Distribute prizes at season end to the leaderboard's paid places
+    /// Place i receives `payout_bps[i]` of the prize pool; when fewer entries are placed
+    /// than the schedule pays, the season's `RemainderPolicy` either redistributes the
+    /// unpaid shares across the paid places or sends them to the treasury
+    /// Recipients are passed as remaining accounts, one per paid place, in leaderboard order
+    pub fn distribute_prizes<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributePrizes<'info>>,
+    ) -> Result<()> {
         let season = &mut ctx.accounts.season;
-        let _arena = &mut ctx.accounts.arena;
+        let arena = &mut ctx.accounts.arena;
         
+        arena.require_unpaused(PAUSE_PAYOUTS)?;
+        require!(
+            season.status != SeasonStatus::Cancelled,
+            ErrorCode::SeasonCancelled
+        );
         require!(
             Clock::get()?.unix_timestamp >= season.end_time,
             ErrorCode::SeasonNotEnded
@@ -355,338 +1201,3230 @@ pub mod signal_wars {
             ErrorCode::InvalidSeasonStatus
         );
         
-        // Validate winners have season entries for this season
-        require!(
-            ctx.accounts.first_place_entry.season_id == season.id,
-            ErrorCode::InvalidWinner
-        );
-        require!(
-            ctx.accounts.second_place_entry.season_id == season.id,
-            ErrorCode::InvalidWinner
-        );
+        // Pay from the program-maintained standings; seasons with fewer placed
+        // entries than paid places settle the unpaid shares per the remainder policy
+        let leaderboard = ctx.accounts.leaderboard.load()?;
+        let winners = (leaderboard.count as usize).min(season.payout_places as usize);
         require!(
-            ctx.accounts.third_place_entry.season_id == season.id,
-            ErrorCode::InvalidWinner
+            ctx.remaining_accounts.len() == winners,
+            ErrorCode::InvalidRecipientCount
         );
+        let native = season.mint == Pubkey::default();
+        let (season_vault, treasury, signer) = if native {
+            (
+                ctx.accounts.season_vault.to_account_info(),
+                ctx.accounts.treasury.to_account_info(),
+                None,
+            )
+        } else {
+            (
+                required(&ctx.accounts.season_token_vault)?.to_account_info(),
+                required(&ctx.accounts.treasury_token_vault)?.to_account_info(),
+                Some(TokenVaultSigner::new(&ctx.accounts.token_program, &ctx.accounts.mint, arena)?),
+            )
+        };
         
-        // Validate winners are in descending score order
-        require!(
-            ctx.accounts.first_place_entry.score >= ctx.accounts.second_place_entry.score,
-            ErrorCode::InvalidWinnerOrder
-        );
+        // SPL seasons pay each winner's token account for the season mint
+        for (standing, recipient) in leaderboard.entries.iter().zip(ctx.remaining_accounts) {
+            let recipient_owner = match &signer {
+                None => recipient.key(),
+                Some(signer) => token_account_for_mint(recipient, &signer.token)?.owner,
+            };
+            require_keys_eq!(
+                recipient_owner,
+                standing.player,
+                ErrorCode::PrizeRecipientMismatch
+            );
+        }
+        drop(leaderboard);
+        
+        let prize_pool = season.total_pool;
+        let (prizes, to_treasury) = season.prize_split(winners);
+        
+        // Transfer prizes to winners
+        for (&prize, recipient) in prizes.iter().zip(ctx.remaining_accounts) {
+            if prize > 0 {
+                pay_from_vault(&season_vault, recipient, prize, signer.as_ref())?;
+            }
+        }
+        
+        if to_treasury > 0 {
+            pay_from_vault(&season_vault, &treasury, to_treasury, signer.as_ref())?;
+            if native {
+                arena.total_fees_collected += to_treasury;
+            }
+        }
+        
+        season.status = SeasonStatus::Completed;
+        
+        emit!(PrizesDistributed {
+            season_id: season.id,
+            total_pool: prize_pool,
+            prizes,
+            to_treasury,
+        });
+        
+        Ok(())
+    }
+
+    /// Cancel a season that has not been distributed (season operator only)
+    /// Entrants then reclaim their fees with `claim_refund`
+    pub fn cancel_season(ctx: Context<CancelSeason>, refund_platform_fee: bool) -> Result<()> {
+        let season = &mut ctx.accounts.season;
         require!(
-            ctx.accounts.second_place_entry.score >= ctx.accounts.third_place_entry.score,
-            ErrorCode::InvalidWinnerOrder
+            season.status == SeasonStatus::Active,
+            ErrorCode::InvalidSeasonStatus
         );
         
-        // Validate winners have actually participated (made predictions)
+        season.status = SeasonStatus::Cancelled;
+        season.refund_platform_fee = refund_platform_fee;
+        // Reserve the fees entrants can reclaim so the treasurer can't withdraw them
+        if refund_platform_fee && season.mint == Pubkey::default() {
+            ctx.accounts.arena.refunds_owed += season.total_platform_fees;
+        } else if refund_platform_fee {
+            required_mut(&mut ctx.accounts.treasury_token_ledger)?.refunds_owed +=
+                season.total_platform_fees;
+        }
+        
+        emit!(SeasonCancelled {
+            season_id: season.id,
+            total_pool: season.total_pool,
+            total_entries: season.total_entries,
+            refund_platform_fee,
+        });
+        
+        Ok(())
+    }
+
+    /// Refund an entry of a cancelled season to its player
+    /// Permissionless: funds can only go to the entrant
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        let season = &mut ctx.accounts.season;
+        let entry = &mut ctx.accounts.season_entry;
+        
         require!(
-            ctx.accounts.first_place_entry.predictions_made > 0,
-            ErrorCode::InvalidWinner
+            season.status == SeasonStatus::Cancelled,
+            ErrorCode::SeasonNotCancelled
         );
+        require!(!entry.refunded, ErrorCode::AlreadyRefunded);
         
-        let prize_pool = season.total_pool;
+        let native = season.mint == Pubkey::default();
+        let (season_vault, treasury, player, signer) = if native {
+            (
+                ctx.accounts.season_vault.to_account_info(),
+                ctx.accounts.treasury.to_account_info(),
+                ctx.accounts.player.to_account_info(),
+                None,
+            )
+        } else {
+            (
+                required(&ctx.accounts.season_token_vault)?.to_account_info(),
+                required(&ctx.accounts.treasury_token_vault)?.to_account_info(),
+                required(&ctx.accounts.player_token_account)?.to_account_info(),
+                Some(TokenVaultSigner::new(&ctx.accounts.token_program, &ctx.accounts.mint, &ctx.accounts.arena)?),
+            )
+        };
         
-        // Prize split: 50% / 30% / 20%
-        let first_place_prize = prize_pool * 50 / 100;
-        let second_place_prize = prize_pool * 30 / 100;
-        let third_place_prize = prize_pool * 20 / 100;
+        let from_pool = entry.prize_contribution;
+        if from_pool > 0 {
+            pay_from_vault(&season_vault, &player, from_pool, signer.as_ref())?;
+            season.total_pool -= from_pool;
+        }
         
-        // Transfer prizes to winners
-        if first_place_prize > 0 {
-            **ctx.accounts.season_vault.to_account_info().lamports.borrow_mut() -= first_place_prize;
-            **ctx.accounts.first_place.to_account_info().lamports.borrow_mut() += first_place_prize;
+        let platform_fee = if season.refund_platform_fee { entry.platform_fee } else { 0 };
+        if platform_fee > 0 {
+            pay_from_vault(&treasury, &player, platform_fee, signer.as_ref())?;
+            if native {
+                let arena = &mut ctx.accounts.arena;
+                arena.total_refunded += platform_fee;
+                arena.refunds_owed -= platform_fee;
+            } else {
+                let ledger = required_mut(&mut ctx.accounts.treasury_token_ledger)?;
+                ledger.total_refunded += platform_fee;
+                ledger.refunds_owed -= platform_fee;
+            }
         }
         
-        if second_place_prize > 0 {
-            **ctx.accounts.season_vault.to_account_info().lamports.borrow_mut() -= second_place_prize;
-            **ctx.accounts.second_place.to_account_info().lamports.borrow_mut() += second_place_prize;
+        entry.refunded = true;
+        
+        emit!(RefundClaimed {
+            season_id: season.id,
+            agent: entry.agent,
+            player: entry.player,
+            prize_contribution: from_pool,
+            platform_fee,
+        });
+        
+        Ok(())
+    }
+
+    /// Close a settled prediction and its vaults (agent owner only)
+    /// Rent goes to whoever paid it at submission; SPL predictions with a stake
+    /// must pass their token vault so it is closed too, and Token-2022 vaults holding
+    /// withheld transfer fees must pass the mint so the fees can be harvested first
+    pub fn close_prediction(ctx: Context<ClosePrediction>) -> Result<()> {
+        let prediction = &ctx.accounts.prediction;
+        require!(
+            prediction.status == PredictionStatus::Resolved
+                || prediction.status == PredictionStatus::Expired,
+            ErrorCode::PredictionNotSettled
+        );
+        // Bettors settle against the prediction, so it must outlive its pool's settlement
+        if prediction.betting_pool != Pubkey::default() {
+            let settled = ctx
+                .accounts
+                .betting_pool
+                .as_ref()
+                .is_some_and(|pool| pool.outcome != BetOutcome::Pending);
+            require!(settled, ErrorCode::BettingPoolUnsettled);
         }
         
-        if third_place_prize > 0 {
-            **ctx.accounts.season_vault.to_account_info().lamports.borrow_mut() -= third_place_prize;
-            **ctx.accounts.third_place.to_account_info().lamports.borrow_mut() += third_place_prize;
+        if let Some(vault) = &ctx.accounts.prediction_token_vault {
+            let token_program = required(&ctx.accounts.token_program)?.to_account_info();
+            if withheld_transfer_fees(&vault.to_account_info())? > 0 {
+                harvest_withheld_fees(
+                    &token_program,
+                    &required(&ctx.accounts.mint)?.to_account_info(),
+                    &vault.to_account_info(),
+                )?;
+            }
+            let arena_seeds: &[&[u8]] = &[b"arena", &[ctx.accounts.arena.bump]];
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program,
+                token_interface::CloseAccount {
+                    account: vault.to_account_info(),
+                    destination: ctx.accounts.rent_payer.to_account_info(),
+                    authority: ctx.accounts.arena.to_account_info(),
+                },
+                &[arena_seeds],
+            ))?;
+        } else if prediction.mint != Pubkey::default() && prediction.stake_amount > 0 {
+            return err!(ErrorCode::MissingTokenAccount);
         }
         
-        season.status = SeasonStatus::Completed;
+        Ok(())
+    }
+
+    /// Close a finished season entry (agent owner only)
+    /// The season must be completed, or cancelled with the refund claimed, and the
+    /// agent may have no open predictions left to resolve against the entry
+    pub fn close_season_entry(ctx: Context<CloseSeasonEntry>) -> Result<()> {
+        let season = &ctx.accounts.season;
+        require!(
+            season.status == SeasonStatus::Completed
+                || (season.status == SeasonStatus::Cancelled && ctx.accounts.season_entry.refunded),
+            ErrorCode::SeasonEntryInUse
+        );
+        require!(
+            ctx.accounts.agent.open_predictions == 0,
+            ErrorCode::AgentHasOpenPredictions
+        );
         
-        emit!(PrizesDistributed {
-            season_id: season.id,
-            total_pool: prize_pool,
-            first_place: first_place_prize,
-            second_place: second_place_prize,
-            third_place: third_place_prize,
+        Ok(())
+    }
+
+    /// Close a retired agent's achievement (agent owner only)
+    /// The reputation it granted stays on the agent's tombstone
+    pub fn close_achievement(ctx: Context<CloseAchievement>) -> Result<()> {
+        require!(
+            ctx.accounts.agent.status == AgentStatus::Retired,
+            ErrorCode::AgentNotRetired
+        );
+        
+        Ok(())
+    }
+
+    /// Create the arena-owned token vault collecting a mint's fees, and its ledger (authority only)
+    /// Required before SPL seasons in that mint can take platform fees or lost stakes
+    pub fn init_treasury_token_vault(ctx: Context<InitTreasuryTokenVault>) -> Result<()> {
+        let ledger = &mut ctx.accounts.treasury_token_ledger;
+        ledger.mint = ctx.accounts.mint.key();
+        ledger.refunds_owed = 0;
+        ledger.total_refunded = 0;
+        ledger.total_withdrawn = 0;
+        ledger.bump = *ctx.bumps.get("treasury_token_ledger").unwrap();
+        
+        emit!(TreasuryTokenVaultCreated {
+            mint: ctx.accounts.mint.key(),
+            vault: ctx.accounts.treasury_token_vault.key(),
         });
         
         Ok(())
     }
 
-    /// Withdraw accumulated treasury fees (authority only)
+    /// Withdraw accumulated treasury fees to the treasurer (treasurer only)
+    /// The treasury always keeps its rent-exempt minimum and the refunds it owes; passing
+    /// a treasury token vault and its ledger withdraws that mint's fees to the destination
+    /// token account instead
     pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
-        let treasury_balance = ctx.accounts.treasury.lamports();
-        require!(amount <= treasury_balance, ErrorCode::InsufficientFunds);
+        if let Some(vault) = &ctx.accounts.treasury_token_vault {
+            let ledger = required_mut(&mut ctx.accounts.treasury_token_ledger)?;
+            require_keys_eq!(ledger.mint, vault.mint, ErrorCode::TokenMintMismatch);
+            require!(
+                amount <= vault.amount.saturating_sub(ledger.refunds_owed),
+                ErrorCode::InsufficientFunds
+            );
+            let destination = required(&ctx.accounts.destination_token_account)?;
+            require_keys_eq!(
+                required(&ctx.accounts.mint)?.key(),
+                vault.mint,
+                ErrorCode::TokenMintMismatch
+            );
+            let signer = TokenVaultSigner::new(
+                &ctx.accounts.token_program,
+                &ctx.accounts.mint,
+                &ctx.accounts.arena,
+            )?;
+            pay_from_vault(
+                &vault.to_account_info(),
+                &destination.to_account_info(),
+                amount,
+                Some(&signer),
+            )?;
+            ledger.total_withdrawn += amount;
+            
+            emit!(TreasuryWithdrawal {
+                treasurer: ctx.accounts.treasurer.key(),
+                mint: vault.mint,
+                amount,
+                remaining_balance: vault.amount - amount,
+                total_withdrawn: ledger.total_withdrawn,
+            });
+            
+            return Ok(());
+        }
+        
+        let treasury = ctx.accounts.treasury.to_account_info();
+        let treasury_balance = treasury.lamports();
+        let rent_floor = Rent::get()?.minimum_balance(treasury.data_len());
+        require!(
+            amount
+                <= treasury_balance
+                    .saturating_sub(rent_floor)
+                    .saturating_sub(ctx.accounts.arena.refunds_owed),
+            ErrorCode::InsufficientFunds
+        );
         
-        **ctx.accounts.treasury.to_account_info().lamports.borrow_mut() -= amount;
-        **ctx.accounts.authority.to_account_info().lamports.borrow_mut() += amount;
+        **treasury.lamports.borrow_mut() -= amount;
+        **ctx.accounts.treasurer.to_account_info().lamports.borrow_mut() += amount;
+        
+        let arena = &mut ctx.accounts.arena;
+        arena.total_withdrawn += amount;
         
         emit!(TreasuryWithdrawal {
-            authority: ctx.accounts.authority.key(),
+            treasurer: ctx.accounts.treasurer.key(),
+            mint: Pubkey::default(),
             amount,
             remaining_balance: treasury_balance - amount,
+            total_withdrawn: arena.total_withdrawn,
         });
         
         Ok(())
     }
-}
 
-// Helper functions
-fn calculate_score(stake: u64, streak: u16) -> u64 {
-    let streak_multiplier = 100 + (streak as u64 * 10); // 1.0 + 0.1 per streak
-    (stake * streak_multiplier) / 100
-}
+    /// Challenge another agent (or anyone, with a default `opponent`) to a duel on one
+    /// asset and horizon, escrowing the challenger's stake and commitment
+    /// (see `duel_commitment` for the scheme)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_duel(
+        ctx: Context<CreateDuel>,
+        opponent: Pubkey,
+        asset_id: [u8; 8],
+        horizon: i64,
+        stake: u64,
+        accept_window: i64,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        let arena = &mut ctx.accounts.arena;
+        let challenger = &mut ctx.accounts.challenger;
+        arena.require_unpaused(PAUSE_PREDICTIONS)?;
+        challenger.require_active()?;
+        require!(opponent != challenger.key(), ErrorCode::SelfDuel);
+        require!(
+            stake > 0
+                && horizon > 0
+                && horizon <= MAX_PREDICTION_HORIZON
+                && accept_window > 0
+                && accept_window <= MAX_DUEL_ACCEPT_WINDOW,
+            ErrorCode::InvalidDuelTerms
+        );
+        
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.duel_vault.to_account_info(),
+                },
+            ),
+            stake,
+        )?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let duel = &mut ctx.accounts.duel;
+        duel.id = arena.total_duels;
+        duel.challenger = challenger.key();
+        duel.challenger_owner = ctx.accounts.owner.key();
+        duel.opponent = opponent;
+        duel.opponent_owner = Pubkey::default();
+        duel.asset_id = asset_id;
+        duel.horizon = horizon;
+        duel.stake = stake;
+        duel.fee_bps = arena.duel_fee_bps;
+        duel.status = DuelStatus::Open;
+        duel.created_at = now;
+        duel.accept_deadline = now + accept_window;
+        duel.challenger_commitment = commitment;
+        duel.winner = Pubkey::default();
+        duel.bump = *ctx.bumps.get("duel").unwrap();
+        ctx.accounts.duel_vault.bump = *ctx.bumps.get("duel_vault").unwrap();
+        
+        arena.total_duels += 1;
+        challenger.open_predictions += 1;
+        
+        emit!(DuelCreated {
+            duel: duel.key(),
+            id: duel.id,
+            challenger: duel.challenger,
+            opponent,
+            asset_id,
+            horizon,
+            stake,
+            accept_deadline: duel.accept_deadline,
+        });
+        
+        Ok(())
+    }
 
-fn update_rank(agent: &mut Account<Agent>) -> Result<()> {
-    let accuracy = if agent.total_predictions > 0 {
-        (agent.correct_predictions as u64 * 100) / agent.total_predictions as u64
-    } else {
-        0
-    };
-    
-    agent.rank = match (accuracy, agent.best_streak, agent.correct_predictions) {
-        (acc, _, _) if acc >= 80 && agent.correct_predictions >= 50 => Rank::Legend,
-        (acc, streak, _) if acc >= 70 && streak >= 10 => Rank::Diamond,
-        (acc, streak, _) if acc >= 60 && streak >= 5 => Rank::Gold,
-        (acc, streak, _) if acc >= 50 && streak >= 3 => Rank::Silver,
-        _ => Rank::Bronze,
+    /// Take up an open duel, matching its stake with the opponent's commitment
+    pub fn accept_duel(ctx: Context<AcceptDuel>, commitment: [u8; 32]) -> Result<()> {
+        ctx.accounts.arena.require_unpaused(PAUSE_PREDICTIONS)?;
+        let duel = &mut ctx.accounts.duel;
+        let opponent = &mut ctx.accounts.opponent;
+        opponent.require_active()?;
+        require!(duel.status == DuelStatus::Open, ErrorCode::InvalidDuelStatus);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= duel.accept_deadline, ErrorCode::DuelAcceptWindowClosed);
+        require!(opponent.key() != duel.challenger, ErrorCode::SelfDuel);
+        require!(
+            duel.opponent == Pubkey::default() || duel.opponent == opponent.key(),
+            ErrorCode::DuelOpponentMismatch
+        );
+        
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.duel_vault.to_account_info(),
+                },
+            ),
+            duel.stake,
+        )?;
+        
+        duel.opponent = opponent.key();
+        duel.opponent_owner = ctx.accounts.owner.key();
+        duel.opponent_commitment = commitment;
+        duel.status = DuelStatus::Active;
+        duel.accepted_at = now;
+        duel.resolve_after = now + duel.horizon;
+        // Reveals close by the horizon, so neither side can reveal once the price is known
+        duel.reveal_deadline = (now + DUEL_REVEAL_WINDOW).min(duel.resolve_after);
+        opponent.open_predictions += 1;
+        
+        emit!(DuelAccepted {
+            duel: duel.key(),
+            opponent: duel.opponent,
+        });
+        
+        Ok(())
+    }
+
+    /// Reveal one side of an active duel; the payload must name the duel's asset and horizon
+    pub fn reveal_duel(
+        ctx: Context<RevealDuel>,
+        payload: PredictionPayload,
+        nonce: [u8; 32],
+    ) -> Result<()> {
+        let duel = &mut ctx.accounts.duel;
+        let agent = ctx.accounts.agent.key();
+        require!(duel.status == DuelStatus::Active, ErrorCode::InvalidDuelStatus);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= duel.reveal_deadline && now < duel.resolve_after,
+            ErrorCode::RevealWindowClosed
+        );
+        
+        let is_challenger = agent == duel.challenger;
+        require!(
+            is_challenger || agent == duel.opponent,
+            ErrorCode::DuelParticipantMismatch
+        );
+        let (commitment, revealed) = if is_challenger {
+            (duel.challenger_commitment, duel.challenger_revealed)
+        } else {
+            (duel.opponent_commitment, duel.opponent_revealed)
+        };
+        require!(!revealed, ErrorCode::DuelAlreadyRevealed);
+        require!(
+            duel_commitment(&duel.key(), &agent, &nonce, &payload) == commitment,
+            ErrorCode::HashMismatch
+        );
+        
+        payload.validate()?;
+        require!(
+            payload.asset_id == duel.asset_id && payload.horizon == duel.horizon,
+            ErrorCode::DuelPayloadMismatch
+        );
+        
+        if is_challenger {
+            duel.challenger_revealed = true;
+            duel.challenger_target_price = payload.target_price;
+            duel.challenger_target_expo = payload.target_expo;
+        } else {
+            duel.opponent_revealed = true;
+            duel.opponent_target_price = payload.target_price;
+            duel.opponent_target_expo = payload.target_expo;
+        }
+        
+        emit!(DuelRevealed {
+            duel: duel.key(),
+            agent,
+            target_price: payload.target_price,
+            target_expo: payload.target_expo,
+        });
+        
+        Ok(())
+    }
+
+    /// Settle an active duel (permissionless)
+    /// With both sides revealed, the target closest to the oracle price at the horizon wins;
+    /// a side that never revealed loses once the reveal window closes. The winner takes
+    /// both stakes minus the duel fee; ties, double no-shows and a horizon price that was
+    /// never cranked within the feed's `max_staleness` refund both stakes
+    /// Not pausable, like `resolve_prediction_with_oracle`
+    pub fn resolve_duel(ctx: Context<ResolveDuel>) -> Result<()> {
+        let duel = &mut ctx.accounts.duel;
+        require!(duel.status == DuelStatus::Active, ErrorCode::InvalidDuelStatus);
+        let now = Clock::get()?.unix_timestamp;
+        
+        let mut settled_price = None;
+        let winner = match (duel.challenger_revealed, duel.opponent_revealed) {
+            (true, true) => {
+                require!(now >= duel.resolve_after, ErrorCode::HorizonNotReached);
+                if horizon_window_missed(
+                    &ctx.accounts.price_feed,
+                    &ctx.accounts.asset_feed,
+                    duel.resolve_after,
+                )? {
+                    None
+                } else {
+                    let price = load_horizon_price(
+                        &ctx.accounts.price_feed,
+                        &ctx.accounts.asset_feed,
+                        duel.resolve_after,
+                    )?;
+                    
+                    let (challenger_distance, opponent_distance) = target_distances(
+                        price.price,
+                        price.expo,
+                        (duel.challenger_target_price, duel.challenger_target_expo),
+                        (duel.opponent_target_price, duel.opponent_target_expo),
+                    )?;
+                    settled_price = Some(price);
+                    match challenger_distance.cmp(&opponent_distance) {
+                        std::cmp::Ordering::Less => Some(duel.challenger),
+                        std::cmp::Ordering::Greater => Some(duel.opponent),
+                        std::cmp::Ordering::Equal => None,
+                    }
+                }
+            }
+            (challenger_revealed, opponent_revealed) => {
+                require!(now > duel.reveal_deadline, ErrorCode::RevealWindowOpen);
+                if challenger_revealed {
+                    Some(duel.challenger)
+                } else if opponent_revealed {
+                    Some(duel.opponent)
+                } else {
+                    None
+                }
+            }
+        };
+        
+        let vault = ctx.accounts.duel_vault.to_account_info();
+        let challenger_owner = ctx.accounts.challenger_owner.to_account_info();
+        let opponent_owner = ctx.accounts.opponent_owner.to_account_info();
+        let pot = duel.stake * 2;
+        let (payout, fee) = match winner {
+            Some(winner) => {
+                let fee = (pot as u128 * duel.fee_bps as u128 / 10000) as u64;
+                let to = if winner == duel.challenger { &challenger_owner } else { &opponent_owner };
+                pay_from_vault(&vault, to, pot - fee, None)?;
+                if fee > 0 {
+                    pay_from_vault(&vault, &ctx.accounts.treasury, fee, None)?;
+                    ctx.accounts.arena.total_fees_collected += fee;
+                }
+                (pot - fee, fee)
+            }
+            None => {
+                pay_from_vault(&vault, &challenger_owner, duel.stake, None)?;
+                pay_from_vault(&vault, &opponent_owner, duel.stake, None)?;
+                (duel.stake, 0)
+            }
+        };
+        
+        duel.status = DuelStatus::Resolved;
+        duel.winner = winner.unwrap_or_default();
+        ctx.accounts.challenger.open_predictions -= 1;
+        ctx.accounts.opponent.open_predictions -= 1;
+        
+        emit!(DuelResolved {
+            duel: duel.key(),
+            winner: duel.winner,
+            price: settled_price.as_ref().map_or(0, |p| p.price),
+            expo: settled_price.as_ref().map_or(0, |p| p.expo),
+            payout,
+            fee,
+        });
+        
+        Ok(())
+    }
+
+    /// Cancel an unaccepted duel and refund the challenger's stake
+    /// The challenger's owner may cancel at any time; anyone may once the acceptance deadline passes
+    pub fn cancel_duel(ctx: Context<CancelDuel>) -> Result<()> {
+        let duel = &mut ctx.accounts.duel;
+        require!(duel.status == DuelStatus::Open, ErrorCode::InvalidDuelStatus);
+        require!(
+            ctx.accounts.canceller.key() == duel.challenger_owner
+                || Clock::get()?.unix_timestamp > duel.accept_deadline,
+            ErrorCode::DuelCancelTooEarly
+        );
+        
+        pay_from_vault(
+            &ctx.accounts.duel_vault.to_account_info(),
+            &ctx.accounts.challenger_owner,
+            duel.stake,
+            None,
+        )?;
+        duel.status = DuelStatus::Cancelled;
+        ctx.accounts.challenger.open_predictions -= 1;
+        
+        emit!(DuelCancelled {
+            duel: duel.key(),
+            refunded: duel.stake,
+        });
+        
+        Ok(())
+    }
+
+    /// Open a bracket tournament on one asset and horizon (season operator)
+    /// Entrants pay `entry_fee` into the tournament vault until registration closes
+    /// Reveals must close before the horizon, so nobody reveals once the price is known
+    pub fn create_tournament(ctx: Context<CreateTournament>, terms: TournamentTerms) -> Result<()> {
+        let TournamentTerms {
+            format,
+            asset_id,
+            horizon,
+            entry_fee,
+            prize_pool_bps,
+            max_entrants,
+            registration_window,
+            commit_window,
+            reveal_window,
+        } = terms;
+        require!(
+            (2..=MAX_TOURNAMENT_ENTRANTS).contains(&(max_entrants as usize))
+                && horizon > 0
+                && horizon <= MAX_PREDICTION_HORIZON
+                && prize_pool_bps <= 10000
+                && registration_window > 0
+                && commit_window > 0
+                && reveal_window > 0
+                && reveal_window < horizon,
+            ErrorCode::InvalidTournamentTerms
+        );
+        
+        let arena = &mut ctx.accounts.arena;
+        let tournament = &mut ctx.accounts.tournament;
+        let now = Clock::get()?.unix_timestamp;
+        tournament.id = arena.total_tournaments;
+        tournament.format = format;
+        tournament.asset_id = asset_id;
+        tournament.horizon = horizon;
+        tournament.entry_fee = entry_fee;
+        tournament.prize_pool_bps = prize_pool_bps;
+        tournament.max_entrants = max_entrants;
+        tournament.commit_window = commit_window;
+        tournament.reveal_window = reveal_window;
+        tournament.created_at = now;
+        tournament.registration_deadline = now + registration_window;
+        tournament.status = TournamentStatus::Registration;
+        tournament.entrants = Vec::new();
+        tournament.round = 0;
+        tournament.matches = Vec::new();
+        tournament.prize_pool = 0;
+        tournament.champion = Pubkey::default();
+        tournament.prize_claimed = false;
+        tournament.bump = *ctx.bumps.get("tournament").unwrap();
+        ctx.accounts.tournament_vault.bump = *ctx.bumps.get("tournament_vault").unwrap();
+        
+        arena.total_tournaments += 1;
+        
+        emit!(TournamentCreated {
+            tournament: tournament.key(),
+            id: tournament.id,
+            format,
+            asset_id,
+            horizon,
+            entry_fee,
+            max_entrants,
+            registration_deadline: tournament.registration_deadline,
+        });
+        
+        Ok(())
+    }
+
+    /// Enter an agent into a tournament's bracket, paying the entry fee
+    /// The agent's reputation score is recorded as its seed
+    pub fn join_tournament(ctx: Context<JoinTournament>) -> Result<()> {
+        ctx.accounts.arena.require_unpaused(PAUSE_ENTRIES)?;
+        let tournament = &mut ctx.accounts.tournament;
+        let agent = &ctx.accounts.agent;
+        agent.require_active()?;
+        require!(
+            tournament.status == TournamentStatus::Registration,
+            ErrorCode::InvalidTournamentStatus
+        );
+        require!(
+            Clock::get()?.unix_timestamp < tournament.registration_deadline,
+            ErrorCode::TournamentRegistrationClosed
+        );
+        require!(
+            tournament.entrants.len() < tournament.max_entrants as usize,
+            ErrorCode::TournamentFull
+        );
+        require!(
+            tournament.entrant_index(&agent.key()).is_none(),
+            ErrorCode::AlreadyInTournament
+        );
+        
+        if tournament.entry_fee > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.tournament_vault.to_account_info(),
+                    },
+                ),
+                tournament.entry_fee,
+            )?;
+        }
+        
+        tournament.entrants.push(TournamentEntrant {
+            agent: agent.key(),
+            seed_score: agent.reputation_score,
+            losses: 0,
+        });
+        
+        emit!(TournamentJoined {
+            tournament: tournament.key(),
+            agent: agent.key(),
+            seed_score: agent.reputation_score,
+            entrants: tournament.entrants.len() as u8,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw an agent before the tournament starts, or after it is cancelled,
+    /// refunding its entry fee to the agent's owner. A tournament voided after it started
+    /// has already paid its platform share, so its entrants split the prize pool instead
+    pub fn leave_tournament(ctx: Context<LeaveTournament>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        require!(
+            matches!(
+                tournament.status,
+                TournamentStatus::Registration | TournamentStatus::Cancelled
+            ),
+            ErrorCode::InvalidTournamentStatus
+        );
+        let index = tournament
+            .entrant_index(&ctx.accounts.agent.key())
+            .ok_or(ErrorCode::NotInTournament)?;
+        
+        let refund = if tournament.round == 0 {
+            tournament.entry_fee
+        } else {
+            let share = tournament.prize_pool / tournament.entrants.len() as u64;
+            tournament.prize_pool -= share;
+            share
+        };
+        
+        tournament.entrants.remove(index);
+        pay_from_vault(
+            &ctx.accounts.tournament_vault.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            refund,
+            None,
+        )?;
+        
+        emit!(TournamentLeft {
+            tournament: tournament.key(),
+            agent: ctx.accounts.agent.key(),
+            refunded: refund,
+        });
+        
+        Ok(())
+    }
+
+    /// Close registration, seed the bracket by reputation and pair the first round
+    /// (season operator). Allowed once the bracket is full or registration has closed;
+    /// the platform share of the entry fees moves to the treasury
+    pub fn start_tournament(ctx: Context<StartTournament>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            tournament.status == TournamentStatus::Registration,
+            ErrorCode::InvalidTournamentStatus
+        );
+        require!(
+            tournament.entrants.len() == tournament.max_entrants as usize
+                || now >= tournament.registration_deadline,
+            ErrorCode::TournamentRegistrationOpen
+        );
+        require!(tournament.entrants.len() >= 2, ErrorCode::NotEnoughEntrants);
+        
+        let total = tournament.entry_fee * tournament.entrants.len() as u64;
+        let platform_fee = (total * (10000 - tournament.prize_pool_bps) as u64) / 10000;
+        if platform_fee > 0 {
+            pay_from_vault(
+                &ctx.accounts.tournament_vault.to_account_info(),
+                &ctx.accounts.treasury,
+                platform_fee,
+                None,
+            )?;
+            ctx.accounts.arena.total_fees_collected += platform_fee;
+        }
+        tournament.prize_pool = total - platform_fee;
+        
+        // Stable sort: equal reputations keep their registration order
+        tournament.entrants.sort_by_key(|e| std::cmp::Reverse(e.seed_score));
+        tournament.status = TournamentStatus::Active;
+        tournament.pair_round(now);
+        
+        emit!(TournamentStarted {
+            tournament: tournament.key(),
+            entrants: tournament.entrants.len() as u8,
+            prize_pool: tournament.prize_pool,
+            platform_fee,
+        });
+        emit!(TournamentRoundStarted {
+            tournament: tournament.key(),
+            round: tournament.round,
+            matches: tournament.matches.len() as u8,
+            commit_deadline: tournament.commit_deadline,
+        });
+        
+        Ok(())
+    }
+
+    /// Cancel a tournament that has not started (season operator)
+    /// Entrants reclaim their fees with `leave_tournament`
+    pub fn cancel_tournament(ctx: Context<CancelTournament>) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        require!(
+            tournament.status == TournamentStatus::Registration,
+            ErrorCode::InvalidTournamentStatus
+        );
+        tournament.status = TournamentStatus::Cancelled;
+        
+        emit!(TournamentCancelled {
+            tournament: tournament.key(),
+            entrants: tournament.entrants.len() as u8,
+        });
+        
+        Ok(())
+    }
+
+    /// Commit an agent's prediction for its match in the current round
+    /// Uses the `prediction_commitment` scheme with the tournament key in place of the season
+    /// Not pausable: the round's deadlines keep running, so a pause would knock entrants out
+    pub fn commit_tournament_prediction(
+        ctx: Context<CommitTournamentPrediction>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.agent.require_active()?;
+        let tournament = &mut ctx.accounts.tournament;
+        require!(
+            tournament.status == TournamentStatus::Active,
+            ErrorCode::InvalidTournamentStatus
+        );
+        require!(
+            Clock::get()?.unix_timestamp <= tournament.commit_deadline,
+            ErrorCode::CommitWindowClosed
+        );
+        let (slot, side) = tournament.match_side(&ctx.accounts.agent.key())?;
+        
+        let match_side = &mut tournament.matches[slot].sides[side];
+        require!(!match_side.committed, ErrorCode::MatchSideCommitted);
+        match_side.commitment = commitment;
+        match_side.committed = true;
+        
+        emit!(TournamentPredictionCommitted {
+            tournament: tournament.key(),
+            round: tournament.round,
+            slot: slot as u8,
+            agent: ctx.accounts.agent.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Reveal a committed tournament prediction
+    /// Opens once both sides of the match have committed or the commit window has closed
+    pub fn reveal_tournament_prediction(
+        ctx: Context<RevealTournamentPrediction>,
+        payload: PredictionPayload,
+        nonce: [u8; 32],
+    ) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let agent = ctx.accounts.agent.key();
+        require!(
+            tournament.status == TournamentStatus::Active,
+            ErrorCode::InvalidTournamentStatus
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= tournament.reveal_deadline, ErrorCode::RevealWindowClosed);
+        let (slot, side) = tournament.match_side(&agent)?;
+        let tournament_key = tournament.key();
+        let (asset_id, horizon, commit_deadline) =
+            (tournament.asset_id, tournament.horizon, tournament.commit_deadline);
+        
+        let bracket_match = &mut tournament.matches[slot];
+        require!(
+            bracket_match.sides.iter().all(|s| s.committed) || now > commit_deadline,
+            ErrorCode::CommitWindowOpen
+        );
+        let match_side = &mut bracket_match.sides[side];
+        require!(match_side.committed, ErrorCode::MatchSideNotCommitted);
+        require!(!match_side.revealed, ErrorCode::MatchSideRevealed);
+        require!(
+            prediction_commitment(&agent, &tournament_key, &nonce, &payload) == match_side.commitment,
+            ErrorCode::HashMismatch
+        );
+        
+        payload.validate()?;
+        require!(
+            payload.asset_id == asset_id && payload.horizon == horizon,
+            ErrorCode::TournamentPayloadMismatch
+        );
+        
+        match_side.revealed = true;
+        match_side.target_price = payload.target_price;
+        match_side.target_expo = payload.target_expo;
+        
+        emit!(TournamentPredictionRevealed {
+            tournament: tournament_key,
+            round: tournament.round,
+            slot: slot as u8,
+            agent,
+            target_price: payload.target_price,
+            target_expo: payload.target_expo,
+        });
+        
+        Ok(())
+    }
+
+    /// Decide a match in the current round (permissionless)
+    /// With both sides revealed, the target closest to the oracle price wins; a side that
+    /// never revealed loses once the reveal window closes. Ties and double no-shows go to
+    /// the higher seed. Resolving the round's last match pairs the next round, or crowns
+    /// the champion once a single agent is left
+    /// Not pausable, like `resolve_prediction_with_oracle`
+    pub fn resolve_tournament_match(ctx: Context<ResolveTournamentMatch>, slot: u8) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        require!(
+            tournament.status == TournamentStatus::Active,
+            ErrorCode::InvalidTournamentStatus
+        );
+        let bracket_match = *tournament
+            .matches
+            .get(slot as usize)
+            .filter(|m| !m.resolved)
+            .ok_or(ErrorCode::InvalidTournamentMatch)?;
+        let [high, low] = bracket_match.sides;
+        let now = Clock::get()?.unix_timestamp;
+        
+        let mut settled_price = None;
+        let low_wins = match (high.revealed, low.revealed) {
+            (true, true) => {
+                require!(now >= tournament.resolve_after, ErrorCode::HorizonNotReached);
+                let price = load_horizon_price(
+                    &ctx.accounts.price_feed,
+                    &ctx.accounts.asset_feed,
+                    tournament.resolve_after,
+                )?;
+                
+                let (high_distance, low_distance) = target_distances(
+                    price.price,
+                    price.expo,
+                    (high.target_price, high.target_expo),
+                    (low.target_price, low.target_expo),
+                )?;
+                settled_price = Some(price);
+                low_distance < high_distance
+            }
+            (_, low_revealed) => {
+                require!(now > tournament.reveal_deadline, ErrorCode::RevealWindowOpen);
+                low_revealed
+            }
+        };
+        let (winner, loser) = if low_wins { (low, high) } else { (high, low) };
+        
+        tournament.entrants[loser.entrant as usize].losses += 1;
+        let bracket_match = &mut tournament.matches[slot as usize];
+        bracket_match.resolved = true;
+        bracket_match.winner = winner.entrant;
+        
+        emit!(TournamentMatchResolved {
+            tournament: tournament.key(),
+            round: tournament.round,
+            slot,
+            winner: tournament.entrants[winner.entrant as usize].agent,
+            loser: tournament.entrants[loser.entrant as usize].agent,
+            price: settled_price.as_ref().map_or(0, |p| p.price),
+            expo: settled_price.as_ref().map_or(0, |p| p.expo),
+        });
+        
+        if tournament.matches.iter().all(|m| m.resolved) {
+            if let Some(champion) = tournament.sole_survivor() {
+                tournament.status = TournamentStatus::Completed;
+                tournament.champion = champion;
+                
+                emit!(TournamentCompleted {
+                    tournament: tournament.key(),
+                    champion,
+                    rounds: tournament.round,
+                    prize_pool: tournament.prize_pool,
+                });
+            } else {
+                tournament.pair_round(now);
+                
+                emit!(TournamentRoundStarted {
+                    tournament: tournament.key(),
+                    round: tournament.round,
+                    matches: tournament.matches.len() as u8,
+                    commit_deadline: tournament.commit_deadline,
+                });
+            }
+        }
+        
+        Ok(())
+    }
+
+    /// Cancel an active tournament whose match can no longer settle (permissionless):
+    /// both sides revealed, but the feed holds no price from inside the horizon's
+    /// staleness window. Entrants reclaim their share with `leave_tournament`
+    pub fn void_tournament(ctx: Context<ResolveTournamentMatch>, slot: u8) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        require!(
+            tournament.status == TournamentStatus::Active,
+            ErrorCode::InvalidTournamentStatus
+        );
+        let bracket_match = tournament
+            .matches
+            .get(slot as usize)
+            .filter(|m| !m.resolved)
+            .ok_or(ErrorCode::InvalidTournamentMatch)?;
+        require!(
+            bracket_match.sides.iter().all(|s| s.revealed)
+                && horizon_window_missed(
+                    &ctx.accounts.price_feed,
+                    &ctx.accounts.asset_feed,
+                    tournament.resolve_after,
+                )?,
+            ErrorCode::ResolutionWindowOpen
+        );
+        tournament.status = TournamentStatus::Cancelled;
+        
+        emit!(TournamentCancelled {
+            tournament: tournament.key(),
+            entrants: tournament.entrants.len() as u8,
+        });
+        
+        Ok(())
+    }
+
+    /// Pay a completed tournament's prize pool to its champion's owner
+    pub fn claim_tournament_prize(ctx: Context<ClaimTournamentPrize>) -> Result<()> {
+        ctx.accounts.arena.require_unpaused(PAUSE_PAYOUTS)?;
+        let tournament = &mut ctx.accounts.tournament;
+        require!(
+            tournament.status == TournamentStatus::Completed,
+            ErrorCode::InvalidTournamentStatus
+        );
+        require!(!tournament.prize_claimed, ErrorCode::PrizeAlreadyClaimed);
+        
+        pay_from_vault(
+            &ctx.accounts.tournament_vault.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            tournament.prize_pool,
+            None,
+        )?;
+        tournament.prize_claimed = true;
+        
+        emit!(TournamentPrizeClaimed {
+            tournament: tournament.key(),
+            champion: tournament.champion,
+            owner: ctx.accounts.owner.key(),
+            amount: tournament.prize_pool,
+        });
+        
+        Ok(())
+    }
+
+    /// Back a committed prediction as right or wrong (spectators only)
+    /// Betting runs while the prediction is still hidden; the first bet opens the pool,
+    /// fixing the arena betting fee for it. Repeat bets add to the bettor's side
+    /// A prediction can only be revealed before its horizon, so a bet placed once the
+    /// outcome is known backs a prediction that can only expire, voiding the pool
+    pub fn place_bet(ctx: Context<PlaceBet>, side: BetSide, amount: u64) -> Result<()> {
+        ctx.accounts.arena.require_unpaused(PAUSE_ENTRIES)?;
+        let prediction = &mut ctx.accounts.prediction;
+        let bettor = ctx.accounts.bettor.key();
+        require!(amount > 0, ErrorCode::InvalidBetAmount);
+        require!(
+            prediction.status == PredictionStatus::Committed
+                && Clock::get()?.unix_timestamp <= prediction.reveal_deadline,
+            ErrorCode::BettingClosed
+        );
+        require!(
+            bettor != ctx.accounts.agent.owner && bettor != prediction.rent_payer,
+            ErrorCode::InsiderBet
+        );
+        
+        let pool = &mut ctx.accounts.betting_pool;
+        if pool.prediction == Pubkey::default() {
+            pool.prediction = prediction.key();
+            pool.fee_bps = ctx.accounts.arena.betting_fee_bps;
+            pool.outcome = BetOutcome::Pending;
+            pool.bump = *ctx.bumps.get("betting_pool").unwrap();
+            ctx.accounts.betting_vault.bump = *ctx.bumps.get("betting_vault").unwrap();
+            prediction.betting_pool = pool.key();
+        }
+        
+        let bet = &mut ctx.accounts.bet;
+        if bet.bettor == Pubkey::default() {
+            bet.pool = pool.key();
+            bet.bettor = bettor;
+            bet.side = side;
+            bet.bump = *ctx.bumps.get("bet").unwrap();
+        }
+        require!(bet.side == side, ErrorCode::BetSideMismatch);
+        
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.bettor.to_account_info(),
+                    to: ctx.accounts.betting_vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        bet.amount += amount;
+        match side {
+            BetSide::Right => pool.right_total += amount,
+            BetSide::Wrong => pool.wrong_total += amount,
+        }
+        
+        emit!(BetPlaced {
+            pool: pool.key(),
+            prediction: pool.prediction,
+            bettor,
+            side,
+            amount,
+            right_total: pool.right_total,
+            wrong_total: pool.wrong_total,
+        });
+        
+        Ok(())
+    }
+
+    /// Fix a betting pool's outcome once its prediction is settled (permissionless)
+    /// The house cut of the whole pool goes to the treasury. Expired predictions,
+    /// and pools with no bets on one side, are void and refund every bet
+    pub fn settle_betting_pool(ctx: Context<SettleBettingPool>) -> Result<()> {
+        ctx.accounts.arena.require_unpaused(PAUSE_PAYOUTS)?;
+        let pool = &mut ctx.accounts.betting_pool;
+        let prediction = &ctx.accounts.prediction;
+        require!(pool.outcome == BetOutcome::Pending, ErrorCode::BettingPoolSettled);
+        
+        let total = pool.right_total + pool.wrong_total;
+        pool.outcome = match prediction.status {
+            PredictionStatus::Resolved if pool.right_total > 0 && pool.wrong_total > 0 => {
+                if prediction.was_correct {
+                    BetOutcome::Right
+                } else {
+                    BetOutcome::Wrong
+                }
+            }
+            PredictionStatus::Resolved | PredictionStatus::Expired => BetOutcome::Void,
+            _ => return err!(ErrorCode::PredictionNotSettled),
+        };
+        
+        if pool.outcome == BetOutcome::Void {
+            pool.payout_pool = total;
+        } else {
+            pool.fee = (total as u128 * pool.fee_bps as u128 / 10000) as u64;
+            pool.payout_pool = total - pool.fee;
+            if pool.fee > 0 {
+                pay_from_vault(
+                    &ctx.accounts.betting_vault.to_account_info(),
+                    &ctx.accounts.treasury,
+                    pool.fee,
+                    None,
+                )?;
+                ctx.accounts.arena.total_fees_collected += pool.fee;
+            }
+        }
+        
+        emit!(BettingPoolSettled {
+            pool: pool.key(),
+            prediction: pool.prediction,
+            outcome: pool.outcome,
+            payout_pool: pool.payout_pool,
+            fee: pool.fee,
+        });
+        
+        Ok(())
+    }
+
+    /// Pay out a bet from a settled pool and close it, refunding its rent
+    /// Winners split the pool net of the house cut in proportion to their stakes;
+    /// void pools return each stake, and losing bets only recover rent
+    pub fn claim_bet(ctx: Context<ClaimBet>) -> Result<()> {
+        let pool = &ctx.accounts.betting_pool;
+        let bet = &ctx.accounts.bet;
+        let payout = match (pool.outcome, bet.side) {
+            (BetOutcome::Pending, _) => return err!(ErrorCode::BettingPoolUnsettled),
+            (BetOutcome::Void, _) => bet.amount,
+            (BetOutcome::Right, BetSide::Right) => {
+                (bet.amount as u128 * pool.payout_pool as u128 / pool.right_total as u128) as u64
+            }
+            (BetOutcome::Wrong, BetSide::Wrong) => {
+                (bet.amount as u128 * pool.payout_pool as u128 / pool.wrong_total as u128) as u64
+            }
+            _ => 0,
+        };
+        if pool.outcome != BetOutcome::Void {
+            ctx.accounts.arena.require_unpaused(PAUSE_PAYOUTS)?;
+        }
+        
+        if payout > 0 {
+            pay_from_vault(
+                &ctx.accounts.betting_vault.to_account_info(),
+                &ctx.accounts.bettor.to_account_info(),
+                payout,
+                None,
+            )?;
+        }
+        
+        emit!(BetClaimed {
+            pool: pool.key(),
+            bettor: bet.bettor,
+            side: bet.side,
+            payout,
+        });
+        
+        Ok(())
+    }
+
+    /// Publish or change an agent's subscription price (owner)
+    /// Subscribers buy access in multiples of `period` seconds; a zero price stops new sales.
+    /// The first call creates the agent's revenue vault
+    pub fn set_subscription_plan(
+        ctx: Context<SetSubscriptionPlan>,
+        price: u64,
+        period: i64,
+    ) -> Result<()> {
+        ctx.accounts.agent.require_active()?;
+        require!(
+            period > 0 && period <= MAX_SUBSCRIPTION_PERIOD,
+            ErrorCode::InvalidSubscriptionPlan
+        );
+        
+        let plan = &mut ctx.accounts.subscription_plan;
+        plan.agent = ctx.accounts.agent.key();
+        plan.price = price;
+        plan.period = period;
+        plan.bump = *ctx.bumps.get("subscription_plan").unwrap();
+        
+        let revenue_vault = &mut ctx.accounts.revenue_vault;
+        revenue_vault.agent = plan.agent;
+        revenue_vault.bump = *ctx.bumps.get("revenue_vault").unwrap();
+        
+        emit!(SubscriptionPlanSet {
+            agent: plan.agent,
+            price,
+            period,
+        });
+        
+        Ok(())
+    }
+
+    /// Buy or extend a subscription to an agent's signals for `periods` plan periods
+    /// Payment goes to the agent's revenue vault, which forwards the arena subscription
+    /// fee to the treasury. Extensions run on from the current expiry while it is active.
+    /// `max_price` guards against a price change landing first
+    pub fn subscribe(ctx: Context<Subscribe>, periods: u16, max_price: u64) -> Result<()> {
+        ctx.accounts.arena.require_unpaused(PAUSE_ENTRIES)?;
+        ctx.accounts.agent.require_active()?;
+        let plan = &ctx.accounts.subscription_plan;
+        require!(plan.price > 0, ErrorCode::SubscriptionsClosed);
+        require!(plan.price <= max_price, ErrorCode::SubscriptionPriceAboveLimit);
+        require!(periods > 0, ErrorCode::InvalidSubscriptionPlan);
+        
+        let paid = plan
+            .price
+            .checked_mul(periods as u64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let duration = plan
+            .period
+            .checked_mul(periods as i64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.subscriber.to_account_info(),
+                    to: ctx.accounts.revenue_vault.to_account_info(),
+                },
+            ),
+            paid,
+        )?;
+        
+        let arena = &mut ctx.accounts.arena;
+        let fee = (paid as u128 * arena.subscription_fee_bps as u128 / 10000) as u64;
+        if fee > 0 {
+            pay_from_vault(
+                &ctx.accounts.revenue_vault.to_account_info(),
+                &ctx.accounts.treasury,
+                fee,
+                None,
+            )?;
+            arena.total_fees_collected += fee;
+        }
+        ctx.accounts.revenue_vault.total_earned += paid - fee;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let subscription = &mut ctx.accounts.subscription;
+        if subscription.subscriber == Pubkey::default() {
+            subscription.agent = ctx.accounts.agent.key();
+            subscription.subscriber = ctx.accounts.subscriber.key();
+            subscription.bump = *ctx.bumps.get("subscription").unwrap();
+        }
+        if !subscription.is_active(now) {
+            subscription.started_at = now;
+            subscription.expires_at = now;
+        }
+        subscription.expires_at += duration;
+        subscription.total_paid += paid;
+        
+        emit!(Subscribed {
+            agent: subscription.agent,
+            subscriber: subscription.subscriber,
+            periods,
+            paid,
+            fee,
+            expires_at: subscription.expires_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Succeeds only while the subscription is active, so an agent endpoint can
+    /// gate early access by simulating this instruction
+    pub fn verify_subscription(ctx: Context<VerifySubscription>) -> Result<()> {
+        require!(
+            ctx.accounts.subscription.is_active(Clock::get()?.unix_timestamp),
+            ErrorCode::SubscriptionExpired
+        );
+        Ok(())
+    }
+
+    /// Close an expired subscription, refunding its rent to the subscriber
+    pub fn close_subscription(ctx: Context<CloseSubscription>) -> Result<()> {
+        require!(
+            !ctx.accounts.subscription.is_active(Clock::get()?.unix_timestamp),
+            ErrorCode::SubscriptionActive
+        );
+        Ok(())
+    }
+
+    /// Withdraw the owner's share of subscription revenue (agent owner)
+    /// Pays whoever owns the agent at withdrawal time
+    pub fn withdraw_agent_revenue(ctx: Context<WithdrawAgentRevenue>, amount: u64) -> Result<()> {
+        let vault = ctx.accounts.revenue_vault.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(vault.data_len());
+        require!(
+            amount <= vault.lamports().saturating_sub(rent_floor),
+            ErrorCode::InsufficientRevenue
+        );
+        
+        pay_from_vault(&vault, &ctx.accounts.owner.to_account_info(), amount, None)?;
+        let revenue_vault = &mut ctx.accounts.revenue_vault;
+        revenue_vault.total_withdrawn += amount;
+        
+        emit!(AgentRevenueWithdrawn {
+            agent: revenue_vault.agent,
+            owner: ctx.accounts.owner.key(),
+            amount,
+            total_withdrawn: revenue_vault.total_withdrawn,
+        });
+        
+        Ok(())
+    }
+}
+
+// Helper functions
+
+/// Commitment an agent submits for a prediction:
+///
+/// `sha256(PREDICTION_COMMITMENT_DOMAIN || agent || season || nonce || borsh(payload))`
+///
+/// `nonce` is 32 random bytes chosen per prediction and kept secret until reveal,
+/// so low-entropy payloads cannot be brute-forced from the hash. Binding the
+/// agent and season keys stops a commitment from being replayed elsewhere.
+pub fn prediction_commitment(
+    agent: &Pubkey,
+    season: &Pubkey,
+    nonce: &[u8; 32],
+    payload: &PredictionPayload,
+) -> [u8; 32] {
+    // Serializing a fixed-size struct into a Vec cannot fail
+    let encoded = payload.try_to_vec().unwrap();
+    hashv(&[
+        PREDICTION_COMMITMENT_DOMAIN,
+        agent.as_ref(),
+        season.as_ref(),
+        nonce,
+        &encoded,
+    ])
+    .to_bytes()
+}
+
+/// Commitment each side of a duel makes:
+///
+/// `sha256(DUEL_COMMITMENT_DOMAIN || duel || agent || nonce || borsh(payload))`
+///
+/// Same scheme as `prediction_commitment`, bound to the duel instead of a season
+pub fn duel_commitment(
+    duel: &Pubkey,
+    agent: &Pubkey,
+    nonce: &[u8; 32],
+    payload: &PredictionPayload,
+) -> [u8; 32] {
+    // Serializing a fixed-size struct into a Vec cannot fail
+    let encoded = payload.try_to_vec().unwrap();
+    hashv(&[DUEL_COMMITMENT_DOMAIN, duel.as_ref(), agent.as_ref(), nonce, &encoded]).to_bytes()
+}
+
+/// Token program and mint an SPL season moves funds in
+pub struct SeasonToken<'info> {
+    program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+}
+
+impl<'info> SeasonToken<'info> {
+    fn new(
+        program: &Option<Interface<'info, TokenInterface>>,
+        mint: &Option<InterfaceAccount<'info, Mint>>,
+    ) -> Result<Self> {
+        let mint = required(mint)?;
+        Ok(Self {
+            program: required(program)?.to_account_info(),
+            mint: mint.to_account_info(),
+            decimals: mint.decimals,
+        })
+    }
+
+    /// Transfer `amount` and return what `to` actually received, which is less
+    /// than `amount` for Token-2022 mints that withhold a transfer fee
+    fn transfer(
+        &self,
+        from: &AccountInfo<'info>,
+        to: &AccountInfo<'info>,
+        authority: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<u64> {
+        let balance_before = token_amount(to)?;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.program.clone(),
+                token_interface::TransferChecked {
+                    from: from.clone(),
+                    mint: self.mint.clone(),
+                    to: to.clone(),
+                    authority: authority.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.decimals,
+        )?;
+        Ok(token_amount(to)? - balance_before)
+    }
+}
+
+/// Signs transfers out of arena-owned token vaults
+pub struct TokenVaultSigner<'info> {
+    token: SeasonToken<'info>,
+    arena: AccountInfo<'info>,
+    arena_bump: u8,
+}
+
+impl<'info> TokenVaultSigner<'info> {
+    fn new(
+        token_program: &Option<Interface<'info, TokenInterface>>,
+        mint: &Option<InterfaceAccount<'info, Mint>>,
+        arena: &Account<'info, Arena>,
+    ) -> Result<Self> {
+        Ok(Self {
+            token: SeasonToken::new(token_program, mint)?,
+            arena: arena.to_account_info(),
+            arena_bump: arena.bump,
+        })
+    }
+}
+
+/// Move funds out of a vault: lamports from a program-owned vault,
+/// or tokens from an arena-owned token vault when a signer is given
+/// Returns the amount `to` received
+fn pay_from_vault<'info>(
+    vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+    signer: Option<&TokenVaultSigner<'info>>,
+) -> Result<u64> {
+    match signer {
+        None => {
+            **vault.lamports.borrow_mut() -= amount;
+            **to.lamports.borrow_mut() += amount;
+            Ok(amount)
+        }
+        Some(signer) => {
+            let arena_seeds: &[&[u8]] = &[b"arena", &[signer.arena_bump]];
+            signer
+                .token
+                .transfer(vault, to, &signer.arena, &[arena_seeds], amount)
+        }
+    }
+}
+
+/// Unwrap an optional account that an SPL season requires
+fn required<T>(account: &Option<T>) -> Result<&T> {
+    account
+        .as_ref()
+        .ok_or_else(|| error!(ErrorCode::MissingTokenAccount))
+}
+
+/// Mutable counterpart of `required`
+fn required_mut<T>(account: &mut Option<T>) -> Result<&mut T> {
+    account
+        .as_mut()
+        .ok_or_else(|| error!(ErrorCode::MissingTokenAccount))
+}
+
+/// Balance of an SPL Token or Token-2022 account
+fn token_amount(account: &AccountInfo) -> Result<u64> {
+    let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    Ok(token_account.amount)
+}
+
+/// Transfer fees Token-2022 has withheld on an account; they block closing it until harvested
+fn withheld_transfer_fees(account: &AccountInfo) -> Result<u64> {
+    if account.owner != &spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = account.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(state
+        .get_extension::<TransferFeeAmount>()
+        .map_or(0, |fee| u64::from(fee.withheld_amount)))
+}
+
+/// Move an account's withheld transfer fees to its mint (permissionless in Token-2022)
+fn harvest_withheld_fees<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+) -> Result<()> {
+    let ix = spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+        token_program.key,
+        mint.key,
+        &[account.key],
+    )?;
+    anchor_lang::solana_program::program::invoke(
+        &ix,
+        &[mint.clone(), account.clone(), token_program.clone()],
+    )?;
+    Ok(())
+}
+
+/// Read a token account passed outside the typed accounts, checking its program and mint
+fn token_account_for_mint(
+    account: &AccountInfo,
+    token: &SeasonToken,
+) -> Result<TokenAccount> {
+    require_keys_eq!(*account.owner, token.program.key(), ErrorCode::TokenMintMismatch);
+    let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    require_keys_eq!(token_account.mint, token.mint.key(), ErrorCode::TokenMintMismatch);
+    Ok(token_account)
+}
+
+/// Accounts a prediction's stake moves between when it settles
+pub struct StakeAccounts<'info> {
+    vault: AccountInfo<'info>,
+    treasury: AccountInfo<'info>,
+    player: AccountInfo<'info>,
+    signer: Option<TokenVaultSigner<'info>>,
+}
+
+/// Apply a prediction outcome: update agent/entry stats and move the stake
+/// Fee structure: Wrong prediction = stake goes to treasury
+fn settle_prediction<'info>(
+    prediction: &mut Account<'info, Prediction>,
+    agent: &mut Account<'info, Agent>,
+    entry: &mut Account<'info, SeasonEntry>,
+    arena: &mut Account<'info, Arena>,
+    leaderboard: &AccountLoader<'info, Leaderboard>,
+    stake: StakeAccounts<'info>,
+    was_correct: bool,
+) -> Result<()> {
+    require!(
+        prediction.status == PredictionStatus::Revealed,
+        ErrorCode::InvalidPredictionStatus
+    );
+
+    prediction.was_correct = was_correct;
+    prediction.status = PredictionStatus::Resolved;
+    prediction.resolved_at = Clock::get()?.unix_timestamp;
+
+    agent.total_predictions += 1;
+    agent.open_predictions -= 1;
+    entry.predictions_made += 1;
+
+    if was_correct {
+        agent.correct_predictions += 1;
+        agent.streak += 1;
+        entry.predictions_correct += 1;
+        entry.score += calculate_score(prediction.stake_amount, agent.streak);
+
+        if agent.streak > agent.best_streak {
+            agent.best_streak = agent.streak;
+        }
+
+        update_rank(agent)?;
+
+        // Return stake to player on correct prediction
+        let stake_return = prediction.stake_amount;
+        if stake_return > 0 {
+            pay_from_vault(&stake.vault, &stake.player, stake_return, stake.signer.as_ref())?;
+        }
+    } else {
+        // Wrong prediction: stake goes to treasury
+        agent.streak = 0;
+        let lost_stake = prediction.stake_amount;
+        if lost_stake > 0 {
+            pay_from_vault(&stake.vault, &stake.treasury, lost_stake, stake.signer.as_ref())?;
+            // Arena fee totals track the lamport treasury only
+            if stake.signer.is_none() {
+                arena.total_fees_collected += lost_stake;
+            }
+        }
+    }
+
+    // Every resolution counts as participation, so the entry is placed even on a miss
+    entry.rank = leaderboard
+        .load_mut()?
+        .record(entry.key(), entry.player, entry.score);
+
+    emit!(PredictionResolved {
+        prediction: prediction.key(),
+        agent: agent.key(),
+        was_correct,
+        score_earned: if was_correct { entry.score } else { 0 },
+        stake_transferred: if was_correct { prediction.stake_amount } else { 0 },
+    });
+
+    Ok(())
+}
+
+/// Aggregate price read from a Pyth-layout price account
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Parse a Pyth v2 price account (or, with the `mock-oracle` feature, a program-owned
+/// mock in the same layout)
+pub fn load_oracle_price(price_feed: &AccountInfo) -> Result<OraclePrice> {
+    let mock_feed = cfg!(feature = "mock-oracle") && price_feed.owner == &crate::ID;
+    require!(
+        PYTH_ORACLE_PROGRAM_IDS.contains(price_feed.owner) || mock_feed,
+        ErrorCode::InvalidPriceFeed
+    );
+
+    let data = price_feed.try_borrow_data()?;
+    require!(data.len() >= PYTH_PRICE_ACCOUNT_MIN_LEN, ErrorCode::InvalidPriceFeed);
+
+    let read_u32 = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    let read_u64 = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+    require!(read_u32(0) == PYTH_MAGIC, ErrorCode::InvalidPriceFeed);
+    require!(read_u32(4) == PYTH_VERSION_2, ErrorCode::InvalidPriceFeed);
+    require!(read_u32(8) == PYTH_ACCOUNT_TYPE_PRICE, ErrorCode::InvalidPriceFeed);
+    require!(
+        read_u32(PYTH_AGG_STATUS_OFFSET) == PYTH_STATUS_TRADING,
+        ErrorCode::PriceUnavailable
+    );
+
+    Ok(OraclePrice {
+        price: read_u64(PYTH_AGG_PRICE_OFFSET) as i64,
+        conf: read_u64(PYTH_AGG_CONF_OFFSET),
+        expo: read_u32(PYTH_EXPO_OFFSET) as i32,
+        publish_time: read_u64(PYTH_TIMESTAMP_OFFSET) as i64,
+    })
+}
+
+/// Load the price that settles a horizon: published at or after `resolve_after`, and
+/// within the feed's `max_staleness` of it, so the result can't be chosen by cranking
+/// at a convenient later moment
+pub fn load_horizon_price(
+    price_feed: &AccountInfo,
+    asset_feed: &AssetFeed,
+    resolve_after: i64,
+) -> Result<OraclePrice> {
+    let price = load_oracle_price(price_feed)?;
+    require!(price.publish_time >= resolve_after, ErrorCode::StalePrice);
+    require!(
+        price.publish_time <= resolve_after + asset_feed.max_staleness,
+        ErrorCode::ResolutionWindowMissed
+    );
+    Ok(price)
+}
+
+/// Whether a horizon can no longer settle: `max_staleness` has passed since `resolve_after`
+/// and the feed holds no price published inside that window
+pub fn horizon_window_missed(
+    price_feed: &AccountInfo,
+    asset_feed: &AssetFeed,
+    resolve_after: i64,
+) -> Result<bool> {
+    let deadline = resolve_after
+        .checked_add(asset_feed.max_staleness)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(Clock::get()?.unix_timestamp > deadline
+        && load_horizon_price(price_feed, asset_feed, resolve_after).is_err())
+}
+
+/// Express `value * 10^value_expo` in units of `10^expo` (`expo` <= `value_expo`)
+fn scale_price(value: i64, value_expo: i32, expo: i32) -> Result<i128> {
+    10i128
+        .checked_pow((value_expo - expo) as u32)
+        .and_then(|factor| (value as i128).checked_mul(factor))
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))
+}
+
+/// Compare an oracle price against a target, normalising both to the smaller exponent
+fn price_target_reached(
+    direction: Direction,
+    price: i64,
+    price_expo: i32,
+    target_price: i64,
+    target_expo: i32,
+) -> Result<bool> {
+    let expo = price_expo.min(target_expo);
+    let price = scale_price(price, price_expo, expo)?;
+    let target = scale_price(target_price, target_expo, expo)?;
+
+    Ok(match direction {
+        Direction::Up => price >= target,
+        Direction::Down => price <= target,
+    })
+}
+
+/// How far each of two (price, expo) targets lands from an oracle price,
+/// all normalised to the smallest exponent so the distances compare directly
+fn target_distances(
+    price: i64,
+    price_expo: i32,
+    a: (i64, i32),
+    b: (i64, i32),
+) -> Result<(u128, u128)> {
+    let expo = price_expo.min(a.1).min(b.1);
+    let price = scale_price(price, price_expo, expo)?;
+    let distance = |(target, target_expo): (i64, i32)| -> Result<u128> {
+        Ok(price.abs_diff(scale_price(target, target_expo, expo)?))
+    };
+    Ok((distance(a)?, distance(b)?))
+}
+
+/// Asset ids are upper-case ASCII letters/digits, left aligned and zero padded
+fn is_valid_asset_id(asset_id: &[u8; 8]) -> bool {
+    let len = asset_id.iter().position(|&b| b == 0).unwrap_or(asset_id.len());
+    len > 0
+        && asset_id[..len]
+            .iter()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        && asset_id[len..].iter().all(|&b| b == 0)
+}
+
+fn calculate_score(stake: u64, streak: u16) -> u64 {
+    let streak_multiplier = 100 + (streak as u64 * 10); // 1.0 + 0.1 per streak
+    (stake * streak_multiplier) / 100
+}
+
+fn update_rank(agent: &mut Account<Agent>) -> Result<()> {
+    let accuracy = if agent.total_predictions > 0 {
+        (agent.correct_predictions as u64 * 100) / agent.total_predictions as u64
+    } else {
+        0
+    };
+    
+    agent.rank = match (accuracy, agent.best_streak, agent.correct_predictions) {
+        (acc, _, _) if acc >= 80 && agent.correct_predictions >= 50 => Rank::Legend,
+        (acc, streak, _) if acc >= 70 && streak >= 10 => Rank::Diamond,
+        (acc, streak, _) if acc >= 60 && streak >= 5 => Rank::Gold,
+        (acc, streak, _) if acc >= 50 && streak >= 3 => Rank::Silver,
+        _ => Rank::Bronze,
     };
     
     Ok(())
 }
 
-// Account structures
+// Account structures
+#[derive(Accounts)]
+pub struct InitializeArena<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Arena::SIZE,
+        seeds = [b"arena"],
+        bump
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::SIZE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut, has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        constraint = arena.pending_authority == pending_authority.key() @ ErrorCode::NotPendingAuthority
+    )]
+    pub arena: Account<'info, Arena>,
+    pub pending_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(mut, has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+    #[account(mut, has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(mut, has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterAgent<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + AgentRegistry::SIZE,
+        seeds = [b"agent_registry", owner.key().as_ref()],
+        bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Agent::SIZE,
+        seeds = [b"agent", owner.key().as_ref(), &agent_registry.next_index.to_le_bytes()],
+        bump
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAgent<'info> {
+    #[account(mut, has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(key: Pubkey)]
+pub struct SetDelegate<'info> {
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + AgentDelegate::SIZE,
+        seeds = [b"delegate", agent.key().as_ref(), key.as_ref()],
+        bump
+    )]
+    pub delegate: Account<'info, AgentDelegate>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    #[account(mut, has_one = agent, close = owner)]
+    pub delegate: Account<'info, AgentDelegate>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAgentTransfer<'info> {
+    #[account(mut, has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAgentTransfer<'info> {
+    #[account(
+        mut,
+        constraint = agent.pending_owner == new_owner.key() @ ErrorCode::NotPendingAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        seeds = [b"agent_registry", agent.owner.as_ref()],
+        bump = old_registry.bump
+    )]
+    pub old_registry: Account<'info, AgentRegistry>,
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = 8 + AgentRegistry::SIZE,
+        seeds = [b"agent_registry", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_registry: Account<'info, AgentRegistry>,
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAgentTransfer<'info> {
+    #[account(mut, has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RetireAgent<'info> {
+    #[account(
+        mut,
+        has_one = owner @ ErrorCode::UnauthorizedAgent,
+        has_one = creator,
+        realloc = 8 + Agent::RETIRED_SIZE,
+        realloc::payer = creator,
+        realloc::zero = false
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        seeds = [b"agent_registry", owner.key().as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    /// CHECK: Creator that paid the agent's rent, receiving the freed endpoint space
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSeason<'info> {
+    #[account(
+        init,
+        payer = season_operator,
+        space = 8 + Season::SIZE,
+        seeds = [b"season", arena.total_seasons.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub season: Account<'info, Season>,
+    #[account(
+        init,
+        payer = season_operator,
+        space = 8 + Leaderboard::SIZE,
+        seeds = [b"leaderboard", season.key().as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+    #[account(
+        init,
+        payer = season_operator,
+        space = 8 + SeasonVault::SIZE,
+        seeds = [b"vault", season.key().as_ref()],
+        bump
+    )]
+    pub season_vault: Account<'info, SeasonVault>,
+    #[account(mut, has_one = season_operator)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut)]
+    pub season_operator: Signer<'info>,
+    /// SPL mint the season is played in; omitted for native SOL seasons
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    /// Arena-owned token vault holding an SPL season's prize pool
+    #[account(
+        init,
+        payer = season_operator,
+        seeds = [b"season_token_vault", season.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = arena
+    )]
+    pub season_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnterSeason<'info> {
+    #[account(mut)]
+    pub season: Account<'info, Season>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + SeasonEntry::SIZE,
+        seeds = [b"entry", season.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub season_entry: Account<'info, SeasonEntry>,
+    #[account(mut, constraint = agent.owner == player.key() @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    /// Season vault for entry fees
+    #[account(
+        mut,
+        seeds = [b"vault", season.key().as_ref()],
+        bump = season_vault.bump
+    )]
+    pub season_vault: Account<'info, SeasonVault>,
+    /// CHECK: Treasury account for fee collection
+    #[account(mut, address = arena.treasury)]
+    pub treasury: AccountInfo<'info>,
+    #[account(address = season.mint @ ErrorCode::TokenMintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    /// Player's token account paying an SPL season's entry fee
+    #[account(mut, token::mint = season.mint, token::authority = player)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"season_token_vault", season.key().as_ref()], bump)]
+    pub season_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"treasury_token_vault", season.mint.as_ref()], bump)]
+    pub treasury_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitPrediction<'info> {
+    #[account(mut)]
+    pub season: Account<'info, Season>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + Prediction::SIZE,
+        seeds = [b"prediction", agent.key().as_ref(), season.key().as_ref(), &agent.predictions_submitted.to_le_bytes()],
+        bump
+    )]
+    pub prediction: Account<'info, Prediction>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + PredictionVault::SIZE,
+        seeds = [b"prediction_vault", prediction.key().as_ref()],
+        bump
+    )]
+    pub prediction_vault: Account<'info, PredictionVault>,
+    /// Arena pause flags; also owns an SPL season's stake vault
+    #[account(seeds = [b"arena"], bump = arena.bump)]
+    pub arena: Account<'info, Arena>,
+    #[account(address = season.mint @ ErrorCode::TokenMintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    /// Player's token account paying an SPL season's stake
+    #[account(mut, token::mint = season.mint, token::authority = player)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = player,
+        seeds = [b"prediction_token_vault", prediction.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = arena
+    )]
+    pub prediction_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+    /// Required when `player` signs as a delegate rather than the agent owner
+    #[account(
+        seeds = [b"delegate", agent.key().as_ref(), player.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, AgentDelegate>>,
+}
+
+#[derive(Accounts)]
+pub struct RevealPrediction<'info> {
+    #[account(
+        mut,
+        constraint = prediction.agent == agent.key()
+    )]
+    pub prediction: Account<'info, Prediction>,
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    /// Required when `player` signs as a delegate rather than the agent owner
+    #[account(
+        seeds = [b"delegate", agent.key().as_ref(), player.key().as_ref()],
+        bump = delegate.bump
+    )]
+    pub delegate: Option<Account<'info, AgentDelegate>>,
+}
+
+#[derive(Accounts)]
+pub struct ResolvePrediction<'info> {
+    #[account(mut, has_one = agent @ ErrorCode::PredictionAgentMismatch)]
+    pub prediction: Account<'info, Prediction>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = season_entry.season_id == prediction.season_id @ ErrorCode::EntrySeasonMismatch,
+        constraint = season_entry.agent == agent.key() @ ErrorCode::EntryAgentMismatch
+    )]
+    pub season_entry: Account<'info, SeasonEntry>,
+    #[account(mut, has_one = resolver)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"leaderboard", prediction.season.as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+    /// CHECK: The asset's feed PDA; must be uninitialized, since assets with a
+    /// registered feed can only resolve through resolve_prediction_with_oracle
+    #[account(seeds = [b"asset_feed", prediction.asset_id.as_ref()], bump)]
+    pub asset_feed: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"prediction_vault", prediction.key().as_ref()],
+        bump
+    )]
+    pub prediction_vault: Account<'info, PredictionVault>,
+    /// CHECK: Treasury for collecting lost stakes
+    #[account(mut, address = arena.treasury)]
+    pub treasury: AccountInfo<'info>,
+    /// CHECK: Player to receive stake return, bound to the agent owner and entrant
+    #[account(
+        mut,
+        constraint = player.key() == agent.owner @ ErrorCode::PlayerNotAgentOwner,
+        constraint = player.key() == season_entry.player @ ErrorCode::PlayerNotEntrant
+    )]
+    pub player: AccountInfo<'info>,
+    #[account(address = prediction.mint @ ErrorCode::TokenMintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, seeds = [b"prediction_token_vault", prediction.key().as_ref()], bump)]
+    pub prediction_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"treasury_token_vault", prediction.mint.as_ref()], bump)]
+    pub treasury_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Player's token account receiving an SPL stake back
+    #[account(mut, token::mint = prediction.mint, token::authority = player)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+impl<'info> ResolvePrediction<'info> {
+    /// Native seasons settle lamports; SPL stakes settle through the token vaults
+    fn stake_accounts(&self) -> Result<StakeAccounts<'info>> {
+        if self.prediction.stake_amount == 0 || self.prediction.mint == Pubkey::default() {
+            return Ok(StakeAccounts {
+                vault: self.prediction_vault.to_account_info(),
+                treasury: self.treasury.to_account_info(),
+                player: self.player.to_account_info(),
+                signer: None,
+            });
+        }
+        Ok(StakeAccounts {
+            vault: required(&self.prediction_token_vault)?.to_account_info(),
+            treasury: required(&self.treasury_token_vault)?.to_account_info(),
+            player: required(&self.player_token_account)?.to_account_info(),
+            signer: Some(TokenVaultSigner::new(&self.token_program, &self.mint, &self.arena)?),
+        })
+    }
+}
+
+#[derive(Accounts)]
+pub struct ResolvePredictionWithOracle<'info> {
+    #[account(mut, has_one = agent @ ErrorCode::PredictionAgentMismatch)]
+    pub prediction: Account<'info, Prediction>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = season_entry.season_id == prediction.season_id @ ErrorCode::EntrySeasonMismatch,
+        constraint = season_entry.agent == agent.key() @ ErrorCode::EntryAgentMismatch
+    )]
+    pub season_entry: Account<'info, SeasonEntry>,
+    #[account(mut)]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        mut,
+        seeds = [b"leaderboard", prediction.season.as_ref()],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+    #[account(
+        seeds = [b"asset_feed", prediction.asset_id.as_ref()],
+        bump = asset_feed.bump
+    )]
+    pub asset_feed: Account<'info, AssetFeed>,
+    /// CHECK: Price account registered for the asset, parsed in load_oracle_price
+    #[account(address = asset_feed.price_feed @ ErrorCode::InvalidPriceFeed)]
+    pub price_feed: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"prediction_vault", prediction.key().as_ref()],
+        bump
+    )]
+    pub prediction_vault: Account<'info, PredictionVault>,
+    /// CHECK: Treasury for collecting lost stakes
+    #[account(mut, address = arena.treasury)]
+    pub treasury: AccountInfo<'info>,
+    /// CHECK: Player to receive stake return, bound to the agent owner and entrant
+    #[account(
+        mut,
+        constraint = player.key() == agent.owner @ ErrorCode::PlayerNotAgentOwner,
+        constraint = player.key() == season_entry.player @ ErrorCode::PlayerNotEntrant
+    )]
+    pub player: AccountInfo<'info>,
+    #[account(address = prediction.mint @ ErrorCode::TokenMintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, seeds = [b"prediction_token_vault", prediction.key().as_ref()], bump)]
+    pub prediction_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"treasury_token_vault", prediction.mint.as_ref()], bump)]
+    pub treasury_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Player's token account receiving an SPL stake back
+    #[account(mut, token::mint = prediction.mint, token::authority = player)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub cranker: Signer<'info>,
+}
+
+impl<'info> ResolvePredictionWithOracle<'info> {
+    /// Native seasons settle lamports; SPL stakes settle through the token vaults
+    fn stake_accounts(&self) -> Result<StakeAccounts<'info>> {
+        if self.prediction.stake_amount == 0 || self.prediction.mint == Pubkey::default() {
+            return Ok(StakeAccounts {
+                vault: self.prediction_vault.to_account_info(),
+                treasury: self.treasury.to_account_info(),
+                player: self.player.to_account_info(),
+                signer: None,
+            });
+        }
+        Ok(StakeAccounts {
+            vault: required(&self.prediction_token_vault)?.to_account_info(),
+            treasury: required(&self.treasury_token_vault)?.to_account_info(),
+            player: required(&self.player_token_account)?.to_account_info(),
+            signer: Some(TokenVaultSigner::new(&self.token_program, &self.mint, &self.arena)?),
+        })
+    }
+}
+
+#[derive(Accounts)]
+pub struct VoidPrediction<'info> {
+    #[account(mut, has_one = agent @ ErrorCode::PredictionAgentMismatch)]
+    pub prediction: Account<'info, Prediction>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        constraint = season_entry.season_id == prediction.season_id @ ErrorCode::EntrySeasonMismatch,
+        constraint = season_entry.agent == agent.key() @ ErrorCode::EntryAgentMismatch
+    )]
+    pub season_entry: Account<'info, SeasonEntry>,
+    pub arena: Account<'info, Arena>,
+    #[account(
+        seeds = [b"asset_feed", prediction.asset_id.as_ref()],
+        bump = asset_feed.bump
+    )]
+    pub asset_feed: Account<'info, AssetFeed>,
+    /// CHECK: Price account registered for the asset, parsed in load_oracle_price
+    #[account(address = asset_feed.price_feed @ ErrorCode::InvalidPriceFeed)]
+    pub price_feed: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"prediction_vault", prediction.key().as_ref()],
+        bump
+    )]
+    pub prediction_vault: Account<'info, PredictionVault>,
+    /// CHECK: Player to receive the stake back, bound to the agent owner and entrant
+    #[account(
+        mut,
+        constraint = player.key() == agent.owner @ ErrorCode::PlayerNotAgentOwner,
+        constraint = player.key() == season_entry.player @ ErrorCode::PlayerNotEntrant
+    )]
+    pub player: AccountInfo<'info>,
+    #[account(address = prediction.mint @ ErrorCode::TokenMintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, seeds = [b"prediction_token_vault", prediction.key().as_ref()], bump)]
+    pub prediction_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Player's token account receiving an SPL stake back
+    #[account(mut, token::mint = prediction.mint, token::authority = player)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SyncEntryRank<'info> {
+    pub season: Account<'info, Season>,
+    #[account(
+        mut,
+        constraint = season_entry.season_id == season.id @ ErrorCode::EntrySeasonMismatch
+    )]
+    pub season_entry: Account<'info, SeasonEntry>,
+    #[account(seeds = [b"leaderboard", season.key().as_ref()], bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+}
+
+#[derive(Accounts)]
+pub struct SyncEntryOwner<'info> {
+    pub season: Account<'info, Season>,
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = season_entry.season_id == season.id @ ErrorCode::EntrySeasonMismatch,
+        constraint = season_entry.agent == agent.key() @ ErrorCode::EntryAgentMismatch
+    )]
+    pub season_entry: Account<'info, SeasonEntry>,
+    #[account(mut, seeds = [b"leaderboard", season.key().as_ref()], bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+}
+
+#[derive(Accounts)]
+pub struct ForfeitPrediction<'info> {
+    #[account(mut, has_one = agent, has_one = season)]
+    pub prediction: Account<'info, Prediction>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub season: Account<'info, Season>,
+    #[account(
+        mut,
+        seeds = [b"entry", season.key().as_ref(), agent.key().as_ref()],
+        bump = season_entry.bump
+    )]
+    pub season_entry: Account<'info, SeasonEntry>,
+    #[account(mut)]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        mut,
+        seeds = [b"prediction_vault", prediction.key().as_ref()],
+        bump
+    )]
+    pub prediction_vault: Account<'info, PredictionVault>,
+    /// Season vault receiving stakes under ForfeitPolicy::PrizePool
+    #[account(
+        mut,
+        seeds = [b"vault", season.key().as_ref()],
+        bump = season_vault.bump
+    )]
+    pub season_vault: Account<'info, SeasonVault>,
+    /// CHECK: Treasury receiving stakes under ForfeitPolicy::Treasury
+    #[account(mut, address = arena.treasury)]
+    pub treasury: AccountInfo<'info>,
+    #[account(address = prediction.mint @ ErrorCode::TokenMintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, seeds = [b"prediction_token_vault", prediction.key().as_ref()], bump)]
+    pub prediction_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"season_token_vault", season.key().as_ref()], bump)]
+    pub season_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"treasury_token_vault", prediction.mint.as_ref()], bump)]
+    pub treasury_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: [u8; 8])]
+pub struct RegisterAssetFeed<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AssetFeed::SIZE,
+        seeds = [b"asset_feed", asset_id.as_ref()],
+        bump
+    )]
+    pub asset_feed: Account<'info, AssetFeed>,
+    #[account(has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: [u8; 8])]
+pub struct UpdateAssetFeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"asset_feed", asset_id.as_ref()],
+        bump = asset_feed.bump
+    )]
+    pub asset_feed: Account<'info, AssetFeed>,
+    #[account(has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: [u8; 8])]
+pub struct InitMockPriceFeed<'info> {
+    /// CHECK: Created here as a raw Pyth-layout account owned by this program
+    #[account(
+        mut,
+        seeds = [b"mock_price_feed", asset_id.as_ref()],
+        bump
+    )]
+    pub mock_price_feed: AccountInfo<'info>,
+    #[account(has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: [u8; 8])]
+pub struct SetMockPrice<'info> {
+    /// CHECK: Raw Pyth-layout account created by init_mock_price_feed
+    #[account(
+        mut,
+        seeds = [b"mock_price_feed", asset_id.as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub mock_price_feed: AccountInfo<'info>,
+    #[account(has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMockSeasonEnd<'info> {
+    #[account(mut)]
+    pub season: Account<'info, Season>,
+    #[account(has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AwardAchievement<'info> {
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = achievement_issuer,
+        space = 8 + Achievement::SIZE,
+        seeds = [b"achievement", agent.key().as_ref(), &(agent.reputation_score as u64).to_le_bytes()],
+        bump
+    )]
+    pub achievement: Account<'info, Achievement>,
+    #[account(mut, has_one = achievement_issuer)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut)]
+    pub achievement_issuer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DistributePrizes<'info> {
+    #[account(mut)]
+    pub season: Account<'info, Season>,
+    #[account(mut, has_one = season_operator)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut)]
+    pub season_operator: Signer<'info>,
+    /// Season vault holding prize pool
+    #[account(
+        mut,
+        seeds = [b"vault", season.key().as_ref()],
+        bump = season_vault.bump
+    )]
+    pub season_vault: Account<'info, SeasonVault>,
+    #[account(seeds = [b"leaderboard", season.key().as_ref()], bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+    /// CHECK: Treasury receiving unpaid prize shares
+    #[account(mut, address = arena.treasury)]
+    pub treasury: AccountInfo<'info>,
+    #[account(address = season.mint @ ErrorCode::TokenMintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, seeds = [b"season_token_vault", season.key().as_ref()], bump)]
+    pub season_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"treasury_token_vault", season.mint.as_ref()], bump)]
+    pub treasury_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSeason<'info> {
+    #[account(mut)]
+    pub season: Account<'info, Season>,
+    #[account(mut, has_one = season_operator)]
+    pub arena: Account<'info, Arena>,
+    pub season_operator: Signer<'info>,
+    /// Reserves an SPL season's platform fees when they are refunded
+    #[account(
+        mut,
+        seeds = [b"treasury_token_ledger", season.mint.as_ref()],
+        bump = treasury_token_ledger.bump
+    )]
+    pub treasury_token_ledger: Option<Account<'info, TreasuryTokenLedger>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub season: Account<'info, Season>,
+    #[account(
+        mut,
+        constraint = season_entry.season_id == season.id @ ErrorCode::EntrySeasonMismatch
+    )]
+    pub season_entry: Account<'info, SeasonEntry>,
+    #[account(mut)]
+    pub arena: Account<'info, Arena>,
+    /// Season vault holding the entry's prize contribution
+    #[account(
+        mut,
+        seeds = [b"vault", season.key().as_ref()],
+        bump = season_vault.bump
+    )]
+    pub season_vault: Account<'info, SeasonVault>,
+    /// CHECK: Treasury refunding the platform fee when the season allows it
+    #[account(mut, address = arena.treasury)]
+    pub treasury: AccountInfo<'info>,
+    /// CHECK: Entrant receiving the refund
+    #[account(mut, address = season_entry.player @ ErrorCode::PlayerNotEntrant)]
+    pub player: AccountInfo<'info>,
+    #[account(address = season.mint @ ErrorCode::TokenMintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut, seeds = [b"season_token_vault", season.key().as_ref()], bump)]
+    pub season_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"treasury_token_vault", season.mint.as_ref()], bump)]
+    pub treasury_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Pays down the mint's reserved refunds when the platform fee is refunded
+    #[account(
+        mut,
+        seeds = [b"treasury_token_ledger", season.mint.as_ref()],
+        bump = treasury_token_ledger.bump
+    )]
+    pub treasury_token_ledger: Option<Account<'info, TreasuryTokenLedger>>,
+    /// Entrant's token account receiving an SPL season's refund
+    #[account(mut, token::mint = season.mint, token::authority = player)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
 #[derive(Accounts)]
-pub struct InitializeArena<'info> {
+pub struct ClosePrediction<'info> {
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        has_one = agent @ ErrorCode::PredictionAgentMismatch,
+        has_one = rent_payer,
+        close = rent_payer
+    )]
+    pub prediction: Account<'info, Prediction>,
+    #[account(
+        mut,
+        seeds = [b"prediction_vault", prediction.key().as_ref()],
+        bump = prediction_vault.bump,
+        close = rent_payer
+    )]
+    pub prediction_vault: Account<'info, PredictionVault>,
+    /// CHECK: Wallet that paid the prediction's rent at submission
+    #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+    pub owner: Signer<'info>,
+    /// Authority over an SPL prediction's token vault
+    #[account(seeds = [b"arena"], bump = arena.bump)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut, seeds = [b"prediction_token_vault", prediction.key().as_ref()], bump)]
+    pub prediction_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Receives a Token-2022 vault's withheld transfer fees before it closes
+    #[account(mut, address = prediction.mint @ ErrorCode::TokenMintMismatch)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// The prediction's betting pool, required once anyone has bet on it
+    #[account(address = prediction.betting_pool)]
+    pub betting_pool: Option<Account<'info, BettingPool>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSeasonEntry<'info> {
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    pub season: Account<'info, Season>,
+    #[account(
+        mut,
+        constraint = season_entry.season_id == season.id @ ErrorCode::EntrySeasonMismatch,
+        constraint = season_entry.agent == agent.key() @ ErrorCode::EntryAgentMismatch,
+        has_one = rent_payer,
+        close = rent_payer
+    )]
+    pub season_entry: Account<'info, SeasonEntry>,
+    /// CHECK: Wallet that paid the entry's rent
+    #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAchievement<'info> {
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    #[account(mut, has_one = agent, has_one = rent_payer, close = rent_payer)]
+    pub achievement: Account<'info, Achievement>,
+    /// CHECK: Issuer that paid the achievement's rent
+    #[account(mut)]
+    pub rent_payer: AccountInfo<'info>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(mut, has_one = treasurer)]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub treasurer: Signer<'info>,
+    /// Token vault to withdraw from instead of the lamport treasury
+    #[account(
+        mut,
+        seeds = [b"treasury_token_vault", treasury_token_vault.mint.as_ref()],
+        bump
+    )]
+    pub treasury_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The vault mint's ledger, holding back the refunds it owes
+    #[account(
+        mut,
+        seeds = [b"treasury_token_ledger", treasury_token_ledger.mint.as_ref()],
+        bump = treasury_token_ledger.bump
+    )]
+    pub treasury_token_ledger: Option<Account<'info, TreasuryTokenLedger>>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct InitTreasuryTokenVault<'info> {
+    #[account(has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    pub mint: InterfaceAccount<'info, Mint>,
     #[account(
         init,
         payer = authority,
-        space = 8 + Arena::SIZE,
-        seeds = [b"arena"],
+        seeds = [b"treasury_token_vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = arena
+    )]
+    pub treasury_token_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TreasuryTokenLedger::SIZE,
+        seeds = [b"treasury_token_ledger", mint.key().as_ref()],
         bump
     )]
-    pub arena: Account<'info, Arena>,
-    /// CHECK: Treasury account for fee collection (can be any account)
-    #[account(mut)]
-    pub treasury: AccountInfo<'info>,
+    pub treasury_token_ledger: Account<'info, TreasuryTokenLedger>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct RegisterAgent<'info> {
+pub struct SetFee<'info> {
+    #[account(mut, has_one = authority)]
+    pub arena: Account<'info, Arena>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(opponent: Pubkey, asset_id: [u8; 8])]
+pub struct CreateDuel<'info> {
+    #[account(mut, seeds = [b"arena"], bump = arena.bump)]
+    pub arena: Account<'info, Arena>,
     #[account(
         init,
         payer = owner,
-        space = 8 + Agent::SIZE,
-        seeds = [b"agent", owner.key().as_ref()],
+        space = 8 + Duel::SIZE,
+        seeds = [b"duel", arena.total_duels.to_le_bytes().as_ref()],
         bump
     )]
-    pub agent: Account<'info, Agent>,
+    pub duel: Account<'info, Duel>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DuelVault::SIZE,
+        seeds = [b"duel_vault", duel.key().as_ref()],
+        bump
+    )]
+    pub duel_vault: Account<'info, DuelVault>,
+    #[account(mut, has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub challenger: Account<'info, Agent>,
+    /// Duels can only be created on assets with a registered price feed
+    #[account(seeds = [b"asset_feed", asset_id.as_ref()], bump = asset_feed.bump)]
+    pub asset_feed: Account<'info, AssetFeed>,
     #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptDuel<'info> {
+    #[account(seeds = [b"arena"], bump = arena.bump)]
     pub arena: Account<'info, Arena>,
     #[account(mut)]
+    pub duel: Account<'info, Duel>,
+    #[account(
+        mut,
+        seeds = [b"duel_vault", duel.key().as_ref()],
+        bump = duel_vault.bump
+    )]
+    pub duel_vault: Account<'info, DuelVault>,
+    #[account(mut, has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub opponent: Account<'info, Agent>,
+    #[account(mut)]
     pub owner: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CreateSeason<'info> {
+pub struct RevealDuel<'info> {
+    #[account(mut)]
+    pub duel: Account<'info, Duel>,
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDuel<'info> {
+    #[account(mut)]
+    pub duel: Account<'info, Duel>,
+    #[account(
+        mut,
+        seeds = [b"duel_vault", duel.key().as_ref()],
+        bump = duel_vault.bump
+    )]
+    pub duel_vault: Account<'info, DuelVault>,
+    #[account(mut, address = duel.challenger @ ErrorCode::DuelParticipantMismatch)]
+    pub challenger: Account<'info, Agent>,
+    #[account(mut, address = duel.opponent @ ErrorCode::DuelParticipantMismatch)]
+    pub opponent: Account<'info, Agent>,
+    /// CHECK: Wallet that staked the challenger side
+    #[account(mut, address = duel.challenger_owner)]
+    pub challenger_owner: AccountInfo<'info>,
+    /// CHECK: Wallet that staked the opponent side
+    #[account(mut, address = duel.opponent_owner)]
+    pub opponent_owner: AccountInfo<'info>,
+    #[account(mut, seeds = [b"arena"], bump = arena.bump)]
+    pub arena: Account<'info, Arena>,
+    /// CHECK: Treasury receiving the duel fee
+    #[account(mut, address = arena.treasury)]
+    pub treasury: AccountInfo<'info>,
+    #[account(
+        seeds = [b"asset_feed", duel.asset_id.as_ref()],
+        bump = asset_feed.bump
+    )]
+    pub asset_feed: Account<'info, AssetFeed>,
+    /// CHECK: Validated against the registered feed and parsed by `load_oracle_price`
+    #[account(address = asset_feed.price_feed @ ErrorCode::InvalidPriceFeed)]
+    pub price_feed: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDuel<'info> {
+    #[account(mut)]
+    pub duel: Account<'info, Duel>,
+    #[account(
+        mut,
+        seeds = [b"duel_vault", duel.key().as_ref()],
+        bump = duel_vault.bump
+    )]
+    pub duel_vault: Account<'info, DuelVault>,
+    #[account(mut, address = duel.challenger @ ErrorCode::DuelParticipantMismatch)]
+    pub challenger: Account<'info, Agent>,
+    /// CHECK: Wallet that staked the challenge, receiving the refund
+    #[account(mut, address = duel.challenger_owner)]
+    pub challenger_owner: AccountInfo<'info>,
+    pub canceller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(terms: TournamentTerms)]
+pub struct CreateTournament<'info> {
     #[account(
         init,
-        payer = authority,
-        space = 8 + Season::SIZE,
-        seeds = [b"season", arena.total_seasons.to_le_bytes().as_ref()],
+        payer = season_operator,
+        space = 8 + Tournament::SIZE,
+        seeds = [b"tournament", arena.total_tournaments.to_le_bytes().as_ref()],
         bump
     )]
-    pub season: Account<'info, Season>,
-    #[account(mut, has_one = authority)]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        init,
+        payer = season_operator,
+        space = 8 + TournamentVault::SIZE,
+        seeds = [b"tournament_vault", tournament.key().as_ref()],
+        bump
+    )]
+    pub tournament_vault: Account<'info, TournamentVault>,
+    #[account(mut, has_one = season_operator)]
     pub arena: Account<'info, Arena>,
+    /// Tournaments can only be played on assets with a registered price feed
+    #[account(seeds = [b"asset_feed", terms.asset_id.as_ref()], bump = asset_feed.bump)]
+    pub asset_feed: Account<'info, AssetFeed>,
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub season_operator: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct EnterSeason<'info> {
+pub struct JoinTournament<'info> {
+    #[account(seeds = [b"arena"], bump = arena.bump)]
+    pub arena: Account<'info, Arena>,
     #[account(mut)]
-    pub season: Account<'info, Season>,
+    pub tournament: Account<'info, Tournament>,
     #[account(
-        init,
-        payer = player,
-        space = 8 + SeasonEntry::SIZE,
-        seeds = [b"entry", season.key().as_ref(), agent.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"tournament_vault", tournament.key().as_ref()],
+        bump = tournament_vault.bump
     )]
-    pub season_entry: Account<'info, SeasonEntry>,
+    pub tournament_vault: Account<'info, TournamentVault>,
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveTournament<'info> {
     #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        mut,
+        seeds = [b"tournament_vault", tournament.key().as_ref()],
+        bump = tournament_vault.bump
+    )]
+    pub tournament_vault: Account<'info, TournamentVault>,
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
     pub agent: Account<'info, Agent>,
     #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartTournament<'info> {
+    #[account(mut, has_one = season_operator)]
     pub arena: Account<'info, Arena>,
     #[account(mut)]
-    pub player: Signer<'info>,
-    /// CHECK: Season vault for entry fees
+    pub tournament: Account<'info, Tournament>,
     #[account(
         mut,
-        seeds = [b"vault", season.key().as_ref()],
-        bump
+        seeds = [b"tournament_vault", tournament.key().as_ref()],
+        bump = tournament_vault.bump
     )]
-    pub season_vault: AccountInfo<'info>,
-    /// CHECK: Treasury account for fee collection
+    pub tournament_vault: Account<'info, TournamentVault>,
+    /// CHECK: Treasury receiving the platform share of the entry fees
     #[account(mut, address = arena.treasury)]
     pub treasury: AccountInfo<'info>,
-    pub system_program: Program<'info, System>,
+    pub season_operator: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct SubmitPrediction<'info> {
+pub struct CancelTournament<'info> {
+    #[account(has_one = season_operator)]
+    pub arena: Account<'info, Arena>,
     #[account(mut)]
-    pub season: Account<'info, Season>,
+    pub tournament: Account<'info, Tournament>,
+    pub season_operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitTournamentPrediction<'info> {
     #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
     pub agent: Account<'info, Agent>,
-    #[account(
-        init,
-        payer = player,
-        space = 8 + Prediction::SIZE,
-        seeds = [b"prediction", agent.key().as_ref(), season.key().as_ref(), &agent.total_predictions.to_le_bytes()],
-        bump
-    )]
-    pub prediction: Account<'info, Prediction>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealTournamentPrediction<'info> {
     #[account(mut)]
-    pub player: Signer<'info>,
+    pub tournament: Account<'info, Tournament>,
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveTournamentMatch<'info> {
+    #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
     #[account(
-        init,
-        payer = player,
-        space = 8 + PredictionVault::SIZE,
-        seeds = [b"prediction_vault", prediction.key().as_ref()],
-        bump
+        seeds = [b"asset_feed", tournament.asset_id.as_ref()],
+        bump = asset_feed.bump
     )]
-    pub prediction_vault: Account<'info, PredictionVault>,
-    pub system_program: Program<'info, System>,
+    pub asset_feed: Account<'info, AssetFeed>,
+    /// CHECK: Validated against the registered feed and parsed by `load_oracle_price`
+    #[account(address = asset_feed.price_feed @ ErrorCode::InvalidPriceFeed)]
+    pub price_feed: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
-pub struct RevealPrediction<'info> {
+pub struct ClaimTournamentPrize<'info> {
+    #[account(seeds = [b"arena"], bump = arena.bump)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
     #[account(
         mut,
-        constraint = prediction.agent == agent.key()
+        seeds = [b"tournament_vault", tournament.key().as_ref()],
+        bump = tournament_vault.bump
+    )]
+    pub tournament_vault: Account<'info, TournamentVault>,
+    #[account(
+        address = tournament.champion @ ErrorCode::NotTournamentChampion,
+        has_one = owner @ ErrorCode::UnauthorizedAgent
     )]
-    pub prediction: Account<'info, Prediction>,
     pub agent: Account<'info, Agent>,
     #[account(mut)]
-    pub player: Signer<'info>,
+    pub owner: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct ResolvePrediction<'info> {
-    #[account(mut)]
+pub struct PlaceBet<'info> {
+    #[account(seeds = [b"arena"], bump = arena.bump)]
+    pub arena: Account<'info, Arena>,
+    #[account(mut, has_one = agent @ ErrorCode::PredictionAgentMismatch)]
     pub prediction: Account<'info, Prediction>,
-    #[account(mut)]
     pub agent: Account<'info, Agent>,
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = 8 + BettingPool::SIZE,
+        seeds = [b"betting_pool", prediction.key().as_ref()],
+        bump
+    )]
+    pub betting_pool: Account<'info, BettingPool>,
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = 8 + BettingVault::SIZE,
+        seeds = [b"betting_vault", betting_pool.key().as_ref()],
+        bump
+    )]
+    pub betting_vault: Account<'info, BettingVault>,
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = 8 + Bet::SIZE,
+        seeds = [b"bet", betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
     #[account(mut)]
-    pub season_entry: Account<'info, SeasonEntry>,
-    #[account(mut, has_one = authority)]
+    pub bettor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleBettingPool<'info> {
+    #[account(mut, seeds = [b"arena"], bump = arena.bump)]
     pub arena: Account<'info, Arena>,
+    #[account(address = betting_pool.prediction)]
+    pub prediction: Account<'info, Prediction>,
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub betting_pool: Account<'info, BettingPool>,
     #[account(
         mut,
-        seeds = [b"prediction_vault", prediction.key().as_ref()],
-        bump
+        seeds = [b"betting_vault", betting_pool.key().as_ref()],
+        bump = betting_vault.bump
     )]
-    pub prediction_vault: Account<'info, PredictionVault>,
-    /// CHECK: Treasury for collecting lost stakes
+    pub betting_vault: Account<'info, BettingVault>,
+    /// CHECK: Treasury receiving the house cut
     #[account(mut, address = arena.treasury)]
     pub treasury: AccountInfo<'info>,
-    /// CHECK: Player to receive stake return
+}
+
+#[derive(Accounts)]
+pub struct ClaimBet<'info> {
+    #[account(seeds = [b"arena"], bump = arena.bump)]
+    pub arena: Account<'info, Arena>,
+    pub betting_pool: Account<'info, BettingPool>,
+    #[account(
+        mut,
+        seeds = [b"betting_vault", betting_pool.key().as_ref()],
+        bump = betting_vault.bump
+    )]
+    pub betting_vault: Account<'info, BettingVault>,
+    #[account(
+        mut,
+        seeds = [b"bet", betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump = bet.bump,
+        close = bettor
+    )]
+    pub bet: Account<'info, Bet>,
     #[account(mut)]
-    pub player: AccountInfo<'info>,
+    pub bettor: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct AwardAchievement<'info> {
+pub struct SetSubscriptionPlan<'info> {
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + SubscriptionPlan::SIZE,
+        seeds = [b"subscription_plan", agent.key().as_ref()],
+        bump
+    )]
+    pub subscription_plan: Account<'info, SubscriptionPlan>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + AgentRevenueVault::SIZE,
+        seeds = [b"agent_revenue_vault", agent.key().as_ref()],
+        bump
+    )]
+    pub revenue_vault: Account<'info, AgentRevenueVault>,
     #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(mut, seeds = [b"arena"], bump = arena.bump)]
+    pub arena: Account<'info, Arena>,
     pub agent: Account<'info, Agent>,
     #[account(
-        init,
-        payer = authority,
-        space = 8 + Achievement::SIZE,
-        seeds = [b"achievement", agent.key().as_ref(), &(agent.reputation_score as u64).to_le_bytes()],
+        seeds = [b"subscription_plan", agent.key().as_ref()],
+        bump = subscription_plan.bump
+    )]
+    pub subscription_plan: Account<'info, SubscriptionPlan>,
+    #[account(
+        mut,
+        seeds = [b"agent_revenue_vault", agent.key().as_ref()],
+        bump = revenue_vault.bump
+    )]
+    pub revenue_vault: Account<'info, AgentRevenueVault>,
+    #[account(
+        init_if_needed,
+        payer = subscriber,
+        space = 8 + Subscription::SIZE,
+        seeds = [b"subscription", agent.key().as_ref(), subscriber.key().as_ref()],
         bump
     )]
-    pub achievement: Account<'info, Achievement>,
-    #[account(mut, has_one = authority)]
-    pub arena: Account<'info, Arena>,
+    pub subscription: Account<'info, Subscription>,
+    /// CHECK: Treasury receiving the arena's share of the payment
+    #[account(mut, address = arena.treasury)]
+    pub treasury: AccountInfo<'info>,
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub subscriber: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct DistributePrizes<'info> {
-    #[account(mut)]
-    pub season: Account<'info, Season>,
-    #[account(mut, has_one = authority)]
-    pub arena: Account<'info, Arena>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    /// CHECK: Season vault holding prize pool
+pub struct VerifySubscription<'info> {
+    #[account(
+        seeds = [b"subscription", subscription.agent.as_ref(), subscription.subscriber.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSubscription<'info> {
     #[account(
         mut,
-        seeds = [b"vault", season.key().as_ref()],
-        bump
+        seeds = [b"subscription", subscription.agent.as_ref(), subscriber.key().as_ref()],
+        bump = subscription.bump,
+        close = subscriber
     )]
-    pub season_vault: AccountInfo<'info>,
-    /// CHECK: First place winner account
+    pub subscription: Account<'info, Subscription>,
     #[account(mut)]
-    pub first_place: AccountInfo<'info>,
-    /// CHECK: Second place winner account
-    #[account(mut)]
-    pub second_place: AccountInfo<'info>,
-    /// CHECK: Third place winner account
-    #[account(mut)]
-    pub third_place: AccountInfo<'info>,
-    /// First place season entry (for validation)
-    pub first_place_entry: Account<'info, SeasonEntry>,
-    /// Second place season entry (for validation)
-    pub second_place_entry: Account<'info, SeasonEntry>,
-    /// Third place season entry (for validation)
-    pub third_place_entry: Account<'info, SeasonEntry>,
+    pub subscriber: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct WithdrawTreasury<'info> {
-    #[account(mut, has_one = authority)]
-    pub arena: Account<'info, Arena>,
-    /// CHECK: Treasury account
-    #[account(mut, address = arena.treasury)]
-    pub treasury: AccountInfo<'info>,
+pub struct WithdrawAgentRevenue<'info> {
+    #[account(has_one = owner @ ErrorCode::UnauthorizedAgent)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        seeds = [b"agent_revenue_vault", agent.key().as_ref()],
+        bump = revenue_vault.bump
+    )]
+    pub revenue_vault: Account<'info, AgentRevenueVault>,
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub owner: Signer<'info>,
 }
 
 // Data structures
 #[account]
 pub struct Arena {
     pub authority: Pubkey,
-    pub treasury: Pubkey,        // Fee collection address
+    pub treasury: Pubkey,        // Treasury vault PDA
     pub total_seasons: u64,
     pub total_agents: u64,
     pub total_fees_collected: u64,
     pub bump: u8,
+    pub total_withdrawn: u64,    // Withdrawn by the treasurer
+    pub total_refunded: u64,     // Platform fees returned by season cancellations
+    pub pending_authority: Pubkey, // Proposed authority awaiting acceptance; default when none
+    pub resolver: Pubkey,           // Resolves predictions manually
+    pub season_operator: Pubkey,    // Creates, cancels and distributes seasons
+    pub achievement_issuer: Pubkey, // Awards achievements
+    pub treasurer: Pubkey,          // Withdraws treasury fees
+    pub pause_flags: u8,            // PAUSE_* bits for halted instruction classes
+    pub total_duels: u64,           // Duel PDA seed counter
+    pub duel_fee_bps: u16,          // Cut of a duel pot taken by the treasury
+    pub total_tournaments: u64,     // Tournament PDA seed counter
+    pub betting_fee_bps: u16,       // House cut of a spectator betting pool
+    pub subscription_fee_bps: u16,  // Treasury share of agent subscription payments
+    pub refunds_owed: u64,          // Platform fees of cancelled seasons not yet claimed back
 }
 
 impl Arena {
-    pub const SIZE: usize = 32 + 32 + 8 + 8 + 8 + 1;
+    pub const SIZE: usize = 32 + 32 + 8 + 8 + 8 + 1 + 8 + 8 + 32 + 32 * 4 + 1 + 8 + 2 + 8 + 2 + 2 + 8;
+
+    pub fn require_unpaused(&self, flag: u8) -> Result<()> {
+        require!(self.pause_flags & flag == 0, ErrorCode::ArenaPaused);
+        Ok(())
+    }
+}
+
+/// Program-owned vault collecting platform fees and lost stakes
+#[account]
+pub struct Treasury {
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const SIZE: usize = 1;
+}
+
+/// A treasury token vault's bookkeeping: the token counterpart of the arena's lamport totals
+#[account]
+pub struct TreasuryTokenLedger {
+    pub mint: Pubkey,
+    pub refunds_owed: u64,    // Platform fees of cancelled seasons not yet claimed back
+    pub total_refunded: u64,
+    pub total_withdrawn: u64, // Withdrawn by the treasurer
+    pub bump: u8,
+}
+
+impl TreasuryTokenLedger {
+    pub const SIZE: usize = 32 + 8 + 8 + 8 + 1;
+}
+
+/// Program-owned vault holding a season's prize pool
+#[account]
+pub struct SeasonVault {
+    pub bump: u8,
+}
+
+impl SeasonVault {
+    pub const SIZE: usize = 1;
 }
 
 #[account]
@@ -702,10 +4440,62 @@ pub struct Agent {
     pub reputation_score: u32,
     pub joined_at: i64,
     pub bump: u8,
+    pub predictions_submitted: u64, // Prediction PDA seed nonce, bumped on every submit
+    pub open_predictions: u32,      // Submitted but not yet resolved or forfeited
+    pub last_updated_at: i64,       // Last update_agent, for the update cooldown
+    pub index: u16,                 // Creator's AgentRegistry index, part of the PDA seeds
+    pub creator: Pubkey,            // Registering wallet, part of the PDA seeds; never changes
+    pub pending_owner: Pubkey,      // Proposed new owner, default when no transfer is pending
+    pub status: AgentStatus,
+    pub retired_at: i64,
 }
 
 impl Agent {
-    pub const SIZE: usize = 32 + 4 + 32 + 4 + 128 + 8 + 8 + 2 + 2 + 1 + 4 + 8 + 1;
+    pub const SIZE: usize = 32 + 4 + 32 + 4 + 128 + 8 + 8 + 2 + 2 + 1 + 4 + 8 + 1 + 8 + 4 + 8 + 2 + 32 + 32 + 1 + 8;
+    /// Tombstone size: a retired agent drops its endpoint
+    pub const RETIRED_SIZE: usize = Self::SIZE - MAX_AGENT_ENDPOINT_LEN;
+
+    pub fn require_active(&self) -> Result<()> {
+        require!(self.status == AgentStatus::Active, ErrorCode::AgentRetired);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct AgentRegistry {
+    pub owner: Pubkey,
+    pub next_index: u16,      // Seed index for the owner's next agent
+    pub agents: Vec<Pubkey>,  // Max MAX_AGENTS_PER_OWNER
+    pub bump: u8,
+}
+
+impl AgentRegistry {
+    pub const SIZE: usize = 32 + 2 + 4 + 32 * MAX_AGENTS_PER_OWNER + 1;
+}
+
+#[account]
+pub struct AgentDelegate {
+    pub agent: Pubkey,
+    pub key: Pubkey,          // Hot key allowed to sign for the agent
+    pub scopes: u8,           // DELEGATE_* bits
+    pub max_stake: u64,       // Per-prediction stake cap, in lamports or mint base units
+    pub expires_at: i64,
+    pub bump: u8,
+    pub granted_by: Pubkey,   // Owner that granted the key; lapses if the agent changes hands
+}
+
+impl AgentDelegate {
+    pub const SIZE: usize = 32 + 32 + 1 + 8 + 8 + 1 + 32;
+
+    pub fn authorize(&self, agent: &Agent, scope: u8) -> Result<()> {
+        require!(self.granted_by == agent.owner, ErrorCode::StaleDelegate);
+        require!(self.scopes & scope != 0, ErrorCode::DelegateScopeMissing);
+        require!(
+            Clock::get()?.unix_timestamp < self.expires_at,
+            ErrorCode::DelegateExpired
+        );
+        Ok(())
+    }
 }
 
 #[account]
@@ -720,57 +4510,522 @@ pub struct Season {
     pub total_pool: u64,      // Prize pool amount
     pub status: SeasonStatus,
     pub bump: u8,
+    pub reveal_window: i64,   // Seconds after submission a prediction must be revealed
+    pub forfeit_policy: ForfeitPolicy,
+    pub payout_bps: [u16; LEADERBOARD_SIZE], // Prize share per place, first `payout_places` used
+    pub payout_places: u8,
+    pub remainder_policy: RemainderPolicy,
+    pub refund_platform_fee: bool, // Whether cancellation refunds also return the platform fee
+    pub mint: Pubkey,              // SPL mint for fees, stakes and prizes; default for native SOL
+    pub total_platform_fees: u64,  // Entry fee shares sent to the treasury
+}
+
+impl Season {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 2 + 8 + 8 + 1 + 1 + 8 + 1 + 2 * LEADERBOARD_SIZE + 1 + 1 + 1 + 32 + 8;
+
+    /// Split the prize pool across the first `winners` paid places
+    /// Returns each winner's prize and the amount left for the treasury
+    pub fn prize_split(&self, winners: usize) -> (Vec<u64>, u64) {
+        let pool = self.total_pool as u128;
+        let shares = &self.payout_bps[..winners.min(self.payout_places as usize)];
+        let paid_bps: u128 = shares.iter().map(|&bps| bps as u128).sum();
+        let redistribute = self.remainder_policy == RemainderPolicy::Redistribute && paid_bps > 0;
+
+        // Redistributing scales the paid shares up to the whole pool
+        let denominator = if redistribute { paid_bps } else { 10000 };
+        let mut prizes: Vec<u64> = shares
+            .iter()
+            .map(|&bps| (pool * bps as u128 / denominator) as u64)
+            .collect();
+
+        let mut leftover = self.total_pool - prizes.iter().sum::<u64>();
+        if redistribute {
+            // Rounding dust goes to first place
+            prizes[0] += leftover;
+            leftover = 0;
+        }
+        (prizes, leftover)
+    }
+}
+
+#[account]
+pub struct SeasonEntry {
+    pub season_id: u64,
+    pub agent: Pubkey,
+    pub player: Pubkey,
+    pub score: u64,
+    pub predictions_made: u64,
+    pub predictions_correct: u64,
+    pub rank: u16,
+    pub bump: u8,
+    pub prize_contribution: u64, // Entry fee share paid into the prize pool
+    pub platform_fee: u64,       // Entry fee share paid to the treasury
+    pub refunded: bool,          // Set once a cancelled season's refund is claimed
+    pub rent_payer: Pubkey,      // Entering wallet, refunded on close
+}
+
+impl SeasonEntry {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 2 + 1 + 8 + 8 + 1 + 32;
+}
+
+#[account]
+pub struct Prediction {
+    pub agent: Pubkey,
+    pub season_id: u64,
+    pub season: Pubkey,
+    pub prediction_hash: [u8; 32],
+    pub stake_amount: u64,
+    pub submitted_at: i64,
+    pub reveal_deadline: i64,
+    pub revealed_at: i64,
+    pub resolved_at: i64,
+    pub was_correct: bool,
+    pub status: PredictionStatus,
+    pub bump: u8,
+    // Revealed payload
+    pub asset_id: [u8; 8],
+    pub direction: Direction,
+    pub target_price: i64,
+    pub target_expo: i32,
+    pub resolve_after: i64,   // submitted_at + horizon
+    pub confidence_bps: u16,
+    pub mint: Pubkey,         // Season mint the stake was paid in
+    pub rent_payer: Pubkey,   // Submitting signer (owner or delegate), refunded on close
+    pub betting_pool: Pubkey, // Spectator betting pool, default until the first bet
+}
+
+impl Prediction {
+    pub const SIZE: usize = 32 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1
+        + 8 + 1 + 8 + 4 + 8 + 2 + 32 + 32 + 32;
+}
+
+#[account]
+pub struct AssetFeed {
+    pub asset_id: [u8; 8],
+    pub price_feed: Pubkey,
+    pub max_staleness: i64, // Seconds a price may lag the resolution time
+    pub bump: u8,
+}
+
+impl AssetFeed {
+    pub const SIZE: usize = 8 + 32 + 8 + 1;
+}
+
+/// Typed prediction an agent commits to and later reveals
+/// Price is `target_price * 10^target_expo`, matching Pyth's fixed-point convention
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PredictionPayload {
+    pub asset_id: [u8; 8],    // Upper-case ASCII symbol, zero padded (e.g. "SOL")
+    pub direction: Direction,
+    pub target_price: i64,
+    pub target_expo: i32,
+    pub horizon: i64,         // Seconds after submission
+    pub confidence_bps: u16,  // 10000 = 100%
+}
+
+impl PredictionPayload {
+    pub fn validate(&self) -> Result<()> {
+        require!(is_valid_asset_id(&self.asset_id), ErrorCode::InvalidAssetId);
+        require!(self.target_price > 0, ErrorCode::InvalidPriceTarget);
+        require!(
+            (MIN_PRICE_EXPO..=MAX_PRICE_EXPO).contains(&self.target_expo),
+            ErrorCode::InvalidPriceTarget
+        );
+        require!(
+            self.horizon > 0 && self.horizon <= MAX_PREDICTION_HORIZON,
+            ErrorCode::InvalidHorizon
+        );
+        require!(self.confidence_bps <= 10000, ErrorCode::InvalidConfidence);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Achievement {
+    pub agent: Pubkey,
+    pub achievement_type: AchievementType,
+    pub awarded_at: i64,
+    pub bump: u8,
+    pub rent_payer: Pubkey,  // Issuer that paid the rent, refunded on close
+}
+
+impl Achievement {
+    pub const SIZE: usize = 32 + 1 + 8 + 1 + 32;
+}
+
+/// Top of a season's standings, kept sorted by score as predictions resolve
+#[account(zero_copy)]
+pub struct Leaderboard {
+    pub season: Pubkey,
+    pub entries: [LeaderboardEntry; LEADERBOARD_SIZE], // Highest score first
+    pub count: u8,
+    pub bump: u8,
+    pub _padding: [u8; 6],
+}
+
+impl Leaderboard {
+    pub const SIZE: usize = 32 + LeaderboardEntry::SIZE * LEADERBOARD_SIZE + 1 + 1 + 6;
+
+    /// Record an entry's latest score and return its place (1-based, 0 if off the board)
+    /// Scores only grow, so the entry just moves up; ties keep the earlier arrival ahead
+    pub fn record(&mut self, season_entry: Pubkey, player: Pubkey, score: u64) -> u16 {
+        let count = self.count as usize;
+        let slot = match self.entries[..count]
+            .iter()
+            .position(|e| e.season_entry == season_entry)
+        {
+            Some(i) => i,
+            None if count < LEADERBOARD_SIZE => {
+                self.count += 1;
+                count
+            }
+            // Full board: only a strictly higher score displaces last place
+            None if score > self.entries[count - 1].score => count - 1,
+            None => return 0,
+        };
+
+        self.entries[slot] = LeaderboardEntry { season_entry, player, score };
+        let mut place = slot;
+        while place > 0 && self.entries[place - 1].score < score {
+            self.entries.swap(place - 1, place);
+            place -= 1;
+        }
+        (place + 1) as u16
+    }
+
+    /// Re-point an entry's standing at a new player (no-op if off the board)
+    pub fn set_player(&mut self, season_entry: &Pubkey, player: Pubkey) {
+        let count = self.count as usize;
+        if let Some(e) = self.entries[..count]
+            .iter_mut()
+            .find(|e| e.season_entry == *season_entry)
+        {
+            e.player = player;
+        }
+    }
+
+    /// Place of an entry on the board (1-based, 0 if off the board)
+    pub fn place_of(&self, season_entry: &Pubkey) -> u16 {
+        self.entries[..self.count as usize]
+            .iter()
+            .position(|e| e.season_entry == *season_entry)
+            .map_or(0, |i| (i + 1) as u16)
+    }
+}
+
+#[zero_copy]
+pub struct LeaderboardEntry {
+    pub season_entry: Pubkey,
+    pub player: Pubkey,
+    pub score: u64,
+}
+
+impl LeaderboardEntry {
+    pub const SIZE: usize = 32 + 32 + 8;
+}
+
+/// Head-to-head duel: two agents commit targets on one asset and horizon,
+/// and the target closest to the oracle price takes both stakes
+#[account]
+pub struct Duel {
+    pub id: u64,
+    pub challenger: Pubkey,        // Challenger agent
+    pub challenger_owner: Pubkey,  // Staked the challenger side and receives its payouts
+    pub opponent: Pubkey,          // Invited agent, or default for an open challenge until accepted
+    pub opponent_owner: Pubkey,
+    pub asset_id: [u8; 8],
+    pub horizon: i64,              // Seconds after acceptance the duel resolves at
+    pub stake: u64,                // Per side, in lamports
+    pub fee_bps: u16,              // Arena duel fee when the duel was created
+    pub status: DuelStatus,
+    pub created_at: i64,
+    pub accept_deadline: i64,
+    pub accepted_at: i64,
+    pub reveal_deadline: i64,      // accepted_at + DUEL_REVEAL_WINDOW, capped at resolve_after
+    pub resolve_after: i64,        // accepted_at + horizon
+    pub challenger_commitment: [u8; 32],
+    pub opponent_commitment: [u8; 32],
+    pub challenger_revealed: bool,
+    pub challenger_target_price: i64,
+    pub challenger_target_expo: i32,
+    pub opponent_revealed: bool,
+    pub opponent_target_price: i64,
+    pub opponent_target_expo: i32,
+    pub winner: Pubkey,            // Winning agent; default for a draw or until resolved
+    pub bump: u8,
+}
+
+impl Duel {
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 2 + 1 + 8 + 8 + 8 + 8 + 8
+        + 32 + 32 + 1 + 8 + 4 + 1 + 8 + 4 + 32 + 1;
+}
+
+/// Program-owned escrow holding both sides of a duel
+#[account]
+pub struct DuelVault {
+    pub bump: u8,
+}
+
+impl DuelVault {
+    pub const SIZE: usize = 1;
+}
+
+#[account]
+pub struct Tournament {
+    pub id: u64,
+    pub format: BracketFormat,
+    pub asset_id: [u8; 8],
+    pub horizon: i64,              // Seconds after the commit deadline each round resolves at
+    pub entry_fee: u64,
+    pub prize_pool_bps: u16,       // Share of the entry fees paid to the champion
+    pub max_entrants: u8,
+    pub commit_window: i64,        // Seconds from a round's pairing to its commit deadline
+    pub reveal_window: i64,        // Seconds after the commit deadline to reveal
+    pub created_at: i64,
+    pub registration_deadline: i64,
+    pub status: TournamentStatus,
+    pub entrants: Vec<TournamentEntrant>, // Seed order once started, highest reputation first
+    pub round: u16,                // Current round, 0 until started
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+    pub resolve_after: i64,        // commit_deadline + horizon
+    pub matches: Vec<BracketMatch>, // Current round's pairings
+    pub prize_pool: u64,           // Set at start, net of the platform fee
+    pub champion: Pubkey,          // Winning agent; default until completed
+    pub prize_claimed: bool,
+    pub bump: u8,
+}
+
+impl Tournament {
+    pub const SIZE: usize = 8 + 1 + 8 + 8 + 8 + 2 + 1 + 8 + 8 + 8 + 8 + 1
+        + 4 + MAX_TOURNAMENT_ENTRANTS * TournamentEntrant::SIZE
+        + 2 + 8 + 8 + 8
+        + 4 + (MAX_TOURNAMENT_ENTRANTS / 2) * BracketMatch::SIZE
+        + 8 + 32 + 1 + 1;
+
+    pub fn entrant_index(&self, agent: &Pubkey) -> Option<usize> {
+        self.entrants.iter().position(|e| e.agent == *agent)
+    }
+
+    /// Slot and side of an agent's match in the current round
+    pub fn match_side(&self, agent: &Pubkey) -> Result<(usize, usize)> {
+        let entrant = self.entrant_index(agent).ok_or(ErrorCode::NotInTournament)? as u8;
+        self.matches
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| !m.resolved)
+            .find_map(|(slot, m)| {
+                m.sides.iter().position(|s| s.entrant == entrant).map(|side| (slot, side))
+            })
+            .ok_or_else(|| error!(ErrorCode::InvalidTournamentMatch))
+    }
+
+    /// The only agent still under the format's loss limit, if just one remains
+    pub fn sole_survivor(&self) -> Option<Pubkey> {
+        let max_losses = self.format.max_losses();
+        let mut alive = self.entrants.iter().filter(|e| e.losses < max_losses);
+        match (alive.next(), alive.next()) {
+            (Some(survivor), None) => Some(survivor.agent),
+            _ => None,
+        }
+    }
+
+    /// Start the next round, pairing surviving entrants that have the same number of losses
+    ///
+    /// Within each loss group the highest remaining seed meets the lowest. An odd group
+    /// passes its lowest seed down to the next group, so the last undefeated agent meets
+    /// the last one-loss agent in a double-elimination final (and again if it loses that
+    /// first meeting). Only the final group can leave an agent over, who gets a bye
+    pub fn pair_round(&mut self, now: i64) {
+        let max_losses = self.format.max_losses();
+        self.round += 1;
+        self.matches.clear();
+        
+        let mut carried = None;
+        for losses in 0..max_losses {
+            let mut group: Vec<u8> = carried
+                .take()
+                .into_iter()
+                .chain((0..self.entrants.len() as u8).filter(|&i| self.entrants[i as usize].losses == losses))
+                .collect();
+            if group.len() % 2 == 1 {
+                if losses + 1 < max_losses {
+                    carried = group.pop();
+                } else {
+                    self.matches.push(BracketMatch::bye(group.remove(0)));
+                }
+            }
+            while !group.is_empty() {
+                let high = group.remove(0);
+                let low = group.pop().unwrap();
+                self.matches.push(BracketMatch::new(high, low));
+            }
+        }
+        
+        self.commit_deadline = now + self.commit_window;
+        self.reveal_deadline = self.commit_deadline + self.reveal_window;
+        self.resolve_after = self.commit_deadline + self.horizon;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TournamentEntrant {
+    pub agent: Pubkey,
+    pub seed_score: u32, // Agent reputation when it joined
+    pub losses: u8,
+}
+
+impl TournamentEntrant {
+    pub const SIZE: usize = 32 + 4 + 1;
+}
+
+/// One pairing in a tournament round; side 0 holds the higher seed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BracketMatch {
+    pub sides: [MatchSide; 2],
+    pub resolved: bool,
+    pub winner: u8, // Entrant index once resolved
+}
+
+impl BracketMatch {
+    pub const SIZE: usize = 2 * MatchSide::SIZE + 1 + 1;
+
+    fn new(high: u8, low: u8) -> Self {
+        Self {
+            sides: [MatchSide::new(high), MatchSide::new(low)],
+            resolved: false,
+            winner: BRACKET_BYE,
+        }
+    }
+
+    /// A walkover: the entrant advances without playing
+    fn bye(entrant: u8) -> Self {
+        Self {
+            sides: [MatchSide::new(entrant), MatchSide::new(BRACKET_BYE)],
+            resolved: true,
+            winner: entrant,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MatchSide {
+    pub entrant: u8, // Index into `Tournament::entrants`, BRACKET_BYE for an empty side
+    pub commitment: [u8; 32],
+    pub committed: bool,
+    pub revealed: bool,
+    pub target_price: i64,
+    pub target_expo: i32,
+}
+
+impl MatchSide {
+    pub const SIZE: usize = 1 + 32 + 1 + 1 + 8 + 4;
+
+    fn new(entrant: u8) -> Self {
+        Self {
+            entrant,
+            commitment: [0; 32],
+            committed: false,
+            revealed: false,
+            target_price: 0,
+            target_expo: 0,
+        }
+    }
+}
+
+/// Program-owned vault holding a tournament's entry fees and prize pool
+#[account]
+pub struct TournamentVault {
+    pub bump: u8,
+}
+
+impl TournamentVault {
+    pub const SIZE: usize = 1;
+}
+
+/// Spectator bets on whether one prediction turns out right
+#[account]
+pub struct BettingPool {
+    pub prediction: Pubkey,
+    pub fee_bps: u16,      // Arena betting fee when the pool opened
+    pub right_total: u64,
+    pub wrong_total: u64,
+    pub outcome: BetOutcome,
+    pub payout_pool: u64,  // Shared by the winning side (or refunded on a void) once settled
+    pub fee: u64,          // House cut paid to the treasury at settlement
+    pub bump: u8,
+}
+
+impl BettingPool {
+    pub const SIZE: usize = 32 + 2 + 8 + 8 + 1 + 8 + 8 + 1;
+}
+
+/// Program-owned vault holding a betting pool's stakes
+#[account]
+pub struct BettingVault {
+    pub bump: u8,
+}
+
+impl BettingVault {
+    pub const SIZE: usize = 1;
+}
+
+#[account]
+pub struct Bet {
+    pub pool: Pubkey,
+    pub bettor: Pubkey,
+    pub side: BetSide,
+    pub amount: u64,
+    pub bump: u8,
 }
 
-impl Season {
-    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 2 + 8 + 8 + 1 + 1;
+impl Bet {
+    pub const SIZE: usize = 32 + 32 + 1 + 8 + 1;
 }
 
+/// An agent's published subscription terms
 #[account]
-pub struct SeasonEntry {
-    pub season_id: u64,
+pub struct SubscriptionPlan {
     pub agent: Pubkey,
-    pub player: Pubkey,
-    pub score: u64,
-    pub predictions_made: u64,
-    pub predictions_correct: u64,
-    pub rank: u16,
+    pub price: u64,   // Lamports per period; 0 when not selling
+    pub period: i64,  // Seconds of access bought per price
     pub bump: u8,
 }
 
-impl SeasonEntry {
-    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 2 + 1;
+impl SubscriptionPlan {
+    pub const SIZE: usize = 32 + 8 + 8 + 1;
 }
 
+/// Program-owned vault collecting an agent owner's share of subscription payments
 #[account]
-pub struct Prediction {
+pub struct AgentRevenueVault {
     pub agent: Pubkey,
-    pub season_id: u64,
-    pub prediction_hash: [u8; 32],
-    pub prediction_data: String, // Revealed later
-    pub stake_amount: u64,
-    pub submitted_at: i64,
-    pub revealed_at: i64,
-    pub resolved_at: i64,
-    pub was_correct: bool,
-    pub status: PredictionStatus,
+    pub total_earned: u64,    // Net of the arena subscription fee
+    pub total_withdrawn: u64,
     pub bump: u8,
 }
 
-impl Prediction {
-    pub const SIZE: usize = 32 + 8 + 32 + 4 + 256 + 8 + 8 + 8 + 8 + 1 + 1 + 1;
+impl AgentRevenueVault {
+    pub const SIZE: usize = 32 + 8 + 8 + 1;
 }
 
+/// A subscriber's paid access to one agent's signals
 #[account]
-pub struct Achievement {
+pub struct Subscription {
     pub agent: Pubkey,
-    pub achievement_type: AchievementType,
-    pub awarded_at: i64,
+    pub subscriber: Pubkey,
+    pub started_at: i64,  // Start of the current unbroken subscription
+    pub expires_at: i64,
+    pub total_paid: u64,
     pub bump: u8,
 }
 
-impl Achievement {
-    pub const SIZE: usize = 32 + 1 + 8 + 1;
+impl Subscription {
+    pub const SIZE: usize = 32 + 32 + 8 + 8 + 8 + 1;
+
+    pub fn is_active(&self, now: i64) -> bool {
+        now < self.expires_at
+    }
 }
 
 #[account]
@@ -792,6 +5047,12 @@ pub enum Rank {
     Legend,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AgentStatus {
+    Active,
+    Retired,   // Tombstone: stats kept, no further play
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum SeasonStatus {
     Active,
@@ -804,6 +5065,109 @@ pub enum PredictionStatus {
     Committed,
     Revealed,
     Resolved,
+    Expired,   // Not revealed in time (forfeited)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ForfeitPolicy {
+    Treasury,   // Forfeited stakes go to the treasury
+    PrizePool,  // Forfeited stakes are added to the season prize pool
+}
+
+/// How a season's prize pool is split between its top places
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PayoutSchedule {
+    pub payout_bps: Vec<u16>, // Prize share per place, first place first
+    pub remainder_policy: RemainderPolicy,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RemainderPolicy {
+    Redistribute, // Unpaid shares are split pro rata among the paid places
+    Treasury,     // Unpaid shares go to the treasury
+}
+
+/// Operational keys the authority delegates on the arena
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Resolver,
+    SeasonOperator,
+    AchievementIssuer,
+    Treasurer,
+}
+
+/// Platform fees the authority can tune
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FeeKind {
+    Duel,
+    Betting,
+    Subscription,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DuelStatus {
+    Open,      // Awaiting an opponent
+    Active,    // Both stakes escrowed
+    Resolved,
+    Cancelled, // Nobody accepted; challenger refunded
+}
+
+/// Terms a tournament is opened with, all windows in seconds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct TournamentTerms {
+    pub format: BracketFormat,
+    pub asset_id: [u8; 8],
+    pub horizon: i64,             // After a round's commit deadline
+    pub entry_fee: u64,
+    pub prize_pool_bps: u16,
+    pub max_entrants: u8,
+    pub registration_window: i64, // From creation
+    pub commit_window: i64,       // From a round's pairing
+    pub reveal_window: i64,       // After the commit deadline, shorter than the horizon
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BracketFormat {
+    SingleElimination,
+    DoubleElimination,
+}
+
+impl BracketFormat {
+    /// Losses that knock an entrant out
+    pub fn max_losses(&self) -> u8 {
+        match self {
+            BracketFormat::SingleElimination => 1,
+            BracketFormat::DoubleElimination => 2,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TournamentStatus {
+    Registration,
+    Active,
+    Completed,
+    Cancelled, // Entrants reclaim their fees, or their prize pool share once started
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BetSide {
+    Right, // The prediction resolves correct
+    Wrong,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BetOutcome {
+    Pending,
+    Right,
+    Wrong,
+    Void, // Every bet refunded
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -851,14 +5215,293 @@ pub enum ErrorCode {
     UnauthorizedAgent,
     #[msg("Unauthorized - not the prediction owner")]
     UnauthorizedPrediction,
+    #[msg("Invalid asset id - expected an upper-case ASCII symbol")]
+    InvalidAssetId,
+    #[msg("Invalid price target")]
+    InvalidPriceTarget,
+    #[msg("Invalid prediction horizon")]
+    InvalidHorizon,
+    #[msg("Confidence must be at most 10000 bps")]
+    InvalidConfidence,
+    #[msg("Prediction horizon has not been reached")]
+    HorizonNotReached,
+    #[msg("Invalid price feed account")]
+    InvalidPriceFeed,
+    #[msg("Price feed is not currently trading")]
+    PriceUnavailable,
+    #[msg("Price is stale for this resolution")]
+    StalePrice,
+    #[msg("Invalid price staleness window")]
+    InvalidStaleness,
+    #[msg("Price was published too long after the horizon")]
+    ResolutionWindowMissed,
+    #[msg("Mock price feeds are disabled in this build")]
+    MockOracleDisabled,
+    #[msg("Mock season clocks are disabled in this build")]
+    MockClockDisabled,
+    #[msg("Asset has a registered price feed; resolve it from the oracle")]
+    AssetHasPriceFeed,
+    #[msg("Oracle resolution window is still open")]
+    ResolutionWindowOpen,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Invalid reveal window")]
+    InvalidRevealWindow,
+    #[msg("Reveal window has closed")]
+    RevealWindowClosed,
+    #[msg("Reveal window is still open")]
+    RevealWindowOpen,
+    #[msg("Agent does not match the prediction")]
+    PredictionAgentMismatch,
+    #[msg("Season entry belongs to a different season")]
+    EntrySeasonMismatch,
+    #[msg("Season entry belongs to a different agent")]
+    EntryAgentMismatch,
+    #[msg("Player is not the agent owner")]
+    PlayerNotAgentOwner,
+    #[msg("Player does not match the season entry")]
+    PlayerNotEntrant,
+    #[msg("Prize recipient does not match the leaderboard")]
+    PrizeRecipientMismatch,
+    #[msg("Payout schedule must have 1-10 non-zero shares summing to 10000 bps")]
+    InvalidPayoutSchedule,
+    #[msg("Expected one prize recipient per paid place")]
+    InvalidRecipientCount,
+    #[msg("Season has been cancelled")]
+    SeasonCancelled,
+    #[msg("Season has not been cancelled")]
+    SeasonNotCancelled,
+    #[msg("Refund already claimed")]
+    AlreadyRefunded,
+    #[msg("Token account required for this season's mint")]
+    MissingTokenAccount,
+    #[msg("Token account does not match the season mint")]
+    TokenMintMismatch,
+    #[msg("Invalid authority")]
+    InvalidAuthority,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("Arena is paused for this instruction")]
+    ArenaPaused,
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    #[msg("Agent update must change the name or endpoint")]
+    EmptyAgentUpdate,
+    #[msg("Agent was updated too recently")]
+    AgentUpdateCooldown,
+    #[msg("Agent has unresolved predictions")]
+    AgentHasOpenPredictions,
+    #[msg("Owner has reached the agent limit")]
+    TooManyAgents,
+    #[msg("Unknown or empty delegate scopes")]
+    InvalidDelegateScopes,
+    #[msg("Delegate expiry must be in the future")]
+    InvalidDelegateExpiry,
+    #[msg("Delegate is not scoped for this instruction")]
+    DelegateScopeMissing,
+    #[msg("Delegate has expired")]
+    DelegateExpired,
+    #[msg("Stake exceeds the delegate's cap")]
+    DelegateStakeCapExceeded,
+    #[msg("Delegate was granted by a previous owner")]
+    StaleDelegate,
+    #[msg("Invalid agent transfer recipient")]
+    InvalidAgentRecipient,
+    #[msg("No agent transfer is pending")]
+    NoPendingAgentTransfer,
+    #[msg("Signer is not the agent's pending owner")]
+    NotPendingAgentOwner,
+    #[msg("Agent is retired")]
+    AgentRetired,
+    #[msg("Agent is not retired")]
+    AgentNotRetired,
+    #[msg("Prediction is not resolved or expired")]
+    PredictionNotSettled,
+    #[msg("Season entry is still needed by its season")]
+    SeasonEntryInUse,
+    #[msg("Fee must be at most 10000 bps")]
+    InvalidFeeBps,
+    #[msg("Invalid duel stake, horizon or acceptance window")]
+    InvalidDuelTerms,
+    #[msg("An agent cannot duel itself")]
+    SelfDuel,
+    #[msg("Invalid duel status")]
+    InvalidDuelStatus,
+    #[msg("Duel acceptance deadline has passed")]
+    DuelAcceptWindowClosed,
+    #[msg("Agent is not the invited opponent")]
+    DuelOpponentMismatch,
+    #[msg("Agent is not in this duel")]
+    DuelParticipantMismatch,
+    #[msg("Duel side already revealed")]
+    DuelAlreadyRevealed,
+    #[msg("Revealed asset or horizon differs from the duel")]
+    DuelPayloadMismatch,
+    #[msg("Only the challenger can cancel before the acceptance deadline")]
+    DuelCancelTooEarly,
+    #[msg("Invalid tournament size, horizon, windows or prize split")]
+    InvalidTournamentTerms,
+    #[msg("Invalid tournament status")]
+    InvalidTournamentStatus,
+    #[msg("Tournament registration has closed")]
+    TournamentRegistrationClosed,
+    #[msg("Tournament registration is still open")]
+    TournamentRegistrationOpen,
+    #[msg("Tournament is full")]
+    TournamentFull,
+    #[msg("Agent already entered this tournament")]
+    AlreadyInTournament,
+    #[msg("Agent is not in this tournament")]
+    NotInTournament,
+    #[msg("A tournament needs at least two entrants")]
+    NotEnoughEntrants,
+    #[msg("No unresolved match for this slot or agent")]
+    InvalidTournamentMatch,
+    #[msg("Commit window has closed")]
+    CommitWindowClosed,
+    #[msg("Opponent can still commit")]
+    CommitWindowOpen,
+    #[msg("Match side already committed")]
+    MatchSideCommitted,
+    #[msg("Match side has not committed")]
+    MatchSideNotCommitted,
+    #[msg("Match side already revealed")]
+    MatchSideRevealed,
+    #[msg("Revealed asset or horizon differs from the tournament")]
+    TournamentPayloadMismatch,
+    #[msg("Agent is not the tournament champion")]
+    NotTournamentChampion,
+    #[msg("Prize already claimed")]
+    PrizeAlreadyClaimed,
+    #[msg("Bet amount must be greater than zero")]
+    InvalidBetAmount,
+    #[msg("Betting has closed for this prediction")]
+    BettingClosed,
+    #[msg("The agent owner and submitter cannot bet on their own prediction")]
+    InsiderBet,
+    #[msg("Bet must stay on the side already backed")]
+    BetSideMismatch,
+    #[msg("Betting pool has not been settled")]
+    BettingPoolUnsettled,
+    #[msg("Betting pool already settled")]
+    BettingPoolSettled,
+    #[msg("Subscription period or count out of range")]
+    InvalidSubscriptionPlan,
+    #[msg("Agent is not selling subscriptions")]
+    SubscriptionsClosed,
+    #[msg("Subscription price is above the subscriber's limit")]
+    SubscriptionPriceAboveLimit,
+    #[msg("Subscription has expired")]
+    SubscriptionExpired,
+    #[msg("Subscription is still active")]
+    SubscriptionActive,
+    #[msg("Insufficient revenue in the agent vault")]
+    InsufficientRevenue,
 }
 
 // Events
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+}
+
+#[event]
+pub struct RoleUpdated {
+    pub role: Role,
+    pub previous: Pubkey,
+    pub key: Pubkey,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub previous: u8,
+    pub pause_flags: u8,
+}
+
+#[event]
+pub struct FeeUpdated {
+    pub kind: FeeKind,
+    pub previous: u16,
+    pub bps: u16,
+}
+
 #[event]
 pub struct AgentRegistered {
     pub agent: Pubkey,
     pub name: String,
     pub owner: Pubkey,
+    pub index: u16,
+}
+
+#[event]
+pub struct AgentUpdated {
+    pub agent: Pubkey,
+    pub old_name: String,
+    pub new_name: String,
+    pub old_endpoint: String,
+    pub new_endpoint: String,
+}
+
+#[event]
+pub struct DelegateSet {
+    pub agent: Pubkey,
+    pub delegate: Pubkey,
+    pub scopes: u8,
+    pub max_stake: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct DelegateRevoked {
+    pub agent: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct AgentRetired {
+    pub agent: Pubkey,
+    pub owner: Pubkey,
+    pub name: String,
+    pub total_predictions: u64,
+    pub correct_predictions: u64,
+    pub best_streak: u16,
+    pub rank: Rank,
+    pub reputation_score: u32,
+}
+
+#[event]
+pub struct AgentTransferProposed {
+    pub agent: Pubkey,
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
+
+#[event]
+pub struct AgentTransferred {
+    pub agent: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct AgentTransferCancelled {
+    pub agent: Pubkey,
+    pub cancelled_owner: Pubkey,
 }
 
 #[event]
@@ -867,6 +5510,7 @@ pub struct SeasonCreated {
     pub entry_fee: u64,
     pub start_time: i64,
     pub end_time: i64,
+    pub mint: Pubkey,
 }
 
 #[event]
@@ -890,6 +5534,12 @@ pub struct PredictionSubmitted {
 pub struct PredictionRevealed {
     pub prediction: Pubkey,
     pub agent: Pubkey,
+    pub asset_id: [u8; 8],
+    pub direction: Direction,
+    pub target_price: i64,
+    pub target_expo: i32,
+    pub resolve_after: i64,
+    pub confidence_bps: u16,
 }
 
 #[event]
@@ -901,6 +5551,45 @@ pub struct PredictionResolved {
     pub stake_transferred: u64,
 }
 
+#[event]
+pub struct OracleResolution {
+    pub prediction: Pubkey,
+    pub price_feed: Pubkey,
+    pub price: i64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+#[event]
+pub struct AssetFeedRegistered {
+    pub asset_id: [u8; 8],
+    pub price_feed: Pubkey,
+    pub max_staleness: i64,
+}
+
+#[event]
+pub struct AssetFeedUpdated {
+    pub asset_id: [u8; 8],
+    pub price_feed: Pubkey,
+    pub max_staleness: i64,
+}
+
+#[event]
+pub struct PredictionVoided {
+    pub prediction: Pubkey,
+    pub agent: Pubkey,
+    pub stake_amount: u64,
+}
+
+#[event]
+pub struct PredictionForfeited {
+    pub prediction: Pubkey,
+    pub agent: Pubkey,
+    pub season_id: u64,
+    pub stake_amount: u64,
+    pub forfeit_policy: ForfeitPolicy,
+}
+
 #[event]
 pub struct AchievementAwarded {
     pub agent: Pubkey,
@@ -911,14 +5600,227 @@ pub struct AchievementAwarded {
 pub struct PrizesDistributed {
     pub season_id: u64,
     pub total_pool: u64,
-    pub first_place: u64,
-    pub second_place: u64,
-    pub third_place: u64,
+    pub prizes: Vec<u64>,     // In leaderboard order
+    pub to_treasury: u64,
+}
+
+#[event]
+pub struct SeasonCancelled {
+    pub season_id: u64,
+    pub total_pool: u64,
+    pub total_entries: u64,
+    pub refund_platform_fee: bool,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub season_id: u64,
+    pub agent: Pubkey,
+    pub player: Pubkey,
+    pub prize_contribution: u64,
+    pub platform_fee: u64,
+}
+
+#[event]
+pub struct TreasuryTokenVaultCreated {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct DuelCreated {
+    pub duel: Pubkey,
+    pub id: u64,
+    pub challenger: Pubkey,
+    pub opponent: Pubkey,
+    pub asset_id: [u8; 8],
+    pub horizon: i64,
+    pub stake: u64,
+    pub accept_deadline: i64,
+}
+
+#[event]
+pub struct DuelAccepted {
+    pub duel: Pubkey,
+    pub opponent: Pubkey,
+}
+
+#[event]
+pub struct DuelRevealed {
+    pub duel: Pubkey,
+    pub agent: Pubkey,
+    pub target_price: i64,
+    pub target_expo: i32,
+}
+
+#[event]
+pub struct DuelResolved {
+    pub duel: Pubkey,
+    pub winner: Pubkey,   // Default for a draw
+    pub price: i64,       // Oracle price, 0 when a no-show decided the duel
+    pub expo: i32,
+    pub payout: u64,      // Paid to the winner, or to each side on a draw
+    pub fee: u64,
+}
+
+#[event]
+pub struct DuelCancelled {
+    pub duel: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct TournamentCreated {
+    pub tournament: Pubkey,
+    pub id: u64,
+    pub format: BracketFormat,
+    pub asset_id: [u8; 8],
+    pub horizon: i64,
+    pub entry_fee: u64,
+    pub max_entrants: u8,
+    pub registration_deadline: i64,
+}
+
+#[event]
+pub struct TournamentJoined {
+    pub tournament: Pubkey,
+    pub agent: Pubkey,
+    pub seed_score: u32,
+    pub entrants: u8,
+}
+
+#[event]
+pub struct TournamentLeft {
+    pub tournament: Pubkey,
+    pub agent: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct TournamentStarted {
+    pub tournament: Pubkey,
+    pub entrants: u8,
+    pub prize_pool: u64,
+    pub platform_fee: u64,
+}
+
+#[event]
+pub struct TournamentCancelled {
+    pub tournament: Pubkey,
+    pub entrants: u8,
+}
+
+#[event]
+pub struct TournamentRoundStarted {
+    pub tournament: Pubkey,
+    pub round: u16,
+    pub matches: u8,      // Including byes
+    pub commit_deadline: i64,
+}
+
+#[event]
+pub struct TournamentPredictionCommitted {
+    pub tournament: Pubkey,
+    pub round: u16,
+    pub slot: u8,
+    pub agent: Pubkey,
+}
+
+#[event]
+pub struct TournamentPredictionRevealed {
+    pub tournament: Pubkey,
+    pub round: u16,
+    pub slot: u8,
+    pub agent: Pubkey,
+    pub target_price: i64,
+    pub target_expo: i32,
+}
+
+#[event]
+pub struct TournamentMatchResolved {
+    pub tournament: Pubkey,
+    pub round: u16,
+    pub slot: u8,
+    pub winner: Pubkey,
+    pub loser: Pubkey,
+    pub price: i64,       // Oracle price, 0 when a no-show decided the match
+    pub expo: i32,
+}
+
+#[event]
+pub struct TournamentCompleted {
+    pub tournament: Pubkey,
+    pub champion: Pubkey,
+    pub rounds: u16,
+    pub prize_pool: u64,
+}
+
+#[event]
+pub struct TournamentPrizeClaimed {
+    pub tournament: Pubkey,
+    pub champion: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BetPlaced {
+    pub pool: Pubkey,
+    pub prediction: Pubkey,
+    pub bettor: Pubkey,
+    pub side: BetSide,
+    pub amount: u64,
+    pub right_total: u64,
+    pub wrong_total: u64,
+}
+
+#[event]
+pub struct BettingPoolSettled {
+    pub pool: Pubkey,
+    pub prediction: Pubkey,
+    pub outcome: BetOutcome,
+    pub payout_pool: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct BetClaimed {
+    pub pool: Pubkey,
+    pub bettor: Pubkey,
+    pub side: BetSide,
+    pub payout: u64,
+}
+
+#[event]
+pub struct SubscriptionPlanSet {
+    pub agent: Pubkey,
+    pub price: u64,
+    pub period: i64,
+}
+
+#[event]
+pub struct Subscribed {
+    pub agent: Pubkey,
+    pub subscriber: Pubkey,
+    pub periods: u16,
+    pub paid: u64,
+    pub fee: u64,          // Forwarded to the treasury
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct AgentRevenueWithdrawn {
+    pub agent: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
 }
 
 #[event]
 pub struct TreasuryWithdrawal {
-    pub authority: Pubkey,
+    pub treasurer: Pubkey,
+    pub mint: Pubkey,            // Default for the lamport treasury
     pub amount: u64,
     pub remaining_balance: u64,
+    pub total_withdrawn: u64,    // Running total for this mint
 }
@@ -1,5 +1,21 @@
 import * as anchor from '@coral-xyz/anchor';
 import { AnchorProvider, web3, BN } from '@coral-xyz/anchor';
+import { createHash, randomBytes } from 'crypto';
+
+// Domain separator for prediction commitments (must match the program)
+export const PREDICTION_COMMITMENT_DOMAIN = Buffer.from('signal-wars:prediction:v1');
+
+/**
+ * On-chain PredictionPayload (price = targetPrice * 10^targetExpo)
+ */
+export interface PredictionPayload {
+  assetId: number[];
+  direction: { up: {} } | { down: {} };
+  targetPrice: BN;
+  targetExpo: number;
+  horizon: BN;
+  confidenceBps: number;
+}
 
 /**
  * Signal Wars Client SDK
@@ -28,6 +44,7 @@ export class SignalWarsClient {
     throw new Error('Not implemented until program is deployed');
   }
 
+  // Agents are derived from owner + the owner's next AgentRegistry index
   async registerAgent(
     _owner: web3.PublicKey,
     _name: string,
@@ -41,7 +58,10 @@ export class SignalWarsClient {
     _entryFee: BN,
     _durationDays: number,
     _prizePoolBps: number,
-    _seasonId: BN
+    _seasonId: BN,
+    _payoutBps: number[] = [5000, 3000, 2000],
+    _remainderPolicy: 'redistribute' | 'treasury' = 'redistribute',
+    _mint: web3.PublicKey | null = null // SPL mint; null for native SOL seasons
   ): Promise<web3.PublicKey> {
     throw new Error('Not implemented until program is deployed');
   }
@@ -52,7 +72,8 @@ export class SignalWarsClient {
     _season: web3.PublicKey,
     _predictionHash: Buffer,
     _stakeAmount: BN,
-    _predictionCount: BN
+    _predictionCount: BN,
+    _delegate: web3.PublicKey | null = null // AgentDelegate PDA when _player is a delegate key
   ): Promise<web3.PublicKey> {
     throw new Error('Not implemented until program is deployed');
   }
@@ -61,7 +82,9 @@ export class SignalWarsClient {
     _player: web3.PublicKey,
     _agent: web3.PublicKey,
     _prediction: web3.PublicKey,
-    _predictionData: string
+    _payload: PredictionPayload,
+    _nonce: Buffer,
+    _delegate: web3.PublicKey | null = null
   ): Promise<void> {
     throw new Error('Not implemented until program is deployed');
   }
@@ -78,30 +101,60 @@ export class SignalWarsClient {
     throw new Error('Not implemented until program is deployed');
   }
 
-  // Helper: Create prediction hash
-  static createPredictionHash(predictionData: string): Buffer {
-    return Buffer.from(
-      anchor.utils.sha256.hash(predictionData),
-      'hex'
-    );
+  // Helper: Fresh per-prediction nonce; store it until reveal
+  static createNonce(): Buffer {
+    return randomBytes(32);
   }
 
-  // Helper: Generate prediction JSON
+  // Helper: Create prediction commitment
+  // sha256(domain || agent || season || nonce || borsh(payload))
+  static createPredictionHash(
+    agent: web3.PublicKey,
+    season: web3.PublicKey,
+    nonce: Buffer,
+    payload: PredictionPayload
+  ): Buffer {
+    return createHash('sha256')
+      .update(PREDICTION_COMMITMENT_DOMAIN)
+      .update(agent.toBuffer())
+      .update(season.toBuffer())
+      .update(nonce)
+      .update(SignalWarsClient.serializePrediction(payload))
+      .digest();
+  }
+
+  // Helper: Generate typed prediction payload
+  // targetPrice is scaled by 10^-targetExpo, e.g. 220.50 with expo -2 -> 22050
   static generatePrediction(
     asset: string,
     direction: 'up' | 'down',
     targetPrice: number,
-    timeframe: string,
-    confidence: number
-  ): string {
-    return JSON.stringify({
-      asset,
-      direction,
-      targetPrice,
-      timeframe,
-      confidence,
-      timestamp: Date.now(),
-    });
+    timeframeSeconds: number,
+    confidence: number,
+    targetExpo: number = -2
+  ): PredictionPayload {
+    const assetId = Buffer.alloc(8);
+    assetId.write(asset.toUpperCase(), 'ascii');
+    return {
+      assetId: Array.from(assetId),
+      direction: direction === 'up' ? { up: {} } : { down: {} },
+      targetPrice: new BN(Math.round(targetPrice * 10 ** -targetExpo)),
+      targetExpo,
+      horizon: new BN(timeframeSeconds),
+      confidenceBps: Math.round(confidence * 100),
+    };
+  }
+
+  // Helper: Borsh-encode a payload exactly as the program does
+  static serializePrediction(payload: PredictionPayload): Buffer {
+    const buf = Buffer.alloc(8 + 1 + 8 + 4 + 8 + 2);
+    Buffer.from(payload.assetId).copy(buf, 0);
+    buf.writeUInt8('up' in payload.direction ? 0 : 1, 8);
+    buf.writeBigInt64LE(BigInt(payload.targetPrice.toString()), 9);
+    buf.writeInt32LE(payload.targetExpo, 17);
+    buf.writeBigInt64LE(BigInt(payload.horizon.toString()), 21);
+    buf.writeUInt16LE(payload.confidenceBps, 29);
+    return buf;
   }
 }
 
@@ -47,9 +47,18 @@ export function deriveArenaPda(): PublicKey {
   )[0];
 }
 
-export function deriveAgentPda(owner: PublicKey): PublicKey {
+export function deriveAgentRegistryPda(owner: PublicKey): PublicKey {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from('agent'), owner.toBuffer()],
+    [Buffer.from('agent_registry'), owner.toBuffer()],
+    PROGRAM_ID
+  )[0];
+}
+
+export function deriveAgentPda(owner: PublicKey, index = 0): PublicKey {
+  const indexBuf = Buffer.alloc(2);
+  indexBuf.writeUInt16LE(index, 0);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('agent'), owner.toBuffer(), indexBuf],
     PROGRAM_ID
   )[0];
 }
@@ -70,9 +79,17 @@ export function deriveSeasonEntryPda(season: PublicKey, agent: PublicKey): Publi
   )[0];
 }
 
-export function derivePredictionPda(agent: PublicKey, season: PublicKey, predictionCount: number): PublicKey {
+export function deriveLeaderboardPda(season: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('leaderboard'), season.toBuffer()],
+    PROGRAM_ID
+  )[0];
+}
+
+// Seeded with the agent's `predictionsSubmitted` counter, not `totalPredictions`
+export function derivePredictionPda(agent: PublicKey, season: PublicKey, predictionsSubmitted: number): PublicKey {
   const countBuf = Buffer.alloc(8);
-  countBuf.writeBigUInt64LE(BigInt(predictionCount), 0);
+  countBuf.writeBigUInt64LE(BigInt(predictionsSubmitted), 0);
   return PublicKey.findProgramAddressSync(
     [Buffer.from('prediction'), agent.toBuffer(), season.toBuffer(), countBuf],
     PROGRAM_ID
@@ -140,6 +157,23 @@ export async function fetchSeasonData(seasonPda: PublicKey) {
   }
 }
 
+// Fetch a season's top standings in a single account read
+export async function fetchSeasonLeaderboard(seasonPda: PublicKey) {
+  const program = getProgram() as any;
+  
+  try {
+    const board = await program.account.leaderboard.fetch(deriveLeaderboardPda(seasonPda));
+    return board.entries.slice(0, board.count).map((e: any, i: number) => ({
+      place: i + 1,
+      seasonEntry: e.seasonEntry.toString(),
+      player: e.player.toString(),
+      score: e.score.toNumber(),
+    }));
+  } catch (e) {
+    return [];
+  }
+}
+
 // Fetch all agents (scanning - for demo purposes)
 export async function fetchAllAgents() {
   const program = getProgram() as any;
@@ -202,13 +236,16 @@ export default {
   getConnection,
   getProgram,
   deriveArenaPda,
+  deriveAgentRegistryPda,
   deriveAgentPda,
   deriveSeasonPda,
   deriveSeasonEntryPda,
   derivePredictionPda,
+  deriveLeaderboardPda,
   fetchArenaData,
   fetchAgentData,
   fetchSeasonData,
+  fetchSeasonLeaderboard,
   fetchAllAgents,
   fetchAllSeasons,
 };
@@ -1,18 +1,57 @@
 import * as anchor from '@coral-xyz/anchor';
 import { Program, AnchorProvider, web3, BN } from '@coral-xyz/anchor';
 import { assert, expect } from 'chai';
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMint,
+  getAccount,
+  getMint,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  getTransferFeeAmount,
+  getTransferFeeConfig,
+  mintTo,
+} from '@solana/spl-token';
 import { SignalWars } from '../target/types/signal_wars';
 import {
   deriveArenaPda,
   deriveAgentPda,
+  deriveAgentRegistryPda,
+  deriveAgentDelegatePda,
   deriveSeasonPda,
   deriveSeasonEntryPda,
   derivePredictionPda,
   deriveSeasonVaultPda,
   derivePredictionVaultPda,
   deriveAchievementPda,
+  deriveAssetFeedPda,
+  deriveMockPriceFeedPda,
+  deriveDuelPda,
+  deriveDuelVaultPda,
+  generateDuelHash,
+  deriveTournamentPda,
+  deriveTournamentVaultPda,
+  deriveBettingPoolPda,
+  deriveBettingVaultPda,
+  deriveBetPda,
+  deriveSubscriptionPlanPda,
+  deriveAgentRevenueVaultPda,
+  deriveSubscriptionPda,
+  deriveLeaderboardPda,
+  deriveTreasuryPda,
+  deriveSeasonTokenVaultPda,
+  derivePredictionTokenVaultPda,
+  deriveTreasuryTokenVaultPda,
+  deriveTreasuryTokenLedgerPda,
+  assetIdFromSymbol,
+  generateNonce,
   generatePredictionHash,
-  generatePredictionData,
+  generatePredictionPayload,
+  PredictionPayload,
   airdrop,
   TEST_CONSTANTS,
   ERROR_CODES,
@@ -42,12 +81,16 @@ describe('Signal Wars', () => {
   let prediction2Pda: web3.PublicKey;
   let seasonVaultPda: web3.PublicKey;
   let predictionVaultPda: web3.PublicKey;
+  let predictionNonce: Buffer;
+  let prediction2Nonce: Buffer;
 
   // Constants
   const ENTRY_FEE = new BN(TEST_CONSTANTS.ENTRY_FEE_LAMPORTS);
   const STAKE_AMOUNT = new BN(TEST_CONSTANTS.STAKE_AMOUNT_LAMPORTS);
   const DURATION_DAYS = TEST_CONSTANTS.DURATION_DAYS_DEFAULT;
   const PRIZE_POOL_BPS = TEST_CONSTANTS.PRIZE_POOL_BPS_DEFAULT;
+  const REVEAL_WINDOW = new BN(TEST_CONSTANTS.REVEAL_WINDOW_SECONDS);
+  const PAYOUT_BPS = TEST_CONSTANTS.PAYOUT_BPS_DEFAULT;
 
   // Setup before all tests
   before(async () => {
@@ -80,6 +123,12 @@ describe('Signal Wars', () => {
       expect(arena.authority.toString()).to.equal(authority.publicKey.toString());
       expect(arena.totalSeasons.toNumber()).to.equal(0);
       expect(arena.totalAgents.toNumber()).to.equal(0);
+
+      // Treasury is a program-owned vault derived at init, not a caller-supplied account
+      const [treasuryPda] = deriveTreasuryPda(program.programId);
+      const treasury = await provider.connection.getAccountInfo(treasuryPda);
+      expect(arena.treasury.toString()).to.equal(treasuryPda.toString());
+      expect(treasury.owner.toString()).to.equal(program.programId.toString());
     });
 
     it('Should fail to initialize arena twice', async () => {
@@ -96,7 +145,7 @@ describe('Signal Wars', () => {
         
         assert.fail('Should have thrown an error');
       } catch (error) {
-        expect(error.toString()).to.include('already in use');
+        expect(error.toString()).to.include('ConstraintSeeds');
       }
     });
   });
@@ -106,7 +155,7 @@ describe('Signal Wars', () => {
   // ==========================================
   describe('Agent Registration', () => {
     it('Should register a new agent', async () => {
-      [agentPda] = deriveAgentPda(player.publicKey, program.programId);
+      [agentPda] = deriveAgentPda(player.publicKey, 0, program.programId);
 
       const name = TEST_CONSTANTS.AGENT_NAMES[0];
       const endpoint = TEST_CONSTANTS.ENDPOINT_URLS[0];
@@ -114,6 +163,7 @@ describe('Signal Wars', () => {
       await program.methods
         .registerAgent(name, endpoint)
         .accounts({
+          agentRegistry: deriveAgentRegistryPda(player.publicKey, program.programId)[0],
           agent: agentPda,
           arena: arenaPda,
           owner: player.publicKey,
@@ -129,6 +179,7 @@ describe('Signal Wars', () => {
       expect(agent.name).to.equal(name);
       expect(agent.endpoint).to.equal(endpoint);
       expect(agent.totalPredictions.toNumber()).to.equal(0);
+      expect(agent.predictionsSubmitted.toNumber()).to.equal(0);
       expect(agent.correctPredictions.toNumber()).to.equal(0);
       expect(agent.streak).to.equal(0);
       expect(agent.bestStreak).to.equal(0);
@@ -136,11 +187,12 @@ describe('Signal Wars', () => {
     });
 
     it('Should register a second agent', async () => {
-      [agent2Pda] = deriveAgentPda(player2.publicKey, program.programId);
+      [agent2Pda] = deriveAgentPda(player2.publicKey, 0, program.programId);
 
       await program.methods
         .registerAgent(TEST_CONSTANTS.AGENT_NAMES[1], TEST_CONSTANTS.ENDPOINT_URLS[1])
         .accounts({
+          agentRegistry: deriveAgentRegistryPda(player2.publicKey, program.programId)[0],
           agent: agent2Pda,
           arena: arenaPda,
           owner: player2.publicKey,
@@ -154,13 +206,14 @@ describe('Signal Wars', () => {
     });
 
     it('Should fail to register agent with name too long', async () => {
-      const [tempAgentPda] = deriveAgentPda(player3.publicKey, program.programId);
+      const [tempAgentPda] = deriveAgentPda(player3.publicKey, 0, program.programId);
       const longName = 'a'.repeat(TEST_CONSTANTS.MAX_NAME_LENGTH + 1);
 
       try {
         await program.methods
           .registerAgent(longName, TEST_CONSTANTS.ENDPOINT_URLS[0])
           .accounts({
+            agentRegistry: deriveAgentRegistryPda(player3.publicKey, program.programId)[0],
             agent: tempAgentPda,
             arena: arenaPda,
             owner: player3.publicKey,
@@ -176,13 +229,14 @@ describe('Signal Wars', () => {
     });
 
     it('Should fail to register agent with endpoint too long', async () => {
-      const [tempAgentPda] = deriveAgentPda(player3.publicKey, program.programId);
+      const [tempAgentPda] = deriveAgentPda(player3.publicKey, 0, program.programId);
       const longEndpoint = 'https://api.' + 'a'.repeat(150) + '.com';
 
       try {
         await program.methods
           .registerAgent('ValidName', longEndpoint)
           .accounts({
+            agentRegistry: deriveAgentRegistryPda(player3.publicKey, program.programId)[0],
             agent: tempAgentPda,
             arena: arenaPda,
             owner: player3.publicKey,
@@ -197,11 +251,12 @@ describe('Signal Wars', () => {
       }
     });
 
-    it('Should fail to register agent twice for same owner', async () => {
+    it('Should fail to re-register an existing agent index', async () => {
       try {
         await program.methods
           .registerAgent('Duplicate', 'https://api.dupe.com')
           .accounts({
+            agentRegistry: deriveAgentRegistryPda(player.publicKey, program.programId)[0],
             agent: agentPda,
             arena: arenaPda,
             owner: player.publicKey,
@@ -226,11 +281,11 @@ describe('Signal Wars', () => {
       [seasonPda] = deriveSeasonPda(seasonId, program.programId);
 
       await program.methods
-        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS)
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
         .accounts({
           season: seasonPda,
           arena: arenaPda,
-          authority: authority.publicKey,
+          seasonOperator: authority.publicKey,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([authority])
@@ -244,6 +299,11 @@ describe('Signal Wars', () => {
       expect(season.prizePoolBps).to.equal(PRIZE_POOL_BPS);
       expect(season.totalEntries.toNumber()).to.equal(0);
       expect(season.status).to.deep.equal({ active: {} });
+      expect(season.revealWindow.toNumber()).to.equal(REVEAL_WINDOW.toNumber());
+      expect(season.forfeitPolicy).to.deep.equal({ treasury: {} });
+      expect(season.payoutPlaces).to.equal(PAYOUT_BPS.length);
+      expect(season.payoutBps.slice(0, PAYOUT_BPS.length)).to.deep.equal(PAYOUT_BPS);
+      expect(season.remainderPolicy).to.deep.equal({ redistribute: {} });
       expect(arena.totalSeasons.toNumber()).to.equal(1);
       expect(season.endTime.toNumber()).to.be.greaterThan(season.startTime.toNumber());
     });
@@ -254,11 +314,11 @@ describe('Signal Wars', () => {
 
       try {
         await program.methods
-          .createSeason(ENTRY_FEE, DURATION_DAYS, 10001)
+          .createSeason(ENTRY_FEE, DURATION_DAYS, 10001, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
           .accounts({
             season: invalidSeasonPda,
             arena: arenaPda,
-            authority: authority.publicKey,
+            seasonOperator: authority.publicKey,
             systemProgram: web3.SystemProgram.programId,
           })
           .signers([authority])
@@ -269,6 +329,30 @@ describe('Signal Wars', () => {
         expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidPrizeSplit);
       }
     });
+
+    it('Should fail to create season with a payout schedule not summing to 10000 bps', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const [invalidSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+
+      for (const payoutBps of [[5000, 3000], [], [10000, 0]]) {
+        try {
+          await program.methods
+            .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps, remainderPolicy: { redistribute: {} } })
+            .accounts({
+              season: invalidSeasonPda,
+              arena: arenaPda,
+              seasonOperator: authority.publicKey,
+              systemProgram: web3.SystemProgram.programId,
+            })
+            .signers([authority])
+            .rpc();
+
+          assert.fail('Should have thrown an error');
+        } catch (error) {
+          expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidPayoutSchedule);
+        }
+      }
+    });
   });
 
   // ==========================================
@@ -365,8 +449,9 @@ describe('Signal Wars', () => {
       );
       [predictionVaultPda] = derivePredictionVaultPda(predictionPda, program.programId);
 
-      const predictionData = generatePredictionData('BTC', 'up', 50000, 85);
-      const predictionHash = generatePredictionHash(predictionData);
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      predictionNonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, predictionNonce, payload);
 
       const playerBalanceBefore = await provider.connection.getBalance(player.publicKey);
 
@@ -375,6 +460,7 @@ describe('Signal Wars', () => {
         .accounts({
           season: seasonPda,
           agent: agentPda,
+          arena: arenaPda,
           prediction: predictionPda,
           player: player.publicKey,
           predictionVault: predictionVaultPda,
@@ -391,6 +477,8 @@ describe('Signal Wars', () => {
       expect(Array.from(prediction.predictionHash)).to.deep.equal(Array.from(predictionHash));
       expect(prediction.stakeAmount.toNumber()).to.equal(STAKE_AMOUNT.toNumber());
       expect(prediction.status).to.deep.equal({ committed: {} });
+      expect(agent.predictionsSubmitted.toNumber()).to.equal(1);
+      expect(agent.totalPredictions.toNumber()).to.equal(0);
 
       const playerBalanceAfter = await provider.connection.getBalance(player.publicKey);
       expect(playerBalanceBefore - playerBalanceAfter).to.be.at.least(STAKE_AMOUNT.toNumber());
@@ -406,14 +494,16 @@ describe('Signal Wars', () => {
       );
       const [predictionVault2Pda] = derivePredictionVaultPda(prediction2Pda, program.programId);
 
-      const predictionData = generatePredictionData('ETH', 'down', 3000, 70);
-      const predictionHash = generatePredictionHash(predictionData);
+      const payload = generatePredictionPayload('ETH', 'down', 3000, 7000);
+      prediction2Nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agent2Pda, seasonPda, prediction2Nonce, payload);
 
       await program.methods
         .submitPrediction(Array.from(predictionHash), new BN(0))
         .accounts({
           season: seasonPda,
           agent: agent2Pda,
+          arena: arenaPda,
           prediction: prediction2Pda,
           player: player2.publicKey,
           predictionVault: predictionVault2Pda,
@@ -427,9 +517,9 @@ describe('Signal Wars', () => {
       expect(prediction.status).to.deep.equal({ committed: {} });
     });
 
-    it('Should submit multiple predictions with incremented count', async () => {
+    it('Should allow concurrent outstanding predictions per agent', async () => {
       const agent = await program.account.agent.fetch(agentPda);
-      const nextPredictionCount = new BN(agent.totalPredictions.toNumber());
+      const nextPredictionCount = new BN(agent.predictionsSubmitted.toNumber());
       
       const [prediction3Pda] = derivePredictionPda(
         agentPda,
@@ -439,14 +529,16 @@ describe('Signal Wars', () => {
       );
       const [predictionVault3Pda] = derivePredictionVaultPda(prediction3Pda, program.programId);
 
-      const predictionData = generatePredictionData('SOL', 'up', 150, 90);
-      const predictionHash = generatePredictionHash(predictionData);
+      const payload = generatePredictionPayload('SOL', 'up', 150, 9000);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, payload);
 
       await program.methods
         .submitPrediction(Array.from(predictionHash), STAKE_AMOUNT)
         .accounts({
           season: seasonPda,
           agent: agentPda,
+          arena: arenaPda,
           prediction: prediction3Pda,
           player: player.publicKey,
           predictionVault: predictionVault3Pda,
@@ -457,6 +549,16 @@ describe('Signal Wars', () => {
 
       const prediction = await program.account.prediction.fetch(prediction3Pda);
       expect(prediction.status).to.deep.equal({ committed: {} });
+
+      // The first prediction is still unresolved, yet the new one got its own PDA
+      const first = await program.account.prediction.fetch(predictionPda);
+      expect(first.status).to.deep.equal({ committed: {} });
+      expect(prediction3Pda.toString()).to.not.equal(predictionPda.toString());
+
+      const agentAfter = await program.account.agent.fetch(agentPda);
+      expect(agentAfter.predictionsSubmitted.toNumber()).to.equal(
+        nextPredictionCount.toNumber() + 1
+      );
     });
   });
 
@@ -465,10 +567,10 @@ describe('Signal Wars', () => {
   // ==========================================
   describe('Prediction Reveal', () => {
     it('Should reveal prediction with valid data', async () => {
-      const predictionData = generatePredictionData('BTC', 'up', 50000, 85);
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
 
       await program.methods
-        .revealPrediction(predictionData)
+        .revealPrediction(payload, Array.from(predictionNonce))
         .accounts({
           prediction: predictionPda,
           agent: agentPda,
@@ -479,16 +581,22 @@ describe('Signal Wars', () => {
 
       const prediction = await program.account.prediction.fetch(predictionPda);
 
-      expect(prediction.predictionData).to.equal(predictionData);
+      expect(prediction.assetId).to.deep.equal(payload.assetId);
+      expect(prediction.direction).to.deep.equal({ up: {} });
+      expect(prediction.targetPrice.toNumber()).to.equal(50000);
+      expect(prediction.confidenceBps).to.equal(8500);
+      expect(prediction.resolveAfter.toNumber()).to.equal(
+        prediction.submittedAt.toNumber() + payload.horizon.toNumber()
+      );
       expect(prediction.status).to.deep.equal({ revealed: {} });
       expect(prediction.revealedAt.toNumber()).to.be.greaterThan(0);
     });
 
     it('Should reveal prediction without stake', async () => {
-      const predictionData = generatePredictionData('ETH', 'down', 3000, 70);
+      const payload = generatePredictionPayload('ETH', 'down', 3000, 7000);
 
       await program.methods
-        .revealPrediction(predictionData)
+        .revealPrediction(payload, Array.from(prediction2Nonce))
         .accounts({
           prediction: prediction2Pda,
           agent: agent2Pda,
@@ -498,13 +606,13 @@ describe('Signal Wars', () => {
         .rpc();
 
       const prediction = await program.account.prediction.fetch(prediction2Pda);
-      expect(prediction.predictionData).to.equal(predictionData);
+      expect(prediction.direction).to.deep.equal({ down: {} });
       expect(prediction.status).to.deep.equal({ revealed: {} });
     });
 
     it('Should fail to reveal with invalid hash (tampered data)', async () => {
       const agent = await program.account.agent.fetch(agentPda);
-      const predictionCount = new BN(agent.totalPredictions.toNumber());
+      const predictionCount = new BN(agent.predictionsSubmitted.toNumber());
       
       const [tempPredictionPda] = derivePredictionPda(
         agentPda,
@@ -514,14 +622,16 @@ describe('Signal Wars', () => {
       );
       const [tempVaultPda] = derivePredictionVaultPda(tempPredictionPda, program.programId);
 
-      const originalData = generatePredictionData('SOL', 'up', 150, 90);
-      const predictionHash = generatePredictionHash(originalData);
+      const originalPayload = generatePredictionPayload('SOL', 'up', 150, 9000);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, originalPayload);
 
       await program.methods
         .submitPrediction(Array.from(predictionHash), new BN(0))
         .accounts({
           season: seasonPda,
           agent: agentPda,
+          arena: arenaPda,
           prediction: tempPredictionPda,
           player: player.publicKey,
           predictionVault: tempVaultPda,
@@ -530,11 +640,11 @@ describe('Signal Wars', () => {
         .signers([player])
         .rpc();
 
-      const tamperedData = generatePredictionData('SOL', 'down', 150, 90);
+      const tamperedPayload = generatePredictionPayload('SOL', 'down', 150, 9000);
 
       try {
         await program.methods
-          .revealPrediction(tamperedData)
+          .revealPrediction(tamperedPayload, Array.from(nonce))
           .accounts({
             prediction: tempPredictionPda,
             agent: agentPda,
@@ -549,12 +659,59 @@ describe('Signal Wars', () => {
       }
     });
 
+    it('Should fail to reveal with the wrong nonce', async () => {
+      const agent = await program.account.agent.fetch(agentPda);
+      const predictionCount = new BN(agent.predictionsSubmitted.toNumber());
+
+      const [tempPredictionPda] = derivePredictionPda(
+        agentPda,
+        seasonPda,
+        predictionCount,
+        program.programId
+      );
+      const [tempVaultPda] = derivePredictionVaultPda(tempPredictionPda, program.programId);
+
+      const payload = generatePredictionPayload('SOL', 'up', 150, 9000);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, payload);
+
+      await program.methods
+        .submitPrediction(Array.from(predictionHash), new BN(0))
+        .accounts({
+          season: seasonPda,
+          agent: agentPda,
+          arena: arenaPda,
+          prediction: tempPredictionPda,
+          player: player.publicKey,
+          predictionVault: tempVaultPda,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      try {
+        await program.methods
+          .revealPrediction(payload, Array.from(generateNonce()))
+          .accounts({
+            prediction: tempPredictionPda,
+            agent: agentPda,
+            player: player.publicKey,
+          })
+          .signers([player])
+          .rpc();
+
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.HashMismatch);
+      }
+    });
+
     it('Should fail to reveal already revealed prediction', async () => {
-      const predictionData = generatePredictionData('BTC', 'up', 50000, 85);
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
 
       try {
         await program.methods
-          .revealPrediction(predictionData)
+          .revealPrediction(payload, Array.from(predictionNonce))
           .accounts({
             prediction: predictionPda,
             agent: agentPda,
@@ -583,7 +740,7 @@ describe('Signal Wars', () => {
           prediction: predictionPda,
           agent: agentPda,
           seasonEntry: seasonEntryPda,
-          authority: authority.publicKey,
+          resolver: authority.publicKey,
           predictionVault: predictionVaultPda,
           player: player.publicKey,
         })
@@ -610,7 +767,7 @@ describe('Signal Wars', () => {
 
     it('Should resolve prediction as incorrect', async () => {
       const agent = await program.account.agent.fetch(agent2Pda);
-      const predictionCount = new BN(agent.totalPredictions.toNumber());
+      const predictionCount = new BN(agent.predictionsSubmitted.toNumber());
       
       const [newPredictionPda] = derivePredictionPda(
         agent2Pda,
@@ -620,14 +777,16 @@ describe('Signal Wars', () => {
       );
       const [newVaultPda] = derivePredictionVaultPda(newPredictionPda, program.programId);
 
-      const predictionData = generatePredictionData('BTC', 'up', 50000, 80);
-      const predictionHash = generatePredictionHash(predictionData);
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8000);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agent2Pda, seasonPda, nonce, payload);
 
       await program.methods
         .submitPrediction(Array.from(predictionHash), STAKE_AMOUNT)
         .accounts({
           season: seasonPda,
           agent: agent2Pda,
+          arena: arenaPda,
           prediction: newPredictionPda,
           player: player2.publicKey,
           predictionVault: newVaultPda,
@@ -637,7 +796,7 @@ describe('Signal Wars', () => {
         .rpc();
 
       await program.methods
-        .revealPrediction(predictionData)
+        .revealPrediction(payload, Array.from(nonce))
         .accounts({
           prediction: newPredictionPda,
           agent: agent2Pda,
@@ -654,7 +813,7 @@ describe('Signal Wars', () => {
           prediction: newPredictionPda,
           agent: agent2Pda,
           seasonEntry: seasonEntry2Pda,
-          authority: authority.publicKey,
+          resolver: authority.publicKey,
           predictionVault: newVaultPda,
           player: player2.publicKey,
         })
@@ -674,7 +833,7 @@ describe('Signal Wars', () => {
 
     it('Should calculate streak correctly on consecutive wins', async () => {
       const agent = await program.account.agent.fetch(agentPda);
-      let predictionCount = new BN(agent.totalPredictions.toNumber());
+      let predictionCount = new BN(agent.predictionsSubmitted.toNumber());
 
       for (let i = 0; i < 3; i++) {
         const [streakPredictionPda] = derivePredictionPda(
@@ -685,14 +844,16 @@ describe('Signal Wars', () => {
         );
         const [streakVaultPda] = derivePredictionVaultPda(streakPredictionPda, program.programId);
 
-        const predictionData = generatePredictionData('BTC', 'up', 50000 + i * 1000, 85);
-        const predictionHash = generatePredictionHash(predictionData);
+        const payload = generatePredictionPayload('BTC', 'up', 50000 + i * 1000, 8500);
+        const nonce = generateNonce();
+        const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, payload);
 
         await program.methods
           .submitPrediction(Array.from(predictionHash), new BN(0))
           .accounts({
             season: seasonPda,
             agent: agentPda,
+            arena: arenaPda,
             prediction: streakPredictionPda,
             player: player.publicKey,
             predictionVault: streakVaultPda,
@@ -702,7 +863,7 @@ describe('Signal Wars', () => {
           .rpc();
 
         await program.methods
-          .revealPrediction(predictionData)
+          .revealPrediction(payload, Array.from(nonce))
           .accounts({
             prediction: streakPredictionPda,
             agent: agentPda,
@@ -717,7 +878,7 @@ describe('Signal Wars', () => {
             prediction: streakPredictionPda,
             agent: agentPda,
             seasonEntry: seasonEntryPda,
-            authority: authority.publicKey,
+            resolver: authority.publicKey,
             predictionVault: streakVaultPda,
             player: player.publicKey,
           })
@@ -740,7 +901,7 @@ describe('Signal Wars', () => {
             prediction: predictionPda,
             agent: agentPda,
             seasonEntry: seasonEntryPda,
-            authority: authority.publicKey,
+            resolver: authority.publicKey,
             predictionVault: predictionVaultPda,
             player: player.publicKey,
           })
@@ -755,7 +916,7 @@ describe('Signal Wars', () => {
 
     it('Should fail to resolve unrevealed prediction', async () => {
       const agent = await program.account.agent.fetch(agentPda);
-      const predictionCount = new BN(agent.totalPredictions.toNumber());
+      const predictionCount = new BN(agent.predictionsSubmitted.toNumber());
       
       const [unrevealedPda] = derivePredictionPda(
         agentPda,
@@ -765,14 +926,16 @@ describe('Signal Wars', () => {
       );
       const [unrevealedVaultPda] = derivePredictionVaultPda(unrevealedPda, program.programId);
 
-      const predictionData = generatePredictionData('BTC', 'up', 50000, 85);
-      const predictionHash = generatePredictionHash(predictionData);
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, payload);
 
       await program.methods
         .submitPrediction(Array.from(predictionHash), new BN(0))
         .accounts({
           season: seasonPda,
           agent: agentPda,
+          arena: arenaPda,
           prediction: unrevealedPda,
           player: player.publicKey,
           predictionVault: unrevealedVaultPda,
@@ -788,7 +951,7 @@ describe('Signal Wars', () => {
             prediction: unrevealedPda,
             agent: agentPda,
             seasonEntry: seasonEntryPda,
-            authority: authority.publicKey,
+            resolver: authority.publicKey,
             predictionVault: unrevealedVaultPda,
             player: player.publicKey,
           })
@@ -810,11 +973,12 @@ describe('Signal Wars', () => {
       const highPerformer = web3.Keypair.generate();
       await airdrop(provider, highPerformer, 5);
 
-      const [highAgentPda] = deriveAgentPda(highPerformer.publicKey, program.programId);
+      const [highAgentPda] = deriveAgentPda(highPerformer.publicKey, 0, program.programId);
       
       await program.methods
         .registerAgent('HighPerformer', 'https://api.high.com')
         .accounts({
+          agentRegistry: deriveAgentRegistryPda(highPerformer.publicKey, program.programId)[0],
           agent: highAgentPda,
           arena: arenaPda,
           owner: highPerformer.publicKey,
@@ -847,14 +1011,16 @@ describe('Signal Wars', () => {
         );
         const [vaultPda] = derivePredictionVaultPda(predPda, program.programId);
 
-        const predictionData = generatePredictionData('BTC', 'up', 50000 + i * 100, 90);
-        const predictionHash = generatePredictionHash(predictionData);
+        const payload = generatePredictionPayload('BTC', 'up', 50000 + i * 100, 9000);
+        const nonce = generateNonce();
+        const predictionHash = generatePredictionHash(highAgentPda, seasonPda, nonce, payload);
 
         await program.methods
           .submitPrediction(Array.from(predictionHash), new BN(0))
           .accounts({
             season: seasonPda,
             agent: highAgentPda,
+            arena: arenaPda,
             prediction: predPda,
             player: highPerformer.publicKey,
             predictionVault: vaultPda,
@@ -864,7 +1030,7 @@ describe('Signal Wars', () => {
           .rpc();
 
         await program.methods
-          .revealPrediction(predictionData)
+          .revealPrediction(payload, Array.from(nonce))
           .accounts({
             prediction: predPda,
             agent: highAgentPda,
@@ -879,7 +1045,7 @@ describe('Signal Wars', () => {
             prediction: predPda,
             agent: highAgentPda,
             seasonEntry: highEntryPda,
-            authority: authority.publicKey,
+            resolver: authority.publicKey,
             predictionVault: vaultPda,
             player: highPerformer.publicKey,
           })
@@ -913,7 +1079,7 @@ describe('Signal Wars', () => {
         .accounts({
           agent: agentPda,
           achievement: achievementPda,
-          authority: authority.publicKey,
+          achievementIssuer: authority.publicKey,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([authority])
@@ -942,7 +1108,7 @@ describe('Signal Wars', () => {
         .accounts({
           agent: agentPda,
           achievement: achievementPda,
-          authority: authority.publicKey,
+          achievementIssuer: authority.publicKey,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([authority])
@@ -967,7 +1133,7 @@ describe('Signal Wars', () => {
         .accounts({
           agent: agentPda,
           achievement: achievementPda,
-          authority: authority.publicKey,
+          achievementIssuer: authority.publicKey,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([authority])
@@ -987,11 +1153,11 @@ describe('Signal Wars', () => {
       const [shortSeasonPda] = deriveSeasonPda(shortSeasonId, program.programId);
 
       await program.methods
-        .createSeason(ENTRY_FEE, TEST_CONSTANTS.DURATION_DAYS_SHORT, PRIZE_POOL_BPS)
+        .createSeason(ENTRY_FEE, TEST_CONSTANTS.DURATION_DAYS_SHORT, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
         .accounts({
           season: shortSeasonPda,
           arena: arenaPda,
-          authority: authority.publicKey,
+          seasonOperator: authority.publicKey,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([authority])
@@ -1003,7 +1169,7 @@ describe('Signal Wars', () => {
         .distributePrizes()
         .accounts({
           season: shortSeasonPda,
-          authority: authority.publicKey,
+          seasonOperator: authority.publicKey,
         })
         .signers([authority])
         .rpc();
@@ -1018,7 +1184,7 @@ describe('Signal Wars', () => {
           .distributePrizes()
           .accounts({
             season: seasonPda,
-            authority: authority.publicKey,
+            seasonOperator: authority.publicKey,
           })
           .signers([authority])
           .rpc();
@@ -1034,11 +1200,11 @@ describe('Signal Wars', () => {
       const [shortSeasonPda] = deriveSeasonPda(shortSeasonId, program.programId);
 
       await program.methods
-        .createSeason(ENTRY_FEE, TEST_CONSTANTS.DURATION_DAYS_SHORT, PRIZE_POOL_BPS)
+        .createSeason(ENTRY_FEE, TEST_CONSTANTS.DURATION_DAYS_SHORT, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
         .accounts({
           season: shortSeasonPda,
           arena: arenaPda,
-          authority: authority.publicKey,
+          seasonOperator: authority.publicKey,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([authority])
@@ -1050,7 +1216,7 @@ describe('Signal Wars', () => {
         .distributePrizes()
         .accounts({
           season: shortSeasonPda,
-          authority: authority.publicKey,
+          seasonOperator: authority.publicKey,
         })
         .signers([authority])
         .rpc();
@@ -1060,7 +1226,7 @@ describe('Signal Wars', () => {
           .distributePrizes()
           .accounts({
             season: shortSeasonPda,
-            authority: authority.publicKey,
+            seasonOperator: authority.publicKey,
           })
           .signers([authority])
           .rpc();
@@ -1081,11 +1247,11 @@ describe('Signal Wars', () => {
       const [completedSeasonPda] = deriveSeasonPda(seasonId, program.programId);
 
       await program.methods
-        .createSeason(ENTRY_FEE, TEST_CONSTANTS.DURATION_DAYS_SHORT, PRIZE_POOL_BPS)
+        .createSeason(ENTRY_FEE, TEST_CONSTANTS.DURATION_DAYS_SHORT, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
         .accounts({
           season: completedSeasonPda,
           arena: arenaPda,
-          authority: authority.publicKey,
+          seasonOperator: authority.publicKey,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([authority])
@@ -1097,7 +1263,7 @@ describe('Signal Wars', () => {
         .distributePrizes()
         .accounts({
           season: completedSeasonPda,
-          authority: authority.publicKey,
+          seasonOperator: authority.publicKey,
         })
         .signers([authority])
         .rpc();
@@ -1134,7 +1300,7 @@ describe('Signal Wars', () => {
       await airdrop(provider, unauthorized);
 
       const agent = await program.account.agent.fetch(agentPda);
-      const predictionCount = new BN(agent.totalPredictions.toNumber());
+      const predictionCount = new BN(agent.predictionsSubmitted.toNumber());
       
       const [testPda] = derivePredictionPda(
         agentPda,
@@ -1144,14 +1310,16 @@ describe('Signal Wars', () => {
       );
       const [testVaultPda] = derivePredictionVaultPda(testPda, program.programId);
 
-      const predictionData = generatePredictionData('BTC', 'up', 50000, 85);
-      const predictionHash = generatePredictionHash(predictionData);
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, payload);
 
       await program.methods
         .submitPrediction(Array.from(predictionHash), new BN(0))
         .accounts({
           season: seasonPda,
           agent: agentPda,
+          arena: arenaPda,
           prediction: testPda,
           player: player.publicKey,
           predictionVault: testVaultPda,
@@ -1161,7 +1329,7 @@ describe('Signal Wars', () => {
         .rpc();
 
       await program.methods
-        .revealPrediction(predictionData)
+        .revealPrediction(payload, Array.from(nonce))
         .accounts({
           prediction: testPda,
           agent: agentPda,
@@ -1177,7 +1345,7 @@ describe('Signal Wars', () => {
             prediction: testPda,
             agent: agentPda,
             seasonEntry: seasonEntryPda,
-            authority: unauthorized.publicKey,
+            resolver: unauthorized.publicKey,
             predictionVault: testVaultPda,
             player: player.publicKey,
           })
@@ -1190,52 +1358,57 @@ describe('Signal Wars', () => {
       }
     });
 
-    it('Should handle empty prediction data reveal', async () => {
+    it('Should reject a payload that fails validation', async () => {
       const agent = await program.account.agent.fetch(agentPda);
-      const predictionCount = new BN(agent.totalPredictions.toNumber());
+      const predictionCount = new BN(agent.predictionsSubmitted.toNumber());
       
-      const [emptyPda] = derivePredictionPda(
+      const [invalidPda] = derivePredictionPda(
         agentPda,
         seasonPda,
         predictionCount,
         program.programId
       );
-      const [emptyVaultPda] = derivePredictionVaultPda(emptyPda, program.programId);
+      const [invalidVaultPda] = derivePredictionVaultPda(invalidPda, program.programId);
 
-      const emptyData = '';
-      const emptyHash = generatePredictionHash(emptyData);
+      // Commitment matches, but confidence is above 100%
+      const invalidPayload = generatePredictionPayload('BTC', 'up', 50000, 10001);
+      const nonce = generateNonce();
+      const invalidHash = generatePredictionHash(agentPda, seasonPda, nonce, invalidPayload);
 
       await program.methods
-        .submitPrediction(Array.from(emptyHash), new BN(0))
+        .submitPrediction(Array.from(invalidHash), new BN(0))
         .accounts({
           season: seasonPda,
           agent: agentPda,
-          prediction: emptyPda,
+          arena: arenaPda,
+          prediction: invalidPda,
           player: player.publicKey,
-          predictionVault: emptyVaultPda,
+          predictionVault: invalidVaultPda,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([player])
         .rpc();
 
-      await program.methods
-        .revealPrediction(emptyData)
-        .accounts({
-          prediction: emptyPda,
-          agent: agentPda,
-          player: player.publicKey,
-        })
-        .signers([player])
-        .rpc();
+      try {
+        await program.methods
+          .revealPrediction(invalidPayload, Array.from(nonce))
+          .accounts({
+            prediction: invalidPda,
+            agent: agentPda,
+            player: player.publicKey,
+          })
+          .signers([player])
+          .rpc();
 
-      const prediction = await program.account.prediction.fetch(emptyPda);
-      expect(prediction.predictionData).to.equal('');
-      expect(prediction.status).to.deep.equal({ revealed: {} });
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidConfidence);
+      }
     });
 
     it('Should prevent double submission with same prediction count', async () => {
       const agent = await program.account.agent.fetch(agentPda);
-      const predictionCount = new BN(agent.totalPredictions.toNumber());
+      const predictionCount = new BN(agent.predictionsSubmitted.toNumber());
       
       const [doublePda] = derivePredictionPda(
         agentPda,
@@ -1245,14 +1418,16 @@ describe('Signal Wars', () => {
       );
       const [doubleVaultPda] = derivePredictionVaultPda(doublePda, program.programId);
 
-      const predictionData = generatePredictionData('BTC', 'up', 50000, 85);
-      const predictionHash = generatePredictionHash(predictionData);
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, payload);
 
       await program.methods
         .submitPrediction(Array.from(predictionHash), new BN(0))
         .accounts({
           season: seasonPda,
           agent: agentPda,
+          arena: arenaPda,
           prediction: doublePda,
           player: player.publicKey,
           predictionVault: doubleVaultPda,
@@ -1267,6 +1442,7 @@ describe('Signal Wars', () => {
           .accounts({
             season: seasonPda,
             agent: agentPda,
+            arena: arenaPda,
             prediction: doublePda,
             player: player.publicKey,
             predictionVault: doubleVaultPda,
@@ -1284,7 +1460,7 @@ describe('Signal Wars', () => {
     it('Should handle large stake amounts correctly', async () => {
       const largeStake = new BN(TEST_CONSTANTS.LARGE_STAKE_LAMPORTS);
       const agent = await program.account.agent.fetch(agentPda);
-      const predictionCount = new BN(agent.totalPredictions.toNumber());
+      const predictionCount = new BN(agent.predictionsSubmitted.toNumber());
       
       const [largeStakePda] = derivePredictionPda(
         agentPda,
@@ -1294,8 +1470,9 @@ describe('Signal Wars', () => {
       );
       const [largeVaultPda] = derivePredictionVaultPda(largeStakePda, program.programId);
 
-      const predictionData = generatePredictionData('BTC', 'up', 50000, 85);
-      const predictionHash = generatePredictionHash(predictionData);
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, payload);
 
       const playerBalanceBefore = await provider.connection.getBalance(player.publicKey);
 
@@ -1304,6 +1481,7 @@ describe('Signal Wars', () => {
         .accounts({
           season: seasonPda,
           agent: agentPda,
+          arena: arenaPda,
           prediction: largeStakePda,
           player: player.publicKey,
           predictionVault: largeVaultPda,
@@ -1319,7 +1497,7 @@ describe('Signal Wars', () => {
       expect(vaultBalance).to.be.at.least(largeStake.toNumber());
 
       await program.methods
-        .revealPrediction(predictionData)
+        .revealPrediction(payload, Array.from(nonce))
         .accounts({
           prediction: largeStakePda,
           agent: agentPda,
@@ -1336,7 +1514,7 @@ describe('Signal Wars', () => {
           prediction: largeStakePda,
           agent: agentPda,
           seasonEntry: seasonEntryPda,
-          authority: authority.publicKey,
+          resolver: authority.publicKey,
           predictionVault: largeVaultPda,
           player: player.publicKey,
         })
@@ -1351,11 +1529,12 @@ describe('Signal Wars', () => {
       const scoreAgent = web3.Keypair.generate();
       await airdrop(provider, scoreAgent, 5);
 
-      const [scoreAgentPda] = deriveAgentPda(scoreAgent.publicKey, program.programId);
+      const [scoreAgentPda] = deriveAgentPda(scoreAgent.publicKey, 0, program.programId);
       
       await program.methods
         .registerAgent('ScoreTest', 'https://api.score.com')
         .accounts({
+          agentRegistry: deriveAgentRegistryPda(scoreAgent.publicKey, program.programId)[0],
           agent: scoreAgentPda,
           arena: arenaPda,
           owner: scoreAgent.publicKey,
@@ -1392,14 +1571,16 @@ describe('Signal Wars', () => {
         );
         const [vaultPda] = derivePredictionVaultPda(predPda, program.programId);
 
-        const predictionData = generatePredictionData('BTC', 'up', 50000 + i * 100, 90);
-        const predictionHash = generatePredictionHash(predictionData);
+        const payload = generatePredictionPayload('BTC', 'up', 50000 + i * 100, 9000);
+        const nonce = generateNonce();
+        const predictionHash = generatePredictionHash(scoreAgentPda, seasonPda, nonce, payload);
 
         await program.methods
           .submitPrediction(Array.from(predictionHash), stakeAmount)
           .accounts({
             season: seasonPda,
             agent: scoreAgentPda,
+            arena: arenaPda,
             prediction: predPda,
             player: scoreAgent.publicKey,
             predictionVault: vaultPda,
@@ -1409,7 +1590,7 @@ describe('Signal Wars', () => {
           .rpc();
 
         await program.methods
-          .revealPrediction(predictionData)
+          .revealPrediction(payload, Array.from(nonce))
           .accounts({
             prediction: predPda,
             agent: scoreAgentPda,
@@ -1424,7 +1605,7 @@ describe('Signal Wars', () => {
             prediction: predPda,
             agent: scoreAgentPda,
             seasonEntry: scoreEntryPda,
-            authority: authority.publicKey,
+            resolver: authority.publicKey,
             predictionVault: vaultPda,
             player: scoreAgent.publicKey,
           })
@@ -1454,11 +1635,12 @@ describe('Signal Wars', () => {
       const eventAgent = web3.Keypair.generate();
       await airdrop(provider, eventAgent);
 
-      const [eventAgentPda] = deriveAgentPda(eventAgent.publicKey, program.programId);
+      const [eventAgentPda] = deriveAgentPda(eventAgent.publicKey, 0, program.programId);
 
       await program.methods
         .registerAgent('EventTestAgent', 'https://api.event.com')
         .accounts({
+          agentRegistry: deriveAgentRegistryPda(eventAgent.publicKey, program.programId)[0],
           agent: eventAgentPda,
           arena: arenaPda,
           owner: eventAgent.publicKey,
@@ -1478,7 +1660,7 @@ describe('Signal Wars', () => {
       });
 
       const agent = await program.account.agent.fetch(agentPda);
-      const predictionCount = new BN(agent.totalPredictions.toNumber());
+      const predictionCount = new BN(agent.predictionsSubmitted.toNumber());
       
       const [eventPredPda] = derivePredictionPda(
         agentPda,
@@ -1488,14 +1670,16 @@ describe('Signal Wars', () => {
       );
       const [eventVaultPda] = derivePredictionVaultPda(eventPredPda, program.programId);
 
-      const predictionData = generatePredictionData('BTC', 'up', 50000, 85);
-      const predictionHash = generatePredictionHash(predictionData);
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, payload);
 
       await program.methods
         .submitPrediction(Array.from(predictionHash), new BN(0))
         .accounts({
           season: seasonPda,
           agent: agentPda,
+          arena: arenaPda,
           prediction: eventPredPda,
           player: player.publicKey,
           predictionVault: eventVaultPda,
@@ -1509,4 +1693,4755 @@ describe('Signal Wars', () => {
       await program.removeEventListener(eventListener);
     });
   });
+
+  // ==========================================
+  // Test Suite 13: Oracle Resolution
+  // ==========================================
+  describe('Oracle Resolution', () => {
+    const assetId = assetIdFromSymbol('SOL');
+    const PRICE_EXPO = -8;
+    let assetFeedPda: web3.PublicKey;
+    let mockFeedPda: web3.PublicKey;
+
+    const submitAndReveal = async (payload: PredictionPayload, stake = new BN(0)) => {
+      const agent = await program.account.agent.fetch(agentPda);
+      const [predPda] = derivePredictionPda(
+        agentPda,
+        seasonPda,
+        new BN(agent.predictionsSubmitted.toNumber()),
+        program.programId
+      );
+      const [vaultPda] = derivePredictionVaultPda(predPda, program.programId);
+
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, payload);
+
+      await program.methods
+        .submitPrediction(Array.from(predictionHash), stake)
+        .accounts({
+          season: seasonPda,
+          agent: agentPda,
+          arena: arenaPda,
+          prediction: predPda,
+          player: player.publicKey,
+          predictionVault: vaultPda,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      await program.methods
+        .revealPrediction(payload, Array.from(nonce))
+        .accounts({
+          prediction: predPda,
+          agent: agentPda,
+          player: player.publicKey,
+        })
+        .signers([player])
+        .rpc();
+
+      return { predPda, vaultPda };
+    };
+
+    const crank = async (
+      predPda: web3.PublicKey,
+      vaultPda: web3.PublicKey,
+      feeds = { assetFeed: assetFeedPda, priceFeed: mockFeedPda }
+    ) => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const cranker = web3.Keypair.generate();
+      await program.methods
+        .resolvePredictionWithOracle()
+        .accounts({
+          prediction: predPda,
+          agent: agentPda,
+          seasonEntry: seasonEntryPda,
+          arena: arenaPda,
+          assetFeed: feeds.assetFeed,
+          priceFeed: feeds.priceFeed,
+          predictionVault: vaultPda,
+          treasury: arena.treasury,
+          player: player.publicKey,
+          cranker: cranker.publicKey,
+        })
+        .signers([cranker])
+        .rpc();
+    };
+
+    before(async () => {
+      [assetFeedPda] = deriveAssetFeedPda(assetId, program.programId);
+      [mockFeedPda] = deriveMockPriceFeedPda(assetId, program.programId);
+
+      await program.methods
+        .initMockPriceFeed(Array.from(assetId), PRICE_EXPO)
+        .accounts({
+          mockPriceFeed: mockFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .registerAssetFeed(Array.from(assetId), mockFeedPda, new BN(60))
+        .accounts({
+          assetFeed: assetFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
+    it('Should let anyone resolve a prediction from the price feed after the horizon', async () => {
+      const payload = generatePredictionPayload('SOL', 'up', 150, 8000, 3);
+      const { predPda, vaultPda } = await submitAndReveal(payload);
+
+      await new Promise(resolve => setTimeout(resolve, 4000));
+
+      // 155.00000000 >= 150 -> correct
+      await program.methods
+        .setMockPrice(Array.from(assetId), new BN(155_00000000), new BN(1000))
+        .accounts({
+          mockPriceFeed: mockFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      // The price window keeps running through a pause, so cranking is never paused
+      const setPause = (flags: number) =>
+        program.methods
+          .setPause(flags)
+          .accounts({ arena: arenaPda, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+      await setPause(TEST_CONSTANTS.PAUSE_ALL);
+      try {
+        await crank(predPda, vaultPda);
+      } finally {
+        await setPause(0);
+      }
+
+      const prediction = await program.account.prediction.fetch(predPda);
+      expect(prediction.status).to.deep.equal({ resolved: {} });
+      expect(prediction.wasCorrect).to.equal(true);
+    });
+
+    it('Should resolve a missed target as incorrect', async () => {
+      const payload = generatePredictionPayload('SOL', 'up', 200, 8000, 3);
+      const { predPda, vaultPda } = await submitAndReveal(payload);
+
+      await new Promise(resolve => setTimeout(resolve, 4000));
+
+      await program.methods
+        .setMockPrice(Array.from(assetId), new BN(155_00000000), new BN(1000))
+        .accounts({
+          mockPriceFeed: mockFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      await crank(predPda, vaultPda);
+
+      const prediction = await program.account.prediction.fetch(predPda);
+      expect(prediction.wasCorrect).to.equal(false);
+    });
+
+    it('Should reject manual resolution of an asset with a price feed', async () => {
+      const payload = generatePredictionPayload('SOL', 'up', 150, 8000, 3600);
+      const { predPda, vaultPda } = await submitAndReveal(payload);
+
+      try {
+        await program.methods
+          .resolvePrediction(true)
+          .accounts({
+            prediction: predPda,
+            agent: agentPda,
+            seasonEntry: seasonEntryPda,
+            resolver: authority.publicKey,
+            assetFeed: assetFeedPda,
+            predictionVault: vaultPda,
+            player: player.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.AssetHasPriceFeed);
+      }
+    });
+
+    it('Should fail to resolve before the horizon', async () => {
+      const payload = generatePredictionPayload('SOL', 'down', 100, 8000, 3600);
+      const { predPda, vaultPda } = await submitAndReveal(payload);
+
+      try {
+        await crank(predPda, vaultPda);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.HorizonNotReached);
+      }
+    });
+
+    it('Should fail to use a price published before the horizon', async () => {
+      await program.methods
+        .setMockPrice(Array.from(assetId), new BN(155_00000000), new BN(1000))
+        .accounts({
+          mockPriceFeed: mockFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const payload = generatePredictionPayload('SOL', 'up', 150, 8000, 3);
+      const { predPda, vaultPda } = await submitAndReveal(payload);
+
+      await new Promise(resolve => setTimeout(resolve, 4000));
+
+      try {
+        await crank(predPda, vaultPda);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.StalePrice);
+      }
+    });
+
+    it('Should fail to reveal after the horizon', async () => {
+      const payload = generatePredictionPayload('SOL', 'up', 150, 8000, 1);
+      const agent = await program.account.agent.fetch(agentPda);
+      const [predPda] = derivePredictionPda(
+        agentPda,
+        seasonPda,
+        new BN(agent.predictionsSubmitted.toNumber()),
+        program.programId
+      );
+      const [vaultPda] = derivePredictionVaultPda(predPda, program.programId);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, seasonPda, nonce, payload);
+
+      await program.methods
+        .submitPrediction(Array.from(predictionHash), new BN(0))
+        .accounts({
+          season: seasonPda,
+          agent: agentPda,
+          arena: arenaPda,
+          prediction: predPda,
+          player: player.publicKey,
+          predictionVault: vaultPda,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 2000));
+
+      // The horizon price may already be out; a late reveal could never be cranked
+      try {
+        await program.methods
+          .revealPrediction(payload, Array.from(nonce))
+          .accounts({
+            prediction: predPda,
+            agent: agentPda,
+            player: player.publicKey,
+          })
+          .signers([player])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.RevealWindowClosed);
+      }
+    });
+
+    it('Should fail to void a prediction while its resolution window is open', async () => {
+      const payload = generatePredictionPayload('SOL', 'down', 100, 8000, 3600);
+      const { predPda, vaultPda } = await submitAndReveal(payload);
+
+      try {
+        await program.methods
+          .voidPrediction()
+          .accounts({
+            prediction: predPda,
+            agent: agentPda,
+            seasonEntry: seasonEntryPda,
+            arena: arenaPda,
+            assetFeed: assetFeedPda,
+            priceFeed: mockFeedPda,
+            predictionVault: vaultPda,
+            player: player.publicKey,
+            cranker: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.ResolutionWindowOpen);
+      }
+    });
+
+    it('Should void a prediction whose horizon price was never cranked and refund the stake', async () => {
+      // One-second staleness window: only prices within a second of the horizon settle it
+      const bonkAssetId = assetIdFromSymbol('BONK');
+      const [bonkAssetFeedPda] = deriveAssetFeedPda(bonkAssetId, program.programId);
+      const [bonkMockFeedPda] = deriveMockPriceFeedPda(bonkAssetId, program.programId);
+
+      await program.methods
+        .initMockPriceFeed(Array.from(bonkAssetId), PRICE_EXPO)
+        .accounts({
+          mockPriceFeed: bonkMockFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .registerAssetFeed(Array.from(bonkAssetId), bonkMockFeedPda, new BN(1))
+        .accounts({
+          assetFeed: bonkAssetFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const payload = generatePredictionPayload('BONK', 'up', 3000, 8000, 3);
+      const { predPda, vaultPda } = await submitAndReveal(payload, STAKE_AMOUNT);
+
+      await new Promise(resolve => setTimeout(resolve, 6000));
+
+      await program.methods
+        .setMockPrice(Array.from(bonkAssetId), new BN(3500_00000000), new BN(1000))
+        .accounts({
+          mockPriceFeed: bonkMockFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      try {
+        await crank(predPda, vaultPda, { assetFeed: bonkAssetFeedPda, priceFeed: bonkMockFeedPda });
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.ResolutionWindowMissed);
+      }
+
+      // Past resolve_after + max_staleness, anyone can void it and return the stake
+      const agentBefore = await program.account.agent.fetch(agentPda);
+      const playerBefore = await provider.connection.getBalance(player.publicKey);
+      await program.methods
+        .voidPrediction()
+        .accounts({
+          prediction: predPda,
+          agent: agentPda,
+          seasonEntry: seasonEntryPda,
+          arena: arenaPda,
+          assetFeed: bonkAssetFeedPda,
+          priceFeed: bonkMockFeedPda,
+          predictionVault: vaultPda,
+          player: player.publicKey,
+          cranker: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const prediction = await program.account.prediction.fetch(predPda);
+      expect(prediction.status).to.deep.equal({ expired: {} });
+      expect(prediction.wasCorrect).to.equal(false);
+      const playerAfter = await provider.connection.getBalance(player.publicKey);
+      expect(playerAfter - playerBefore).to.equal(STAKE_AMOUNT.toNumber());
+      const agentAfter = await program.account.agent.fetch(agentPda);
+      expect(agentAfter.openPredictions).to.equal(agentBefore.openPredictions - 1);
+      expect(agentAfter.totalPredictions.toNumber()).to.equal(
+        agentBefore.totalPredictions.toNumber()
+      );
+    });
+
+    it('Should let the authority repoint an asset feed', async () => {
+      const bonkAssetId = assetIdFromSymbol('BONK');
+      const [bonkAssetFeedPda] = deriveAssetFeedPda(bonkAssetId, program.programId);
+      const [bonkMockFeedPda] = deriveMockPriceFeedPda(bonkAssetId, program.programId);
+
+      await program.methods
+        .updateAssetFeed(Array.from(bonkAssetId), bonkMockFeedPda, new BN(120))
+        .accounts({
+          assetFeed: bonkAssetFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const assetFeed = await program.account.assetFeed.fetch(bonkAssetFeedPda);
+      expect(assetFeed.priceFeed.toString()).to.equal(bonkMockFeedPda.toString());
+      expect(assetFeed.maxStaleness.toNumber()).to.equal(120);
+
+      try {
+        await program.methods
+          .updateAssetFeed(Array.from(bonkAssetId), bonkMockFeedPda, new BN(60))
+          .accounts({
+            assetFeed: bonkAssetFeedPda,
+            arena: arenaPda,
+            authority: player.publicKey,
+          })
+          .signers([player])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+    });
+  });
+
+  // ==========================================
+  // Test Suite 14: Reveal Deadlines & Forfeiture
+  // ==========================================
+  describe('Reveal Deadlines & Forfeiture', () => {
+    let forfeitSeasonPda: web3.PublicKey;
+    let forfeitEntryPda: web3.PublicKey;
+    let forfeitVaultPda: web3.PublicKey;
+    let lapsedPda: web3.PublicKey;
+    let lapsedVaultPda: web3.PublicKey;
+    let lapsedPayload: PredictionPayload;
+    let lapsedNonce: Buffer;
+
+    before(async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      [forfeitSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      [forfeitEntryPda] = deriveSeasonEntryPda(forfeitSeasonPda, agentPda, program.programId);
+      [forfeitVaultPda] = deriveSeasonVaultPda(forfeitSeasonPda, program.programId);
+
+      // One-second reveal window, forfeited stakes roll into the prize pool
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, new BN(1), { prizePool: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: forfeitSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: forfeitSeasonPda,
+          seasonEntry: forfeitEntryPda,
+          agent: agentPda,
+          arena: arenaPda,
+          player: player.publicKey,
+          seasonVault: forfeitVaultPda,
+          treasury: arena.treasury,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      const agent = await program.account.agent.fetch(agentPda);
+      [lapsedPda] = derivePredictionPda(
+        agentPda,
+        forfeitSeasonPda,
+        new BN(agent.predictionsSubmitted.toNumber()),
+        program.programId
+      );
+      [lapsedVaultPda] = derivePredictionVaultPda(lapsedPda, program.programId);
+
+      lapsedPayload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      lapsedNonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, forfeitSeasonPda, lapsedNonce, lapsedPayload);
+
+      await program.methods
+        .submitPrediction(Array.from(predictionHash), STAKE_AMOUNT)
+        .accounts({
+          season: forfeitSeasonPda,
+          agent: agentPda,
+          arena: arenaPda,
+          prediction: lapsedPda,
+          player: player.publicKey,
+          predictionVault: lapsedVaultPda,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+    });
+
+    const forfeit = async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const cranker = web3.Keypair.generate();
+      await program.methods
+        .forfeitPrediction()
+        .accounts({
+          prediction: lapsedPda,
+          agent: agentPda,
+          season: forfeitSeasonPda,
+          seasonEntry: forfeitEntryPda,
+          arena: arenaPda,
+          predictionVault: lapsedVaultPda,
+          seasonVault: forfeitVaultPda,
+          treasury: arena.treasury,
+          cranker: cranker.publicKey,
+        })
+        .signers([cranker])
+        .rpc();
+    };
+
+    it('Should fail to forfeit while the reveal window is open', async () => {
+      try {
+        await forfeit();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.RevealWindowOpen);
+      }
+    });
+
+    it('Should fail to reveal after the deadline', async () => {
+      await new Promise(resolve => setTimeout(resolve, 2500));
+
+      try {
+        await program.methods
+          .revealPrediction(lapsedPayload, Array.from(lapsedNonce))
+          .accounts({
+            prediction: lapsedPda,
+            agent: agentPda,
+            player: player.publicKey,
+          })
+          .signers([player])
+          .rpc();
+
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.RevealWindowClosed);
+      }
+    });
+
+    it('Should let anyone forfeit a lapsed prediction', async () => {
+      const agentBefore = await program.account.agent.fetch(agentPda);
+      const seasonBefore = await program.account.season.fetch(forfeitSeasonPda);
+
+      await forfeit();
+
+      const prediction = await program.account.prediction.fetch(lapsedPda);
+      const agent = await program.account.agent.fetch(agentPda);
+      const entry = await program.account.seasonEntry.fetch(forfeitEntryPda);
+      const season = await program.account.season.fetch(forfeitSeasonPda);
+
+      expect(prediction.status).to.deep.equal({ expired: {} });
+      expect(agent.totalPredictions.toNumber()).to.equal(agentBefore.totalPredictions.toNumber() + 1);
+      expect(agent.correctPredictions.toNumber()).to.equal(agentBefore.correctPredictions.toNumber());
+      expect(agent.streak).to.equal(0);
+      expect(entry.predictionsMade.toNumber()).to.equal(1);
+      expect(entry.predictionsCorrect.toNumber()).to.equal(0);
+      expect(season.totalPool.toNumber()).to.equal(
+        seasonBefore.totalPool.toNumber() + STAKE_AMOUNT.toNumber()
+      );
+    });
+
+    it('Should fail to forfeit twice', async () => {
+      try {
+        await forfeit();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidPredictionStatus);
+      }
+    });
+  });
+
+  // ==========================================
+  // Test Suite 15: Resolution Account Binding
+  // ==========================================
+  describe('Resolution Account Binding', () => {
+    let bindSeasonPda: web3.PublicKey;
+    let bindEntryPda: web3.PublicKey;
+    let bindEntry2Pda: web3.PublicKey;
+    let boundPda: web3.PublicKey;
+    let boundVaultPda: web3.PublicKey;
+
+    before(async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      [bindSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      [bindEntryPda] = deriveSeasonEntryPda(bindSeasonPda, agentPda, program.programId);
+      [bindEntry2Pda] = deriveSeasonEntryPda(bindSeasonPda, agent2Pda, program.programId);
+      const [bindVaultPda] = deriveSeasonVaultPda(bindSeasonPda, program.programId);
+
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: bindSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      for (const [agent, entry, owner] of [
+        [agentPda, bindEntryPda, player],
+        [agent2Pda, bindEntry2Pda, player2],
+      ] as [web3.PublicKey, web3.PublicKey, web3.Keypair][]) {
+        await program.methods
+          .enterSeason()
+          .accounts({
+            season: bindSeasonPda,
+            seasonEntry: entry,
+            agent,
+            arena: arenaPda,
+            player: owner.publicKey,
+            seasonVault: bindVaultPda,
+            treasury: arena.treasury,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+      }
+
+      const agent = await program.account.agent.fetch(agentPda);
+      [boundPda] = derivePredictionPda(
+        agentPda,
+        bindSeasonPda,
+        new BN(agent.predictionsSubmitted.toNumber()),
+        program.programId
+      );
+      [boundVaultPda] = derivePredictionVaultPda(boundPda, program.programId);
+
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, bindSeasonPda, nonce, payload);
+
+      await program.methods
+        .submitPrediction(Array.from(predictionHash), STAKE_AMOUNT)
+        .accounts({
+          season: bindSeasonPda,
+          agent: agentPda,
+          arena: arenaPda,
+          prediction: boundPda,
+          player: player.publicKey,
+          predictionVault: boundVaultPda,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      await program.methods
+        .revealPrediction(payload, Array.from(nonce))
+        .accounts({
+          prediction: boundPda,
+          agent: agentPda,
+          player: player.publicKey,
+        })
+        .signers([player])
+        .rpc();
+    });
+
+    const resolveWith = async (overrides: Record<string, web3.PublicKey>) => {
+      await program.methods
+        .resolvePrediction(true)
+        .accounts({
+          prediction: boundPda,
+          agent: agentPda,
+          seasonEntry: bindEntryPda,
+          resolver: authority.publicKey,
+          predictionVault: boundVaultPda,
+          player: player.publicKey,
+          ...overrides,
+        })
+        .signers([authority])
+        .rpc();
+    };
+
+    it('Should reject an agent that does not own the prediction', async () => {
+      try {
+        await resolveWith({ agent: agent2Pda, seasonEntry: bindEntry2Pda, player: player2.publicKey });
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.PredictionAgentMismatch);
+      }
+    });
+
+    it("Should reject another agent's season entry", async () => {
+      try {
+        await resolveWith({ seasonEntry: bindEntry2Pda });
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.EntryAgentMismatch);
+      }
+    });
+
+    it("Should reject the agent's entry from a different season", async () => {
+      try {
+        await resolveWith({ seasonEntry: seasonEntryPda });
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.EntrySeasonMismatch);
+      }
+    });
+
+    it('Should reject a stake recipient other than the agent owner', async () => {
+      try {
+        await resolveWith({ player: player3.publicKey });
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.PlayerNotAgentOwner);
+      }
+    });
+
+    it("Should fail to enter a season with someone else's agent", async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const [otherSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      const [hijackEntryPda] = deriveSeasonEntryPda(otherSeasonPda, agent2Pda, program.programId);
+      const [otherVaultPda] = deriveSeasonVaultPda(otherSeasonPda, program.programId);
+
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: otherSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      // Without this an entrant could diverge from the agent owner and strand its stakes
+      try {
+        await program.methods
+          .enterSeason()
+          .accounts({
+            season: otherSeasonPda,
+            seasonEntry: hijackEntryPda,
+            agent: agent2Pda,
+            arena: arenaPda,
+            player: player3.publicKey,
+            seasonVault: otherVaultPda,
+            treasury: arena.treasury,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([player3])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.UnauthorizedAgent);
+      }
+    });
+
+    it('Should resolve once every account matches the prediction', async () => {
+      const playerBalanceBefore = await provider.connection.getBalance(player.publicKey);
+
+      await resolveWith({});
+
+      const prediction = await program.account.prediction.fetch(boundPda);
+      const entry = await program.account.seasonEntry.fetch(bindEntryPda);
+      const playerBalanceAfter = await provider.connection.getBalance(player.publicKey);
+
+      expect(prediction.status).to.deep.equal({ resolved: {} });
+      expect(entry.predictionsCorrect.toNumber()).to.equal(1);
+      expect(playerBalanceAfter - playerBalanceBefore).to.equal(STAKE_AMOUNT.toNumber());
+    });
+  });
+
+  // ==========================================
+  // Test Suite 16: Season Leaderboard
+  // ==========================================
+  describe('Season Leaderboard', () => {
+    let boardSeasonPda: web3.PublicKey;
+    let boardPda: web3.PublicKey;
+    let boardEntryPda: web3.PublicKey;
+    let boardEntry2Pda: web3.PublicKey;
+
+    const submitRevealResolve = async (
+      agentKey: web3.PublicKey,
+      entryKey: web3.PublicKey,
+      owner: web3.Keypair,
+      wasCorrect: boolean
+    ) => {
+      const agent = await program.account.agent.fetch(agentKey);
+      const [predPda] = derivePredictionPda(
+        agentKey,
+        boardSeasonPda,
+        new BN(agent.predictionsSubmitted.toNumber()),
+        program.programId
+      );
+      const [vaultPda] = derivePredictionVaultPda(predPda, program.programId);
+
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentKey, boardSeasonPda, nonce, payload);
+
+      await program.methods
+        .submitPrediction(Array.from(predictionHash), STAKE_AMOUNT)
+        .accounts({
+          season: boardSeasonPda,
+          agent: agentKey,
+          arena: arenaPda,
+          prediction: predPda,
+          player: owner.publicKey,
+          predictionVault: vaultPda,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+
+      await program.methods
+        .revealPrediction(payload, Array.from(nonce))
+        .accounts({
+          prediction: predPda,
+          agent: agentKey,
+          player: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+
+      await program.methods
+        .resolvePrediction(wasCorrect)
+        .accounts({
+          prediction: predPda,
+          agent: agentKey,
+          seasonEntry: entryKey,
+          resolver: authority.publicKey,
+          leaderboard: boardPda,
+          predictionVault: vaultPda,
+          player: owner.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    };
+
+    before(async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      [boardSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      [boardPda] = deriveLeaderboardPda(boardSeasonPda, program.programId);
+      [boardEntryPda] = deriveSeasonEntryPda(boardSeasonPda, agentPda, program.programId);
+      [boardEntry2Pda] = deriveSeasonEntryPda(boardSeasonPda, agent2Pda, program.programId);
+      const [boardVaultPda] = deriveSeasonVaultPda(boardSeasonPda, program.programId);
+
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: boardSeasonPda,
+          leaderboard: boardPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      for (const [agent, entry, owner] of [
+        [agentPda, boardEntryPda, player],
+        [agent2Pda, boardEntry2Pda, player2],
+      ] as [web3.PublicKey, web3.PublicKey, web3.Keypair][]) {
+        await program.methods
+          .enterSeason()
+          .accounts({
+            season: boardSeasonPda,
+            seasonEntry: entry,
+            agent,
+            arena: arenaPda,
+            player: owner.publicKey,
+            seasonVault: boardVaultPda,
+            treasury: arena.treasury,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+      }
+    });
+
+    it('Should create an empty leaderboard with the season', async () => {
+      const board = await program.account.leaderboard.fetch(boardPda);
+      expect(board.season.toString()).to.equal(boardSeasonPda.toString());
+      expect(board.count).to.equal(0);
+    });
+
+    it('Should place entries as their predictions resolve', async () => {
+      await submitRevealResolve(agent2Pda, boardEntry2Pda, player2, false);
+      await submitRevealResolve(agentPda, boardEntryPda, player, true);
+
+      const board = await program.account.leaderboard.fetch(boardPda);
+      const entry = await program.account.seasonEntry.fetch(boardEntryPda);
+
+      expect(board.count).to.equal(2);
+      expect(board.entries[0].seasonEntry.toString()).to.equal(boardEntryPda.toString());
+      expect(board.entries[0].player.toString()).to.equal(player.publicKey.toString());
+      expect(board.entries[0].score.toNumber()).to.equal(entry.score.toNumber());
+      expect(board.entries[1].seasonEntry.toString()).to.equal(boardEntry2Pda.toString());
+      expect(board.entries[1].score.toNumber()).to.equal(0);
+      expect(entry.rank).to.equal(1);
+    });
+
+    it('Should sync a displaced entry rank from the leaderboard', async () => {
+      // agent2 was placed first on its own resolution, then pushed down
+      const stale = await program.account.seasonEntry.fetch(boardEntry2Pda);
+      expect(stale.rank).to.equal(1);
+
+      await program.methods
+        .syncEntryRank()
+        .accounts({
+          season: boardSeasonPda,
+          seasonEntry: boardEntry2Pda,
+          leaderboard: boardPda,
+        })
+        .rpc();
+
+      const entry = await program.account.seasonEntry.fetch(boardEntry2Pda);
+      expect(entry.rank).to.equal(2);
+    });
+  });
+
+  // ==========================================
+  // Test Suite 17: Season Cancellation & Refunds
+  // ==========================================
+  describe('Season Cancellation & Refunds', () => {
+    let cancelSeasonPda: web3.PublicKey;
+    let cancelEntryPda: web3.PublicKey;
+    let cancelVaultPda: web3.PublicKey;
+
+    const claimRefund = async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      await program.methods
+        .claimRefund()
+        .accounts({
+          season: cancelSeasonPda,
+          seasonEntry: cancelEntryPda,
+          arena: arenaPda,
+          seasonVault: cancelVaultPda,
+          treasury: arena.treasury,
+          player: player.publicKey,
+        })
+        .rpc();
+    };
+
+    before(async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      [cancelSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      [cancelEntryPda] = deriveSeasonEntryPda(cancelSeasonPda, agentPda, program.programId);
+      [cancelVaultPda] = deriveSeasonVaultPda(cancelSeasonPda, program.programId);
+
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: cancelSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: cancelSeasonPda,
+          seasonEntry: cancelEntryPda,
+          agent: agentPda,
+          arena: arenaPda,
+          player: player.publicKey,
+          seasonVault: cancelVaultPda,
+          treasury: arena.treasury,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+    });
+
+    it('Should fail to claim a refund from an active season', async () => {
+      try {
+        await claimRefund();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.SeasonNotCancelled);
+      }
+    });
+
+    it('Should fail to cancel a season as non-authority', async () => {
+      try {
+        await program.methods
+          .cancelSeason(true)
+          .accounts({
+            season: cancelSeasonPda,
+            arena: arenaPda,
+            seasonOperator: player.publicKey,
+          })
+          .signers([player])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+    });
+
+    it('Should cancel a season', async () => {
+      const arenaBefore = await program.account.arena.fetch(arenaPda);
+
+      await program.methods
+        .cancelSeason(true)
+        .accounts({
+          season: cancelSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const season = await program.account.season.fetch(cancelSeasonPda);
+      const entry = await program.account.seasonEntry.fetch(cancelEntryPda);
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(season.status).to.deep.equal({ cancelled: {} });
+      expect(season.refundPlatformFee).to.equal(true);
+      expect(season.totalPlatformFees.toNumber()).to.equal(entry.platformFee.toNumber());
+      expect(arena.refundsOwed.sub(arenaBefore.refundsOwed).toNumber()).to.equal(
+        season.totalPlatformFees.toNumber()
+      );
+    });
+
+    it('Should keep owed refunds out of treasury withdrawals', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const balance = await provider.connection.getBalance(arena.treasury);
+      const info = await provider.connection.getAccountInfo(arena.treasury);
+      const rentFloor = await provider.connection.getMinimumBalanceForRentExemption(info.data.length);
+      const withdrawable = balance - rentFloor - arena.refundsOwed.toNumber();
+
+      try {
+        await program.methods
+          .withdrawTreasury(new BN(withdrawable + 1))
+          .accounts({
+            arena: arenaPda,
+            treasury: arena.treasury,
+            treasurer: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InsufficientFunds);
+      }
+    });
+
+    it('Should refund the full entry fee to the entrant', async () => {
+      const entryBefore = await program.account.seasonEntry.fetch(cancelEntryPda);
+      const arenaBefore = await program.account.arena.fetch(arenaPda);
+      const playerBalanceBefore = await provider.connection.getBalance(player.publicKey);
+
+      await claimRefund();
+
+      const entry = await program.account.seasonEntry.fetch(cancelEntryPda);
+      const season = await program.account.season.fetch(cancelSeasonPda);
+      const playerBalanceAfter = await provider.connection.getBalance(player.publicKey);
+
+      expect(entry.refunded).to.equal(true);
+      expect(season.totalPool.toNumber()).to.equal(0);
+      expect(entryBefore.prizeContribution.add(entryBefore.platformFee).toNumber()).to.equal(
+        ENTRY_FEE.toNumber()
+      );
+      expect(playerBalanceAfter - playerBalanceBefore).to.equal(ENTRY_FEE.toNumber());
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arenaBefore.refundsOwed.sub(arena.refundsOwed).toNumber()).to.equal(
+        entryBefore.platformFee.toNumber()
+      );
+    });
+
+    it('Should fail to claim a refund twice', async () => {
+      try {
+        await claimRefund();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.AlreadyRefunded);
+      }
+    });
+
+    it('Should never distribute a cancelled season', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      try {
+        await program.methods
+          .distributePrizes()
+          .accounts({
+            season: cancelSeasonPda,
+            arena: arenaPda,
+            seasonOperator: authority.publicKey,
+            seasonVault: cancelVaultPda,
+            treasury: arena.treasury,
+          })
+          .signers([authority])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.SeasonCancelled);
+      }
+    });
+  });
+
+  // ==========================================
+  // Test Suite 18: Treasury Vault
+  // ==========================================
+  describe('Treasury Vault', () => {
+    let treasuryPda: web3.PublicKey;
+
+    const withdraw = async (amount: BN, signer: web3.Keypair = authority) => {
+      await program.methods
+        .withdrawTreasury(amount)
+        .accounts({
+          arena: arenaPda,
+          treasury: treasuryPda,
+          treasurer: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+    };
+
+    const rentFloor = async () => {
+      const info = await provider.connection.getAccountInfo(treasuryPda);
+      return provider.connection.getMinimumBalanceForRentExemption(info.data.length);
+    };
+
+    before(() => {
+      [treasuryPda] = deriveTreasuryPda(program.programId);
+    });
+
+    it('Should reconcile arena accounting with the treasury balance', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const balance = await provider.connection.getBalance(treasuryPda);
+      const retained = arena.totalFeesCollected
+        .sub(arena.totalWithdrawn)
+        .sub(arena.totalRefunded)
+        .toNumber();
+
+      expect(arena.totalFeesCollected.toNumber()).to.be.greaterThan(0);
+      expect(balance).to.be.at.least((await rentFloor()) + retained);
+    });
+
+    it('Should fail to withdraw as non-authority', async () => {
+      try {
+        await withdraw(new BN(1), player);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+    });
+
+    it('Should withdraw fees and record them on the arena', async () => {
+      const arenaBefore = await program.account.arena.fetch(arenaPda);
+      const amount = new BN(1000);
+
+      await withdraw(amount);
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arena.totalWithdrawn.toNumber()).to.equal(
+        arenaBefore.totalWithdrawn.toNumber() + amount.toNumber()
+      );
+    });
+
+    it('Should keep the treasury rent-exempt', async () => {
+      const balance = await provider.connection.getBalance(treasuryPda);
+      const available = balance - (await rentFloor());
+
+      try {
+        await withdraw(new BN(available + 1));
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InsufficientFunds);
+      }
+
+      await withdraw(new BN(available));
+      expect(await provider.connection.getBalance(treasuryPda)).to.equal(await rentFloor());
+    });
+  });
+
+  // ==========================================
+  // Test Suite 19: SPL Token Seasons
+  // ==========================================
+  describe('SPL Token Seasons', () => {
+    const TOKEN_ENTRY_FEE = new BN(TEST_CONSTANTS.TOKEN_ENTRY_FEE);
+    const TOKEN_STAKE = new BN(TEST_CONSTANTS.TOKEN_STAKE_AMOUNT);
+
+    let mint: web3.PublicKey;
+    let tokenSeasonPda: web3.PublicKey;
+    let tokenEntryPda: web3.PublicKey;
+    let seasonTokenVault: web3.PublicKey;
+    let treasuryTokenVault: web3.PublicKey;
+    let treasuryTokenLedger: web3.PublicKey;
+    let playerTokenAccount: web3.PublicKey;
+    let authorityTokenAccount: web3.PublicKey;
+
+    const tokenBalance = async (account: web3.PublicKey) =>
+      Number((await getAccount(provider.connection, account)).amount);
+
+    const stakeAndResolve = async (season: web3.PublicKey, seasonEntry: web3.PublicKey, wasCorrect: boolean) => {
+      const agent = await program.account.agent.fetch(agentPda);
+      const [predPda] = derivePredictionPda(agentPda, season, agent.predictionsSubmitted, program.programId);
+      const [predictionVault] = derivePredictionVaultPda(predPda, program.programId);
+      const [predictionTokenVault] = derivePredictionTokenVaultPda(predPda, program.programId);
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      const nonce = generateNonce();
+
+      await program.methods
+        .submitPrediction(Array.from(generatePredictionHash(agentPda, season, nonce, payload)), TOKEN_STAKE)
+        .accounts({
+          season,
+          agent: agentPda,
+          prediction: predPda,
+          player: player.publicKey,
+          predictionVault,
+          arena: arenaPda,
+          mint,
+          playerTokenAccount,
+          predictionTokenVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      await program.methods
+        .revealPrediction(payload, Array.from(nonce))
+        .accounts({ prediction: predPda, agent: agentPda, player: player.publicKey })
+        .signers([player])
+        .rpc();
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      await program.methods
+        .resolvePrediction(wasCorrect)
+        .accounts({
+          prediction: predPda,
+          agent: agentPda,
+          seasonEntry,
+          arena: arenaPda,
+          resolver: authority.publicKey,
+          leaderboard: deriveLeaderboardPda(season, program.programId)[0],
+          predictionVault,
+          treasury: arena.treasury,
+          player: player.publicKey,
+          mint,
+          predictionTokenVault,
+          treasuryTokenVault,
+          playerTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+
+      return predictionTokenVault;
+    };
+
+    before(async () => {
+      mint = await createMint(
+        provider.connection,
+        authority,
+        authority.publicKey,
+        null,
+        TEST_CONSTANTS.TOKEN_DECIMALS
+      );
+      playerTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, player, mint, player.publicKey)
+      ).address;
+      authorityTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, authority, mint, authority.publicKey)
+      ).address;
+      await mintTo(provider.connection, authority, mint, playerTokenAccount, authority, 100_000_000);
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      [tokenSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      [tokenEntryPda] = deriveSeasonEntryPda(tokenSeasonPda, agentPda, program.programId);
+      [seasonTokenVault] = deriveSeasonTokenVaultPda(tokenSeasonPda, program.programId);
+      [treasuryTokenVault] = deriveTreasuryTokenVaultPda(mint, program.programId);
+      [treasuryTokenLedger] = deriveTreasuryTokenLedgerPda(mint, program.programId);
+    });
+
+    it('Should create the treasury token vault for a mint', async () => {
+      await program.methods
+        .initTreasuryTokenVault()
+        .accounts({
+          arena: arenaPda,
+          mint,
+          treasuryTokenVault,
+          treasuryTokenLedger: deriveTreasuryTokenLedgerPda(mint, program.programId)[0],
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const vault = await getAccount(provider.connection, treasuryTokenVault);
+      expect(vault.mint.toString()).to.equal(mint.toString());
+      expect(vault.owner.toString()).to.equal(arenaPda.toString());
+
+      const ledger = await program.account.treasuryTokenLedger.fetch(treasuryTokenLedger);
+      expect(ledger.mint.toString()).to.equal(mint.toString());
+      expect(ledger.refundsOwed.toNumber()).to.equal(0);
+    });
+
+    it('Should create a season in the mint with its token vault', async () => {
+      await program.methods
+        .createSeason(TOKEN_ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: tokenSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          mint,
+          seasonTokenVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const season = await program.account.season.fetch(tokenSeasonPda);
+      const vault = await getAccount(provider.connection, seasonTokenVault);
+      expect(season.mint.toString()).to.equal(mint.toString());
+      expect(vault.owner.toString()).to.equal(arenaPda.toString());
+    });
+
+    it('Should pay the entry fee in the season mint', async () => {
+      const arenaBefore = await program.account.arena.fetch(arenaPda);
+      const playerBefore = await tokenBalance(playerTokenAccount);
+      const treasuryBefore = await tokenBalance(treasuryTokenVault);
+
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: tokenSeasonPda,
+          seasonEntry: tokenEntryPda,
+          agent: agentPda,
+          arena: arenaPda,
+          player: player.publicKey,
+          seasonVault: deriveSeasonVaultPda(tokenSeasonPda, program.programId)[0],
+          treasury: arenaBefore.treasury,
+          mint,
+          playerTokenAccount,
+          seasonTokenVault,
+          treasuryTokenVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      const entry = await program.account.seasonEntry.fetch(tokenEntryPda);
+      const season = await program.account.season.fetch(tokenSeasonPda);
+      const arena = await program.account.arena.fetch(arenaPda);
+
+      expect(playerBefore - (await tokenBalance(playerTokenAccount))).to.equal(TOKEN_ENTRY_FEE.toNumber());
+      expect(await tokenBalance(seasonTokenVault)).to.equal(entry.prizeContribution.toNumber());
+      expect((await tokenBalance(treasuryTokenVault)) - treasuryBefore).to.equal(entry.platformFee.toNumber());
+      expect(season.totalPool.toNumber()).to.equal(entry.prizeContribution.toNumber());
+      // Lamport fee accounting is untouched by token fees
+      expect(arena.totalFeesCollected.toNumber()).to.equal(arenaBefore.totalFeesCollected.toNumber());
+    });
+
+    it('Should reject a player token account in another mint', async () => {
+      const otherMint = await createMint(provider.connection, authority, authority.publicKey, null, 6);
+      const otherAccount = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, player, otherMint, player.publicKey)
+      ).address;
+      const agent = await program.account.agent.fetch(agentPda);
+      const [predPda] = derivePredictionPda(agentPda, tokenSeasonPda, agent.predictionsSubmitted, program.programId);
+
+      try {
+        await program.methods
+          .submitPrediction(Array.from(generateNonce()), TOKEN_STAKE)
+          .accounts({
+            season: tokenSeasonPda,
+            agent: agentPda,
+            prediction: predPda,
+            player: player.publicKey,
+            predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+            arena: arenaPda,
+            mint,
+            playerTokenAccount: otherAccount,
+            predictionTokenVault: derivePredictionTokenVaultPda(predPda, program.programId)[0],
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([player])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('ConstraintTokenMint');
+      }
+    });
+
+    it('Should stake and return a correct prediction in the season mint', async () => {
+      const agent = await program.account.agent.fetch(agentPda);
+      const [predPda] = derivePredictionPda(agentPda, tokenSeasonPda, agent.predictionsSubmitted, program.programId);
+      const [predictionVault] = derivePredictionVaultPda(predPda, program.programId);
+      const [predictionTokenVault] = derivePredictionTokenVaultPda(predPda, program.programId);
+
+      const payload = generatePredictionPayload('BTC', 'up', 50000, 8500);
+      const nonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, tokenSeasonPda, nonce, payload);
+
+      await program.methods
+        .submitPrediction(Array.from(predictionHash), TOKEN_STAKE)
+        .accounts({
+          season: tokenSeasonPda,
+          agent: agentPda,
+          prediction: predPda,
+          player: player.publicKey,
+          predictionVault,
+          arena: arenaPda,
+          mint,
+          playerTokenAccount,
+          predictionTokenVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      expect(await tokenBalance(predictionTokenVault)).to.equal(TOKEN_STAKE.toNumber());
+      const prediction = await program.account.prediction.fetch(predPda);
+      expect(prediction.mint.toString()).to.equal(mint.toString());
+
+      await program.methods
+        .revealPrediction(payload, Array.from(nonce))
+        .accounts({
+          prediction: predPda,
+          agent: agentPda,
+          player: player.publicKey,
+        })
+        .signers([player])
+        .rpc();
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      const playerBefore = await tokenBalance(playerTokenAccount);
+
+      await program.methods
+        .resolvePrediction(true)
+        .accounts({
+          prediction: predPda,
+          agent: agentPda,
+          seasonEntry: tokenEntryPda,
+          arena: arenaPda,
+          resolver: authority.publicKey,
+          leaderboard: deriveLeaderboardPda(tokenSeasonPda, program.programId)[0],
+          predictionVault,
+          treasury: arena.treasury,
+          player: player.publicKey,
+          mint,
+          predictionTokenVault,
+          treasuryTokenVault,
+          playerTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+
+      expect(await tokenBalance(predictionTokenVault)).to.equal(0);
+      expect((await tokenBalance(playerTokenAccount)) - playerBefore).to.equal(TOKEN_STAKE.toNumber());
+    });
+
+    it('Should move an incorrect stake to the treasury token vault', async () => {
+      const playerBefore = await tokenBalance(playerTokenAccount);
+      const treasuryBefore = await tokenBalance(treasuryTokenVault);
+
+      const predictionTokenVault = await stakeAndResolve(tokenSeasonPda, tokenEntryPda, false);
+
+      expect(await tokenBalance(predictionTokenVault)).to.equal(0);
+      expect((await tokenBalance(treasuryTokenVault)) - treasuryBefore).to.equal(TOKEN_STAKE.toNumber());
+      expect(playerBefore - (await tokenBalance(playerTokenAccount))).to.equal(TOKEN_STAKE.toNumber());
+    });
+
+    it('Should withdraw token fees from the treasury token vault', async () => {
+      const arenaBefore = await program.account.arena.fetch(arenaPda);
+      const ledgerBefore = await program.account.treasuryTokenLedger.fetch(treasuryTokenLedger);
+      const available = await tokenBalance(treasuryTokenVault);
+      const authorityBefore = await tokenBalance(authorityTokenAccount);
+
+      await program.methods
+        .withdrawTreasury(new BN(available))
+        .accounts({
+          arena: arenaPda,
+          treasury: arenaBefore.treasury,
+          treasurer: authority.publicKey,
+          mint,
+          treasuryTokenVault,
+          treasuryTokenLedger,
+          destinationTokenAccount: authorityTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(await tokenBalance(treasuryTokenVault)).to.equal(0);
+      expect((await tokenBalance(authorityTokenAccount)) - authorityBefore).to.equal(available);
+      expect(arena.totalWithdrawn.toNumber()).to.equal(arenaBefore.totalWithdrawn.toNumber());
+
+      const ledger = await program.account.treasuryTokenLedger.fetch(treasuryTokenLedger);
+      expect(ledger.totalWithdrawn.toNumber()).to.equal(ledgerBefore.totalWithdrawn.toNumber() + available);
+    });
+
+    it('Should refund a cancelled token season in the season mint', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      await program.methods
+        .cancelSeason(false)
+        .accounts({
+          season: tokenSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const entry = await program.account.seasonEntry.fetch(tokenEntryPda);
+      const playerBefore = await tokenBalance(playerTokenAccount);
+
+      await program.methods
+        .claimRefund()
+        .accounts({
+          season: tokenSeasonPda,
+          seasonEntry: tokenEntryPda,
+          arena: arenaPda,
+          seasonVault: deriveSeasonVaultPda(tokenSeasonPda, program.programId)[0],
+          treasury: arena.treasury,
+          player: player.publicKey,
+          mint,
+          seasonTokenVault,
+          treasuryTokenVault,
+          playerTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      expect((await tokenBalance(playerTokenAccount)) - playerBefore).to.equal(
+        entry.prizeContribution.toNumber()
+      );
+      expect(await tokenBalance(seasonTokenVault)).to.equal(0);
+    });
+
+    it("Should pay SPL prizes only to the placed players' token accounts", async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const [payoutSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      const [payoutEntryPda] = deriveSeasonEntryPda(payoutSeasonPda, agentPda, program.programId);
+      const [payoutTokenVault] = deriveSeasonTokenVaultPda(payoutSeasonPda, program.programId);
+
+      await program.methods
+        .createSeason(TOKEN_ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: payoutSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          mint,
+          seasonTokenVault: payoutTokenVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: payoutSeasonPda,
+          seasonEntry: payoutEntryPda,
+          agent: agentPda,
+          arena: arenaPda,
+          player: player.publicKey,
+          seasonVault: deriveSeasonVaultPda(payoutSeasonPda, program.programId)[0],
+          treasury: arena.treasury,
+          mint,
+          playerTokenAccount,
+          seasonTokenVault: payoutTokenVault,
+          treasuryTokenVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      // A correct call places the agent first on the season leaderboard
+      await stakeAndResolve(payoutSeasonPda, payoutEntryPda, true);
+
+      await program.methods
+        .setMockSeasonEnd(new BN(0))
+        .accounts({ season: payoutSeasonPda, arena: arenaPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      const distribute = (recipient: web3.PublicKey) =>
+        program.methods
+          .distributePrizes()
+          .accounts({
+            season: payoutSeasonPda,
+            arena: arenaPda,
+            seasonOperator: authority.publicKey,
+            seasonVault: deriveSeasonVaultPda(payoutSeasonPda, program.programId)[0],
+            leaderboard: deriveLeaderboardPda(payoutSeasonPda, program.programId)[0],
+            treasury: arena.treasury,
+            mint,
+            seasonTokenVault: payoutTokenVault,
+            treasuryTokenVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts([{ pubkey: recipient, isWritable: true, isSigner: false }])
+          .signers([authority])
+          .rpc();
+
+      // A token account in the right mint, but not owned by the placed player
+      try {
+        await distribute(authorityTokenAccount);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.PrizeRecipientMismatch);
+      }
+
+      const season = await program.account.season.fetch(payoutSeasonPda);
+      const playerBefore = await tokenBalance(playerTokenAccount);
+      await distribute(playerTokenAccount);
+
+      // A single placed winner takes the whole redistributed pool
+      expect((await tokenBalance(playerTokenAccount)) - playerBefore).to.equal(season.totalPool.toNumber());
+      expect(await tokenBalance(payoutTokenVault)).to.equal(0);
+      const completed = await program.account.season.fetch(payoutSeasonPda);
+      expect(completed.status).to.deep.equal({ completed: {} });
+    });
+
+    it('Should hold back refundable token fees from treasury withdrawals', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const [refundSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      const [refundEntryPda] = deriveSeasonEntryPda(refundSeasonPda, agentPda, program.programId);
+      const [refundTokenVault] = deriveSeasonTokenVaultPda(refundSeasonPda, program.programId);
+
+      await program.methods
+        .createSeason(TOKEN_ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: refundSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          mint,
+          seasonTokenVault: refundTokenVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: refundSeasonPda,
+          seasonEntry: refundEntryPda,
+          agent: agentPda,
+          arena: arenaPda,
+          player: player.publicKey,
+          seasonVault: deriveSeasonVaultPda(refundSeasonPda, program.programId)[0],
+          treasury: arena.treasury,
+          mint,
+          playerTokenAccount,
+          seasonTokenVault: refundTokenVault,
+          treasuryTokenVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      await program.methods
+        .cancelSeason(true)
+        .accounts({
+          season: refundSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          treasuryTokenLedger,
+        })
+        .signers([authority])
+        .rpc();
+
+      const entry = await program.account.seasonEntry.fetch(refundEntryPda);
+      const reserved = await program.account.treasuryTokenLedger.fetch(treasuryTokenLedger);
+      expect(reserved.refundsOwed.toNumber()).to.equal(entry.platformFee.toNumber());
+
+      const withdraw = (amount: number) =>
+        program.methods
+          .withdrawTreasury(new BN(amount))
+          .accounts({
+            arena: arenaPda,
+            treasury: arena.treasury,
+            treasurer: authority.publicKey,
+            mint,
+            treasuryTokenVault,
+            treasuryTokenLedger,
+            destinationTokenAccount: authorityTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([authority])
+          .rpc();
+
+      const available = await tokenBalance(treasuryTokenVault);
+      try {
+        await withdraw(available);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InsufficientFunds);
+      }
+      await withdraw(available - entry.platformFee.toNumber());
+      expect(await tokenBalance(treasuryTokenVault)).to.equal(entry.platformFee.toNumber());
+
+      const playerBefore = await tokenBalance(playerTokenAccount);
+      await program.methods
+        .claimRefund()
+        .accounts({
+          season: refundSeasonPda,
+          seasonEntry: refundEntryPda,
+          arena: arenaPda,
+          seasonVault: deriveSeasonVaultPda(refundSeasonPda, program.programId)[0],
+          treasury: arena.treasury,
+          player: player.publicKey,
+          mint,
+          seasonTokenVault: refundTokenVault,
+          treasuryTokenVault,
+          treasuryTokenLedger,
+          playerTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      expect((await tokenBalance(playerTokenAccount)) - playerBefore).to.equal(
+        entry.prizeContribution.toNumber() + entry.platformFee.toNumber()
+      );
+      const ledger = await program.account.treasuryTokenLedger.fetch(treasuryTokenLedger);
+      expect(ledger.refundsOwed.toNumber()).to.equal(0);
+      expect(ledger.totalRefunded.toNumber()).to.equal(entry.platformFee.toNumber());
+    });
+  });
+
+  // ==========================================
+  // Test Suite 20: Token-2022 Seasons
+  // ==========================================
+  describe('Token-2022 Seasons', () => {
+    const TOKEN_ENTRY_FEE = new BN(TEST_CONSTANTS.TOKEN_ENTRY_FEE);
+    const TOKEN_STAKE = new BN(TEST_CONSTANTS.TOKEN_STAKE_AMOUNT);
+    const FEE_BPS = TEST_CONSTANTS.TRANSFER_FEE_BPS;
+
+    let mint: web3.PublicKey;
+    let feeSeasonPda: web3.PublicKey;
+    let feeEntryPda: web3.PublicKey;
+    let seasonTokenVault: web3.PublicKey;
+    let treasuryTokenVault: web3.PublicKey;
+    let playerTokenAccount: web3.PublicKey;
+    let feePredictionPda: web3.PublicKey;
+    let feePayload: PredictionPayload;
+    let feeNonce: Buffer;
+
+    const tokenBalance = async (account: web3.PublicKey) =>
+      Number((await getAccount(provider.connection, account, undefined, TOKEN_2022_PROGRAM_ID)).amount);
+
+    before(async () => {
+      // Transfer-fee mint: every transfer withholds FEE_BPS of the amount sent
+      const mintKeypair = web3.Keypair.generate();
+      mint = mintKeypair.publicKey;
+      const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+      const tx = new web3.Transaction().add(
+        web3.SystemProgram.createAccount({
+          fromPubkey: authority.publicKey,
+          newAccountPubkey: mint,
+          space: mintLen,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          mint,
+          authority.publicKey,
+          authority.publicKey,
+          FEE_BPS,
+          BigInt(1_000_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+          mint,
+          TEST_CONSTANTS.TOKEN_DECIMALS,
+          authority.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        )
+      );
+      await web3.sendAndConfirmTransaction(provider.connection, tx, [authority, mintKeypair]);
+
+      playerTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          player,
+          mint,
+          player.publicKey,
+          false,
+          undefined,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).address;
+      await mintTo(
+        provider.connection,
+        authority,
+        mint,
+        playerTokenAccount,
+        authority,
+        100_000_000,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      [feeSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      [feeEntryPda] = deriveSeasonEntryPda(feeSeasonPda, agentPda, program.programId);
+      [seasonTokenVault] = deriveSeasonTokenVaultPda(feeSeasonPda, program.programId);
+      [treasuryTokenVault] = deriveTreasuryTokenVaultPda(mint, program.programId);
+
+      await program.methods
+        .initTreasuryTokenVault()
+        .accounts({
+          arena: arenaPda,
+          mint,
+          treasuryTokenVault,
+          treasuryTokenLedger: deriveTreasuryTokenLedgerPda(mint, program.programId)[0],
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .createSeason(TOKEN_ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: feeSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          mint,
+          seasonTokenVault,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
+    it('Should record the entry fee shares the vaults actually received', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: feeSeasonPda,
+          seasonEntry: feeEntryPda,
+          agent: agentPda,
+          arena: arenaPda,
+          player: player.publicKey,
+          seasonVault: deriveSeasonVaultPda(feeSeasonPda, program.programId)[0],
+          treasury: arena.treasury,
+          mint,
+          playerTokenAccount,
+          seasonTokenVault,
+          treasuryTokenVault,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      const entry = await program.account.seasonEntry.fetch(feeEntryPda);
+      const season = await program.account.season.fetch(feeSeasonPda);
+      const nominalContribution = (TOKEN_ENTRY_FEE.toNumber() * PRIZE_POOL_BPS) / 10000;
+
+      // Token-2022 withholds the fee on the receiving account, outside its spendable amount
+      expect(entry.prizeContribution.toNumber()).to.equal(
+        nominalContribution - (nominalContribution * FEE_BPS) / 10000
+      );
+      expect(entry.prizeContribution.toNumber()).to.be.lessThan(nominalContribution);
+      expect(entry.platformFee.toNumber()).to.be.lessThan(TOKEN_ENTRY_FEE.toNumber() - nominalContribution);
+      expect(season.totalPool.toNumber()).to.equal(entry.prizeContribution.toNumber());
+      expect(await tokenBalance(seasonTokenVault)).to.equal(entry.prizeContribution.toNumber());
+    });
+
+    it('Should record the stake the prediction vault actually received', async () => {
+      const agent = await program.account.agent.fetch(agentPda);
+      const [predPda] = derivePredictionPda(agentPda, feeSeasonPda, agent.predictionsSubmitted, program.programId);
+      const [predictionTokenVault] = derivePredictionTokenVaultPda(predPda, program.programId);
+
+      feePredictionPda = predPda;
+      feePayload = generatePredictionPayload('ETH', 'down', 3000, 6000);
+      feeNonce = generateNonce();
+      const predictionHash = generatePredictionHash(agentPda, feeSeasonPda, feeNonce, feePayload);
+
+      await program.methods
+        .submitPrediction(Array.from(predictionHash), TOKEN_STAKE)
+        .accounts({
+          season: feeSeasonPda,
+          agent: agentPda,
+          prediction: predPda,
+          player: player.publicKey,
+          predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+          arena: arenaPda,
+          mint,
+          playerTokenAccount,
+          predictionTokenVault,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      const prediction = await program.account.prediction.fetch(predPda);
+      expect(prediction.stakeAmount.toNumber()).to.equal(
+        TOKEN_STAKE.toNumber() - (TOKEN_STAKE.toNumber() * FEE_BPS) / 10000
+      );
+      expect(await tokenBalance(predictionTokenVault)).to.equal(prediction.stakeAmount.toNumber());
+    });
+
+    it('Should harvest withheld fees to the mint when closing the prediction vault', async () => {
+      const [predictionVault] = derivePredictionVaultPda(feePredictionPda, program.programId);
+      const [predictionTokenVault] = derivePredictionTokenVaultPda(feePredictionPda, program.programId);
+      const arena = await program.account.arena.fetch(arenaPda);
+
+      await program.methods
+        .revealPrediction(feePayload, Array.from(feeNonce))
+        .accounts({
+          prediction: feePredictionPda,
+          agent: agentPda,
+          player: player.publicKey,
+        })
+        .signers([player])
+        .rpc();
+
+      await program.methods
+        .resolvePrediction(true)
+        .accounts({
+          prediction: feePredictionPda,
+          agent: agentPda,
+          seasonEntry: feeEntryPda,
+          arena: arenaPda,
+          resolver: authority.publicKey,
+          leaderboard: deriveLeaderboardPda(feeSeasonPda, program.programId)[0],
+          predictionVault,
+          treasury: arena.treasury,
+          player: player.publicKey,
+          mint,
+          predictionTokenVault,
+          treasuryTokenVault,
+          playerTokenAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+
+      // The stake left the vault, but the fee withheld when it arrived is still there
+      const vault = await getAccount(provider.connection, predictionTokenVault, undefined, TOKEN_2022_PROGRAM_ID);
+      const withheld = Number(getTransferFeeAmount(vault).withheldAmount);
+      expect(Number(vault.amount)).to.equal(0);
+      expect(withheld).to.be.greaterThan(0);
+
+      const mintWithheld = async () =>
+        Number(
+          getTransferFeeConfig(await getMint(provider.connection, mint, undefined, TOKEN_2022_PROGRAM_ID))
+            .withheldAmount
+        );
+      const mintBefore = await mintWithheld();
+
+      await program.methods
+        .closePrediction()
+        .accounts({
+          agent: agentPda,
+          prediction: feePredictionPda,
+          predictionVault,
+          rentPayer: player.publicKey,
+          owner: player.publicKey,
+          arena: arenaPda,
+          predictionTokenVault,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([player])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(predictionTokenVault)).to.be.null;
+      expect(await mintWithheld()).to.equal(mintBefore + withheld);
+    });
+  });
+
+  // ==========================================
+  // Test Suite 21: Authority Transfer
+  // ==========================================
+  describe('Authority Transfer', () => {
+    const newAuthority = web3.Keypair.generate();
+
+    const propose = async (key: web3.PublicKey, signer: web3.Keypair = authority) => {
+      await program.methods
+        .proposeAuthority(key)
+        .accounts({ arena: arenaPda, authority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    };
+
+    const accept = async (signer: web3.Keypair) => {
+      await program.methods
+        .acceptAuthority()
+        .accounts({ arena: arenaPda, pendingAuthority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    };
+
+    before(async () => {
+      await airdrop(provider, newAuthority);
+    });
+
+    it('Should fail to propose as non-authority', async () => {
+      try {
+        await propose(player.publicKey, player);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+    });
+
+    it('Should fail to cancel with no pending transfer', async () => {
+      try {
+        await program.methods
+          .cancelAuthorityTransfer()
+          .accounts({ arena: arenaPda, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.NoPendingAuthority);
+      }
+    });
+
+    it('Should record a proposed authority without transferring', async () => {
+      await propose(newAuthority.publicKey);
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arena.pendingAuthority.toString()).to.equal(newAuthority.publicKey.toString());
+      expect(arena.authority.toString()).to.equal(authority.publicKey.toString());
+    });
+
+    it('Should only let the pending authority accept', async () => {
+      try {
+        await accept(player);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.NotPendingAuthority);
+      }
+    });
+
+    it('Should cancel a pending transfer', async () => {
+      await program.methods
+        .cancelAuthorityTransfer()
+        .accounts({ arena: arenaPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arena.pendingAuthority.toString()).to.equal(web3.PublicKey.default.toString());
+
+      try {
+        await accept(newAuthority);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.NotPendingAuthority);
+      }
+    });
+
+    it('Should hand every admin check to the accepted authority', async () => {
+      await propose(newAuthority.publicKey);
+      await accept(newAuthority);
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arena.authority.toString()).to.equal(newAuthority.publicKey.toString());
+      expect(arena.pendingAuthority.toString()).to.equal(web3.PublicKey.default.toString());
+
+      // The previous authority is locked out of admin instructions
+      try {
+        await propose(authority.publicKey, authority);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+
+      // Hand the arena back for the remaining suites
+      await propose(authority.publicKey, newAuthority);
+      await accept(authority);
+    });
+  });
+
+  // ==========================================
+  // Test Suite 22: Arena Roles
+  // ==========================================
+  describe('Arena Roles', () => {
+    const resolverBot = web3.Keypair.generate();
+    const treasurer = web3.Keypair.generate();
+
+    const setRole = async (role: object, key: web3.PublicKey, signer: web3.Keypair = authority) => {
+      await program.methods
+        .setRole(role as any, key)
+        .accounts({ arena: arenaPda, authority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    };
+
+    const withdraw = async (signer: web3.Keypair) => {
+      await program.methods
+        .withdrawTreasury(new BN(0))
+        .accounts({
+          arena: arenaPda,
+          treasury: deriveTreasuryPda(program.programId)[0],
+          treasurer: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+    };
+
+    before(async () => {
+      await airdrop(provider, resolverBot);
+      await airdrop(provider, treasurer);
+    });
+
+    it('Should default every role to the authority', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      for (const key of [arena.resolver, arena.seasonOperator, arena.achievementIssuer, arena.treasurer]) {
+        expect(key.toString()).to.equal(authority.publicKey.toString());
+      }
+    });
+
+    it('Should fail to set a role as non-authority', async () => {
+      try {
+        await setRole({ treasurer: {} }, player.publicKey, player);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+    });
+
+    it('Should assign separate resolver and treasurer keys', async () => {
+      await setRole({ resolver: {} }, resolverBot.publicKey);
+      await setRole({ treasurer: {} }, treasurer.publicKey);
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arena.resolver.toString()).to.equal(resolverBot.publicKey.toString());
+      expect(arena.treasurer.toString()).to.equal(treasurer.publicKey.toString());
+      expect(arena.seasonOperator.toString()).to.equal(authority.publicKey.toString());
+    });
+
+    it('Should not let the resolver withdraw the treasury', async () => {
+      try {
+        await withdraw(resolverBot);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+    });
+
+    it('Should not let the resolver create seasons', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const [nextSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      try {
+        await program.methods
+          .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+          .accounts({
+            season: nextSeasonPda,
+            arena: arenaPda,
+            seasonOperator: resolverBot.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([resolverBot])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+    });
+
+    it('Should move withdrawals from the authority to the treasurer', async () => {
+      try {
+        await withdraw(authority);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+
+      await withdraw(treasurer);
+    });
+
+    it('Should hand roles back to the authority', async () => {
+      await setRole({ resolver: {} }, authority.publicKey);
+      await setRole({ treasurer: {} }, authority.publicKey);
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arena.resolver.toString()).to.equal(authority.publicKey.toString());
+      expect(arena.treasurer.toString()).to.equal(authority.publicKey.toString());
+    });
+  });
+
+  // ==========================================
+  // Test Suite 23: Emergency Pause
+  // ==========================================
+  describe('Emergency Pause', () => {
+    let pauseSeasonPda: web3.PublicKey;
+    let pauseVaultPda: web3.PublicKey;
+    let pauseEntryPda: web3.PublicKey;
+
+    const setPause = async (flags: number, signer: web3.Keypair = authority) => {
+      await program.methods
+        .setPause(flags)
+        .accounts({ arena: arenaPda, authority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    };
+
+    const expectPaused = async (call: () => Promise<unknown>) => {
+      try {
+        await call();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.ArenaPaused);
+      }
+    };
+
+    before(async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      [pauseSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      [pauseVaultPda] = deriveSeasonVaultPda(pauseSeasonPda, program.programId);
+      [pauseEntryPda] = deriveSeasonEntryPda(pauseSeasonPda, agentPda, program.programId);
+
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: pauseSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: pauseSeasonPda,
+          seasonEntry: pauseEntryPda,
+          agent: agentPda,
+          arena: arenaPda,
+          player: player.publicKey,
+          seasonVault: pauseVaultPda,
+          treasury: arena.treasury,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+    });
+
+    it('Should fail to pause as non-authority', async () => {
+      try {
+        await setPause(TEST_CONSTANTS.PAUSE_ALL, player);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+    });
+
+    it('Should reject unknown pause flags', async () => {
+      try {
+        await setPause(8);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidPauseFlags);
+      }
+    });
+
+    it('Should halt entries, predictions and payouts', async () => {
+      await setPause(TEST_CONSTANTS.PAUSE_ALL);
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arena.pauseFlags).to.equal(TEST_CONSTANTS.PAUSE_ALL);
+
+      const [entry2Pda] = deriveSeasonEntryPda(pauseSeasonPda, agent2Pda, program.programId);
+      await expectPaused(() =>
+        program.methods
+          .enterSeason()
+          .accounts({
+            season: pauseSeasonPda,
+            seasonEntry: entry2Pda,
+            agent: agent2Pda,
+            arena: arenaPda,
+            player: player2.publicKey,
+            seasonVault: pauseVaultPda,
+            treasury: arena.treasury,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([player2])
+          .rpc()
+      );
+
+      const agent = await program.account.agent.fetch(agentPda);
+      const [predPda] = derivePredictionPda(agentPda, pauseSeasonPda, agent.predictionsSubmitted, program.programId);
+      await expectPaused(() =>
+        program.methods
+          .submitPrediction(Array.from(generateNonce()), STAKE_AMOUNT)
+          .accounts({
+            season: pauseSeasonPda,
+            agent: agentPda,
+            arena: arenaPda,
+            prediction: predPda,
+            player: player.publicKey,
+            predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([player])
+          .rpc()
+      );
+
+      await expectPaused(() =>
+        program.methods
+          .distributePrizes()
+          .accounts({
+            season: pauseSeasonPda,
+            arena: arenaPda,
+            seasonOperator: authority.publicKey,
+            seasonVault: pauseVaultPda,
+            treasury: arena.treasury,
+          })
+          .signers([authority])
+          .rpc()
+      );
+    });
+
+    it('Should pause one instruction class at a time', async () => {
+      await setPause(TEST_CONSTANTS.PAUSE_PAYOUTS);
+
+      const agent = await program.account.agent.fetch(agentPda);
+      const [predPda] = derivePredictionPda(agentPda, pauseSeasonPda, agent.predictionsSubmitted, program.programId);
+      await program.methods
+        .submitPrediction(Array.from(generateNonce()), new BN(0))
+        .accounts({
+          season: pauseSeasonPda,
+          agent: agentPda,
+          arena: arenaPda,
+          prediction: predPda,
+          player: player.publicKey,
+          predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      await setPause(TEST_CONSTANTS.PAUSE_ALL);
+    });
+
+    it('Should still process refunds and withdrawals while paused', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+
+      await program.methods
+        .cancelSeason(false)
+        .accounts({
+          season: pauseSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .claimRefund()
+        .accounts({
+          season: pauseSeasonPda,
+          seasonEntry: pauseEntryPda,
+          arena: arenaPda,
+          seasonVault: pauseVaultPda,
+          treasury: arena.treasury,
+          player: player.publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .withdrawTreasury(new BN(0))
+        .accounts({
+          arena: arenaPda,
+          treasury: arena.treasury,
+          treasurer: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const entry = await program.account.seasonEntry.fetch(pauseEntryPda);
+      expect(entry.refunded).to.equal(true);
+    });
+
+    it('Should resume when the flags are cleared', async () => {
+      await setPause(0);
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arena.pauseFlags).to.equal(0);
+    });
+  });
+
+  // ==========================================
+  // Test Suite 24: Agent Profile Updates
+  // ==========================================
+  describe('Agent Profile Updates', () => {
+    const updater = web3.Keypair.generate();
+    let updaterAgentPda: web3.PublicKey;
+
+    const updateAgent = async (
+      agent: web3.PublicKey,
+      owner: web3.Keypair,
+      name: string | null,
+      endpoint: string | null
+    ) => {
+      await program.methods
+        .updateAgent(name, endpoint)
+        .accounts({ agent, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+    };
+
+    before(async () => {
+      await airdrop(provider, updater);
+      [updaterAgentPda] = deriveAgentPda(updater.publicKey, 0, program.programId);
+
+      await program.methods
+        .registerAgent(TEST_CONSTANTS.AGENT_NAMES[3], TEST_CONSTANTS.ENDPOINT_URLS[2])
+        .accounts({
+          agentRegistry: deriveAgentRegistryPda(updater.publicKey, program.programId)[0],
+          agent: updaterAgentPda,
+          arena: arenaPda,
+          owner: updater.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([updater])
+        .rpc();
+    });
+
+    it('Should fail to update another owner\'s agent', async () => {
+      try {
+        await updateAgent(updaterAgentPda, player, 'Hijacked', null);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.UnauthorizedAgent);
+      }
+    });
+
+    it('Should enforce the registration length limits', async () => {
+      try {
+        await updateAgent(updaterAgentPda, updater, null, 'https://' + 'a'.repeat(TEST_CONSTANTS.MAX_ENDPOINT_LENGTH));
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.EndpointTooLong);
+      }
+    });
+
+    it('Should fail an update that changes nothing', async () => {
+      try {
+        await updateAgent(updaterAgentPda, updater, null, null);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.EmptyAgentUpdate);
+      }
+    });
+
+    it('Should rename an agent and move its endpoint', async () => {
+      await updateAgent(updaterAgentPda, updater, TEST_CONSTANTS.AGENT_NAMES[4], 'https://new-host.example/predict');
+
+      const agent = await program.account.agent.fetch(updaterAgentPda);
+      expect(agent.name).to.equal(TEST_CONSTANTS.AGENT_NAMES[4]);
+      expect(agent.endpoint).to.equal('https://new-host.example/predict');
+      expect(agent.lastUpdatedAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it('Should rate limit consecutive updates', async () => {
+      try {
+        await updateAgent(updaterAgentPda, updater, null, 'https://another-host.example/predict');
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.AgentUpdateCooldown);
+      }
+    });
+
+    it('Should block renames while predictions are open but allow endpoint moves', async () => {
+      const agent = await program.account.agent.fetch(agentPda);
+      expect(agent.openPredictions).to.be.greaterThan(0);
+
+      try {
+        await updateAgent(agentPda, player, 'RenamedMidSeason', null);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.AgentHasOpenPredictions);
+      }
+
+      await updateAgent(agentPda, player, null, 'https://moved.testagent.com/predictions');
+      const moved = await program.account.agent.fetch(agentPda);
+      expect(moved.name).to.equal(agent.name);
+      expect(moved.endpoint).to.equal('https://moved.testagent.com/predictions');
+    });
+  });
+
+  // ==========================================
+  // Test Suite 25: Multiple Agents per Owner
+  // ==========================================
+  describe('Multiple Agents per Owner', () => {
+    const fleetOwner = web3.Keypair.generate();
+    let registryPda: web3.PublicKey;
+
+    const registerFleetAgent = async (index: number, name: string) => {
+      const [fleetAgentPda] = deriveAgentPda(fleetOwner.publicKey, index, program.programId);
+      await program.methods
+        .registerAgent(name, TEST_CONSTANTS.ENDPOINT_URLS[0])
+        .accounts({
+          agentRegistry: registryPda,
+          agent: fleetAgentPda,
+          arena: arenaPda,
+          owner: fleetOwner.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([fleetOwner])
+        .rpc();
+      return fleetAgentPda;
+    };
+
+    before(async () => {
+      await airdrop(provider, fleetOwner);
+      [registryPda] = deriveAgentRegistryPda(fleetOwner.publicKey, program.programId);
+    });
+
+    it('Should register several agents from one wallet', async () => {
+      const before = await program.account.arena.fetch(arenaPda);
+
+      const first = await registerFleetAgent(0, 'FleetMomentum');
+      const second = await registerFleetAgent(1, 'FleetMeanRevert');
+
+      const registry = await program.account.agentRegistry.fetch(registryPda);
+      expect(registry.owner.toString()).to.equal(fleetOwner.publicKey.toString());
+      expect(registry.nextIndex).to.equal(2);
+      expect(registry.agents.map((a) => a.toString())).to.deep.equal([
+        first.toString(),
+        second.toString(),
+      ]);
+
+      const agent = await program.account.agent.fetch(second);
+      expect(agent.owner.toString()).to.equal(fleetOwner.publicKey.toString());
+      expect(agent.index).to.equal(1);
+      expect(agent.name).to.equal('FleetMeanRevert');
+
+      const after = await program.account.arena.fetch(arenaPda);
+      expect(after.totalAgents.toNumber()).to.equal(before.totalAgents.toNumber() + 2);
+    });
+
+    it('Should derive distinct downstream PDAs per agent', async () => {
+      const [first] = deriveAgentPda(fleetOwner.publicKey, 0, program.programId);
+      const [second] = deriveAgentPda(fleetOwner.publicKey, 1, program.programId);
+
+      const [firstEntry] = deriveSeasonEntryPda(seasonPda, first, program.programId);
+      const [secondEntry] = deriveSeasonEntryPda(seasonPda, second, program.programId);
+      expect(firstEntry.toString()).to.not.equal(secondEntry.toString());
+    });
+
+    it('Should fail to skip ahead of the registry index', async () => {
+      try {
+        await registerFleetAgent(3, 'FleetSkipped');
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('ConstraintSeeds');
+      }
+    });
+  });
+
+  // ==========================================
+  // Test Suite 26: Agent Delegates
+  // ==========================================
+  describe('Agent Delegates', () => {
+    const hotKey = web3.Keypair.generate();
+    let delegatePda: web3.PublicKey;
+    let delegateSeasonPda: web3.PublicKey;
+
+    const setDelegate = async (scopes: number, maxStake: BN, expiresAt: BN, owner: web3.Keypair = player2) => {
+      await program.methods
+        .setDelegate(hotKey.publicKey, scopes, maxStake, expiresAt)
+        .accounts({
+          agent: agent2Pda,
+          delegate: delegatePda,
+          owner: owner.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+    };
+
+    const submitAsHotKey = async (stake: BN) => {
+      const agent = await program.account.agent.fetch(agent2Pda);
+      const nonce = generateNonce();
+      const payload = generatePredictionPayload('SOL', 'up', 150, 7000);
+      const hash = generatePredictionHash(agent2Pda, delegateSeasonPda, nonce, payload);
+      const [predPda] = derivePredictionPda(agent2Pda, delegateSeasonPda, agent.predictionsSubmitted, program.programId);
+
+      await program.methods
+        .submitPrediction(Array.from(hash), stake)
+        .accounts({
+          season: delegateSeasonPda,
+          agent: agent2Pda,
+          arena: arenaPda,
+          prediction: predPda,
+          player: hotKey.publicKey,
+          predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+          systemProgram: web3.SystemProgram.programId,
+          delegate: delegatePda,
+        })
+        .signers([hotKey])
+        .rpc();
+      return { predPda, nonce, payload };
+    };
+
+    const inOneDay = async () => {
+      const slot = await provider.connection.getSlot();
+      const now = await provider.connection.getBlockTime(slot);
+      return new BN(now + 86400);
+    };
+
+    before(async () => {
+      await airdrop(provider, hotKey);
+      [delegatePda] = deriveAgentDelegatePda(agent2Pda, hotKey.publicKey, program.programId);
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      [delegateSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: delegateSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
+    it('Should fail to submit from an unregistered key', async () => {
+      const agent = await program.account.agent.fetch(agent2Pda);
+      const [predPda] = derivePredictionPda(agent2Pda, delegateSeasonPda, agent.predictionsSubmitted, program.programId);
+      try {
+        await program.methods
+          .submitPrediction(Array.from(generateNonce()), new BN(0))
+          .accounts({
+            season: delegateSeasonPda,
+            agent: agent2Pda,
+            arena: arenaPda,
+            prediction: predPda,
+            player: hotKey.publicKey,
+            predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([hotKey])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.UnauthorizedAgent);
+      }
+    });
+
+    it('Should fail to set a delegate for another owner\'s agent', async () => {
+      try {
+        await setDelegate(TEST_CONSTANTS.DELEGATE_ALL, STAKE_AMOUNT, await inOneDay(), player);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.UnauthorizedAgent);
+      }
+    });
+
+    it('Should reject unknown scopes and past expiries', async () => {
+      try {
+        await setDelegate(4, STAKE_AMOUNT, await inOneDay());
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidDelegateScopes);
+      }
+
+      try {
+        await setDelegate(TEST_CONSTANTS.DELEGATE_ALL, STAKE_AMOUNT, new BN(1));
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidDelegateExpiry);
+      }
+    });
+
+    it('Should register a submit-only delegate', async () => {
+      const expiresAt = await inOneDay();
+      await setDelegate(TEST_CONSTANTS.DELEGATE_SUBMIT, STAKE_AMOUNT, expiresAt);
+
+      const delegate = await program.account.agentDelegate.fetch(delegatePda);
+      expect(delegate.agent.toString()).to.equal(agent2Pda.toString());
+      expect(delegate.key.toString()).to.equal(hotKey.publicKey.toString());
+      expect(delegate.scopes).to.equal(TEST_CONSTANTS.DELEGATE_SUBMIT);
+      expect(delegate.maxStake.toNumber()).to.equal(STAKE_AMOUNT.toNumber());
+      expect(delegate.expiresAt.toNumber()).to.equal(expiresAt.toNumber());
+    });
+
+    it('Should enforce the delegate stake cap', async () => {
+      try {
+        await submitAsHotKey(STAKE_AMOUNT.add(new BN(1)));
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.DelegateStakeCapExceeded);
+      }
+    });
+
+    it('Should submit with the delegate but keep the reveal scoped', async () => {
+      const { predPda, nonce, payload } = await submitAsHotKey(STAKE_AMOUNT);
+
+      const prediction = await program.account.prediction.fetch(predPda);
+      expect(prediction.agent.toString()).to.equal(agent2Pda.toString());
+      expect(prediction.stakeAmount.toNumber()).to.equal(STAKE_AMOUNT.toNumber());
+
+      const reveal = () =>
+        program.methods
+          .revealPrediction(payload, Array.from(nonce))
+          .accounts({
+            prediction: predPda,
+            agent: agent2Pda,
+            player: hotKey.publicKey,
+            delegate: delegatePda,
+          })
+          .signers([hotKey])
+          .rpc();
+
+      try {
+        await reveal();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.DelegateScopeMissing);
+      }
+
+      // Widening the scopes updates the same delegate account
+      await setDelegate(TEST_CONSTANTS.DELEGATE_ALL, STAKE_AMOUNT, await inOneDay());
+      await reveal();
+
+      const revealed = await program.account.prediction.fetch(predPda);
+      expect(revealed.status).to.deep.equal({ revealed: {} });
+    });
+
+    it('Should revoke a delegate and refund its rent to the owner', async () => {
+      const ownerBefore = await provider.connection.getBalance(player2.publicKey);
+
+      await program.methods
+        .revokeDelegate()
+        .accounts({
+          agent: agent2Pda,
+          delegate: delegatePda,
+          owner: player2.publicKey,
+        })
+        .signers([player2])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(delegatePda)).to.be.null;
+      const ownerAfter = await provider.connection.getBalance(player2.publicKey);
+      expect(ownerAfter).to.be.greaterThan(ownerBefore);
+
+      try {
+        await submitAsHotKey(new BN(0));
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('AccountNotInitialized');
+      }
+    });
+  });
+
+  // ==========================================
+  // Test Suite 27: Agent Ownership Transfer
+  // ==========================================
+  describe('Agent Ownership Transfer', () => {
+    const seller = web3.Keypair.generate();
+    const buyer = web3.Keypair.generate();
+    const sellerHotKey = web3.Keypair.generate();
+    let soldAgentPda: web3.PublicKey;
+    let transferSeasonPda: web3.PublicKey;
+    let transferEntryPda: web3.PublicKey;
+
+    const proposeTransfer = async (agent: web3.PublicKey, owner: web3.Keypair, newOwner: web3.PublicKey) => {
+      await program.methods
+        .proposeAgentTransfer(newOwner)
+        .accounts({ agent, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+    };
+
+    const cancelTransfer = async (agent: web3.PublicKey, owner: web3.Keypair) => {
+      await program.methods
+        .cancelAgentTransfer()
+        .accounts({ agent, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+    };
+
+    const acceptTransfer = async (agent: web3.PublicKey, previousOwner: web3.PublicKey, newOwner: web3.Keypair) => {
+      await program.methods
+        .acceptAgentTransfer()
+        .accounts({
+          agent,
+          oldRegistry: deriveAgentRegistryPda(previousOwner, program.programId)[0],
+          newRegistry: deriveAgentRegistryPda(newOwner.publicKey, program.programId)[0],
+          newOwner: newOwner.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([newOwner])
+        .rpc();
+    };
+
+    before(async () => {
+      await airdrop(provider, seller);
+      await airdrop(provider, buyer);
+      [soldAgentPda] = deriveAgentPda(seller.publicKey, 0, program.programId);
+
+      await program.methods
+        .registerAgent('ForSale', TEST_CONSTANTS.ENDPOINT_URLS[1])
+        .accounts({
+          agentRegistry: deriveAgentRegistryPda(seller.publicKey, program.programId)[0],
+          agent: soldAgentPda,
+          arena: arenaPda,
+          owner: seller.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([seller])
+        .rpc();
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      [transferSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      [transferEntryPda] = deriveSeasonEntryPda(transferSeasonPda, soldAgentPda, program.programId);
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: transferSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: transferSeasonPda,
+          seasonEntry: transferEntryPda,
+          agent: soldAgentPda,
+          arena: arenaPda,
+          player: seller.publicKey,
+          seasonVault: deriveSeasonVaultPda(transferSeasonPda, program.programId)[0],
+          treasury: arena.treasury,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([seller])
+        .rpc();
+
+      const slot = await provider.connection.getSlot();
+      const now = await provider.connection.getBlockTime(slot);
+      await program.methods
+        .setDelegate(sellerHotKey.publicKey, TEST_CONSTANTS.DELEGATE_ALL, STAKE_AMOUNT, new BN(now + 86400))
+        .accounts({
+          agent: soldAgentPda,
+          delegate: deriveAgentDelegatePda(soldAgentPda, sellerHotKey.publicKey, program.programId)[0],
+          owner: seller.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([seller])
+        .rpc();
+    });
+
+    it('Should fail to propose a transfer as non-owner', async () => {
+      try {
+        await proposeTransfer(soldAgentPda, buyer, buyer.publicKey);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.UnauthorizedAgent);
+      }
+    });
+
+    it('Should fail to transfer an agent with open predictions', async () => {
+      await proposeTransfer(agentPda, player, buyer.publicKey);
+      try {
+        await acceptTransfer(agentPda, player.publicKey, buyer);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.AgentHasOpenPredictions);
+      }
+
+      await cancelTransfer(agentPda, player);
+      const agent = await program.account.agent.fetch(agentPda);
+      expect(agent.pendingOwner.toString()).to.equal(web3.PublicKey.default.toString());
+    });
+
+    it('Should fail to accept as someone other than the pending owner', async () => {
+      await proposeTransfer(soldAgentPda, seller, buyer.publicKey);
+      try {
+        await acceptTransfer(soldAgentPda, seller.publicKey, player3);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.NotPendingAgentOwner);
+      }
+    });
+
+    it('Should hand the agent to the recipient with its record intact', async () => {
+      const before = await program.account.agent.fetch(soldAgentPda);
+
+      await acceptTransfer(soldAgentPda, seller.publicKey, buyer);
+
+      const agent = await program.account.agent.fetch(soldAgentPda);
+      expect(agent.owner.toString()).to.equal(buyer.publicKey.toString());
+      expect(agent.creator.toString()).to.equal(seller.publicKey.toString());
+      expect(agent.pendingOwner.toString()).to.equal(web3.PublicKey.default.toString());
+      expect(agent.name).to.equal(before.name);
+      expect(agent.reputationScore).to.equal(before.reputationScore);
+      expect(agent.joinedAt.toNumber()).to.equal(before.joinedAt.toNumber());
+
+      const sellerRegistry = await program.account.agentRegistry.fetch(
+        deriveAgentRegistryPda(seller.publicKey, program.programId)[0]
+      );
+      const buyerRegistry = await program.account.agentRegistry.fetch(
+        deriveAgentRegistryPda(buyer.publicKey, program.programId)[0]
+      );
+      expect(sellerRegistry.agents).to.be.empty;
+      expect(sellerRegistry.nextIndex).to.equal(1);
+      expect(buyerRegistry.agents.map((a) => a.toString())).to.deep.equal([soldAgentPda.toString()]);
+    });
+
+    it('Should lock the previous owner and their delegates out', async () => {
+      try {
+        await program.methods
+          .updateAgent(null, 'https://seller.example/predict')
+          .accounts({ agent: soldAgentPda, owner: seller.publicKey })
+          .signers([seller])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.UnauthorizedAgent);
+      }
+
+      await airdrop(provider, sellerHotKey);
+      const agent = await program.account.agent.fetch(soldAgentPda);
+      const [predPda] = derivePredictionPda(soldAgentPda, transferSeasonPda, agent.predictionsSubmitted, program.programId);
+      try {
+        await program.methods
+          .submitPrediction(Array.from(generateNonce()), new BN(0))
+          .accounts({
+            season: transferSeasonPda,
+            agent: soldAgentPda,
+            arena: arenaPda,
+            prediction: predPda,
+            player: sellerHotKey.publicKey,
+            predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+            systemProgram: web3.SystemProgram.programId,
+            delegate: deriveAgentDelegatePda(soldAgentPda, sellerHotKey.publicKey, program.programId)[0],
+          })
+          .signers([sellerHotKey])
+          .rpc();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.StaleDelegate);
+      }
+    });
+
+    it('Should move an active season entry to the new owner', async () => {
+      await program.methods
+        .syncEntryOwner()
+        .accounts({
+          season: transferSeasonPda,
+          agent: soldAgentPda,
+          seasonEntry: transferEntryPda,
+          leaderboard: deriveLeaderboardPda(transferSeasonPda, program.programId)[0],
+        })
+        .rpc();
+
+      const entry = await program.account.seasonEntry.fetch(transferEntryPda);
+      expect(entry.agent.toString()).to.equal(soldAgentPda.toString());
+      expect(entry.player.toString()).to.equal(buyer.publicKey.toString());
+    });
+
+    it('Should fail to cancel when no transfer is pending', async () => {
+      try {
+        await cancelTransfer(soldAgentPda, buyer);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.NoPendingAgentTransfer);
+      }
+    });
+  });
+
+  // ==========================================
+  // Test Suite 28: Agent Retirement & Rent Reclamation
+  // ==========================================
+  describe('Agent Retirement & Rent Reclamation', () => {
+    const retiree = web3.Keypair.generate();
+    let retireeAgentPda: web3.PublicKey;
+    let retireeRegistryPda: web3.PublicKey;
+    let retireSeasonPda: web3.PublicKey;
+    let retireEntryPda: web3.PublicKey;
+    let retireVaultPda: web3.PublicKey;
+    let settledPredictionPda: web3.PublicKey;
+    let achievementPda: web3.PublicKey;
+
+    const commit = async () => {
+      const agent = await program.account.agent.fetch(retireeAgentPda);
+      const nonce = generateNonce();
+      const payload = generatePredictionPayload('ETH', 'up', 3000, 6000);
+      const [predPda] = derivePredictionPda(retireeAgentPda, retireSeasonPda, agent.predictionsSubmitted, program.programId);
+
+      await program.methods
+        .submitPrediction(Array.from(generatePredictionHash(retireeAgentPda, retireSeasonPda, nonce, payload)), STAKE_AMOUNT)
+        .accounts({
+          season: retireSeasonPda,
+          agent: retireeAgentPda,
+          arena: arenaPda,
+          prediction: predPda,
+          player: retiree.publicKey,
+          predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([retiree])
+        .rpc();
+      return { predPda, nonce, payload };
+    };
+
+    const settle = async ({ predPda, nonce, payload }: Awaited<ReturnType<typeof commit>>) => {
+      await program.methods
+        .revealPrediction(payload, Array.from(nonce))
+        .accounts({ prediction: predPda, agent: retireeAgentPda, player: retiree.publicKey })
+        .signers([retiree])
+        .rpc();
+
+      await program.methods
+        .resolvePrediction(true)
+        .accounts({
+          prediction: predPda,
+          agent: retireeAgentPda,
+          seasonEntry: retireEntryPda,
+          resolver: authority.publicKey,
+          predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+          player: retiree.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    };
+
+    const closePrediction = async (predPda: web3.PublicKey) => {
+      await program.methods
+        .closePrediction()
+        .accounts({
+          agent: retireeAgentPda,
+          prediction: predPda,
+          predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+          rentPayer: retiree.publicKey,
+          owner: retiree.publicKey,
+          arena: arenaPda,
+        })
+        .signers([retiree])
+        .rpc();
+    };
+
+    const closeEntry = async () => {
+      await program.methods
+        .closeSeasonEntry()
+        .accounts({
+          agent: retireeAgentPda,
+          season: retireSeasonPda,
+          seasonEntry: retireEntryPda,
+          rentPayer: retiree.publicKey,
+          owner: retiree.publicKey,
+        })
+        .signers([retiree])
+        .rpc();
+    };
+
+    const closeAchievement = async () => {
+      await program.methods
+        .closeAchievement()
+        .accounts({
+          agent: retireeAgentPda,
+          achievement: achievementPda,
+          rentPayer: authority.publicKey,
+          owner: retiree.publicKey,
+        })
+        .signers([retiree])
+        .rpc();
+    };
+
+    const retire = async (owner: web3.Keypair) => {
+      await program.methods
+        .retireAgent()
+        .accounts({
+          agent: retireeAgentPda,
+          agentRegistry: deriveAgentRegistryPda(owner.publicKey, program.programId)[0],
+          creator: retiree.publicKey,
+          owner: owner.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+    };
+
+    before(async () => {
+      await airdrop(provider, retiree, 5);
+      [retireeAgentPda] = deriveAgentPda(retiree.publicKey, 0, program.programId);
+      [retireeRegistryPda] = deriveAgentRegistryPda(retiree.publicKey, program.programId);
+
+      await program.methods
+        .registerAgent('Retiree', TEST_CONSTANTS.ENDPOINT_URLS[2])
+        .accounts({
+          agentRegistry: retireeRegistryPda,
+          agent: retireeAgentPda,
+          arena: arenaPda,
+          owner: retiree.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([retiree])
+        .rpc();
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      [retireSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      [retireEntryPda] = deriveSeasonEntryPda(retireSeasonPda, retireeAgentPda, program.programId);
+      [retireVaultPda] = deriveSeasonVaultPda(retireSeasonPda, program.programId);
+
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: retireSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: retireSeasonPda,
+          seasonEntry: retireEntryPda,
+          agent: retireeAgentPda,
+          arena: arenaPda,
+          player: retiree.publicKey,
+          seasonVault: retireVaultPda,
+          treasury: arena.treasury,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([retiree])
+        .rpc();
+
+      const settled = await commit();
+      await settle(settled);
+      settledPredictionPda = settled.predPda;
+
+      [achievementPda] = deriveAchievementPda(retireeAgentPda, new BN(0), program.programId);
+      await program.methods
+        .awardAchievement({ firstWin: {} })
+        .accounts({
+          agent: retireeAgentPda,
+          achievement: achievementPda,
+          achievementIssuer: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
+    it('Should fail to close an unsettled prediction', async () => {
+      const pending = await commit();
+      try {
+        await closePrediction(pending.predPda);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.PredictionNotSettled);
+      }
+      await settle(pending);
+    });
+
+    it('Should close a settled prediction and refund its payer', async () => {
+      const [vaultPda] = derivePredictionVaultPda(settledPredictionPda, program.programId);
+      const rent =
+        (await provider.connection.getBalance(settledPredictionPda)) +
+        (await provider.connection.getBalance(vaultPda));
+      const before = await provider.connection.getBalance(retiree.publicKey);
+
+      await closePrediction(settledPredictionPda);
+
+      expect(await provider.connection.getAccountInfo(settledPredictionPda)).to.be.null;
+      expect(await provider.connection.getAccountInfo(vaultPda)).to.be.null;
+      const after = await provider.connection.getBalance(retiree.publicKey);
+      expect(after - before).to.equal(rent);
+    });
+
+    it('Should keep an entry open while its season is live', async () => {
+      try {
+        await closeEntry();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.SeasonEntryInUse);
+      }
+    });
+
+    it('Should close a refunded entry of a cancelled season', async () => {
+      await program.methods
+        .cancelSeason(true)
+        .accounts({
+          season: retireSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      await program.methods
+        .claimRefund()
+        .accounts({
+          season: retireSeasonPda,
+          seasonEntry: retireEntryPda,
+          arena: arenaPda,
+          seasonVault: retireVaultPda,
+          treasury: arena.treasury,
+          player: retiree.publicKey,
+        })
+        .rpc();
+
+      await closeEntry();
+      expect(await provider.connection.getAccountInfo(retireEntryPda)).to.be.null;
+    });
+
+    it('Should keep achievements while the agent is active', async () => {
+      try {
+        await closeAchievement();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.AgentNotRetired);
+      }
+    });
+
+    it('Should fail to retire another owner\'s agent', async () => {
+      try {
+        await retire(player);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.UnauthorizedAgent);
+      }
+    });
+
+    it('Should retire an agent to a tombstone', async () => {
+      const before = await program.account.agent.fetch(retireeAgentPda);
+      const sizeBefore = (await provider.connection.getAccountInfo(retireeAgentPda)).data.length;
+
+      await retire(retiree);
+
+      const agent = await program.account.agent.fetch(retireeAgentPda);
+      expect(agent.status).to.deep.equal({ retired: {} });
+      expect(agent.retiredAt.toNumber()).to.be.greaterThan(0);
+      expect(agent.endpoint).to.equal('');
+      expect(agent.name).to.equal(before.name);
+      expect(agent.totalPredictions.toNumber()).to.equal(before.totalPredictions.toNumber());
+      expect(agent.correctPredictions.toNumber()).to.equal(before.correctPredictions.toNumber());
+      expect(agent.reputationScore).to.equal(before.reputationScore);
+
+      const sizeAfter = (await provider.connection.getAccountInfo(retireeAgentPda)).data.length;
+      expect(sizeAfter).to.equal(sizeBefore - TEST_CONSTANTS.MAX_ENDPOINT_LENGTH);
+
+      const registry = await program.account.agentRegistry.fetch(retireeRegistryPda);
+      expect(registry.agents).to.be.empty;
+    });
+
+    it('Should refuse further play from a retired agent', async () => {
+      try {
+        await commit();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.AgentRetired);
+      }
+
+      try {
+        await retire(retiree);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.AgentRetired);
+      }
+    });
+
+    it('Should close a retired agent\'s achievement and refund the issuer', async () => {
+      const rent = await provider.connection.getBalance(achievementPda);
+      const before = await provider.connection.getBalance(authority.publicKey);
+
+      await closeAchievement();
+
+      expect(await provider.connection.getAccountInfo(achievementPda)).to.be.null;
+      const after = await provider.connection.getBalance(authority.publicKey);
+      expect(after - before).to.equal(rent);
+    });
+  });
+
+  // ==========================================
+  // Test Suite 29: Head-to-Head Duels
+  // ==========================================
+  describe('Head-to-Head Duels', () => {
+    const assetId = assetIdFromSymbol('SOL');
+    const bystander = web3.Keypair.generate();
+    let bystanderAgentPda: web3.PublicKey;
+    let assetFeedPda: web3.PublicKey;
+    let mockFeedPda: web3.PublicKey;
+
+    // Long enough for both sides to reveal before the horizon
+    const DUEL_HORIZON = 6;
+    const challengerPayload = generatePredictionPayload('SOL', 'up', 150, 7000, DUEL_HORIZON);
+    const opponentPayload = generatePredictionPayload('SOL', 'down', 170, 7000, DUEL_HORIZON);
+    const challengerNonce = generateNonce();
+    const opponentNonce = generateNonce();
+    let duelPda: web3.PublicKey;
+    let duelVaultPda: web3.PublicKey;
+
+    const setFee = async (bps: number, signer: web3.Keypair = authority) => {
+      await program.methods
+        .setFee({ duel: {} }, bps)
+        .accounts({ arena: arenaPda, authority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    };
+
+    const createDuel = async (
+      opponent: web3.PublicKey,
+      stake: BN,
+      acceptWindow: number,
+      challengerAgent: web3.PublicKey = agentPda,
+      owner: web3.Keypair = player
+    ) => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const [duel] = deriveDuelPda(arena.totalDuels, program.programId);
+      const [vault] = deriveDuelVaultPda(duel, program.programId);
+      const commitment = generateDuelHash(duel, challengerAgent, challengerNonce, challengerPayload);
+
+      await program.methods
+        .createDuel(
+          opponent,
+          Array.from(assetId),
+          new BN(DUEL_HORIZON),
+          stake,
+          new BN(acceptWindow),
+          Array.from(commitment)
+        )
+        .accounts({
+          arena: arenaPda,
+          duel,
+          duelVault: vault,
+          challenger: challengerAgent,
+          assetFeed: assetFeedPda,
+          owner: owner.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+      return { duel, vault };
+    };
+
+    const acceptDuel = async (duel: web3.PublicKey, vault: web3.PublicKey, opponent: web3.PublicKey, owner: web3.Keypair) => {
+      const commitment = generateDuelHash(duel, opponent, opponentNonce, opponentPayload);
+      await program.methods
+        .acceptDuel(Array.from(commitment))
+        .accounts({
+          arena: arenaPda,
+          duel,
+          duelVault: vault,
+          opponent,
+          owner: owner.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+    };
+
+    const revealDuel = async (agent: web3.PublicKey, owner: web3.Keypair, payload: PredictionPayload, nonce: Buffer) => {
+      await program.methods
+        .revealDuel(payload, Array.from(nonce))
+        .accounts({ duel: duelPda, agent, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+    };
+
+    const resolveDuel = async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      await program.methods
+        .resolveDuel()
+        .accounts({
+          duel: duelPda,
+          duelVault: duelVaultPda,
+          challenger: agentPda,
+          opponent: agent2Pda,
+          challengerOwner: player.publicKey,
+          opponentOwner: player2.publicKey,
+          arena: arenaPda,
+          treasury: arena.treasury,
+          assetFeed: assetFeedPda,
+          priceFeed: mockFeedPda,
+        })
+        .rpc();
+    };
+
+    const cancelDuel = async (duel: web3.PublicKey, vault: web3.PublicKey, canceller: web3.Keypair) => {
+      await program.methods
+        .cancelDuel()
+        .accounts({
+          duel,
+          duelVault: vault,
+          challenger: agentPda,
+          challengerOwner: player.publicKey,
+          canceller: canceller.publicKey,
+        })
+        .signers([canceller])
+        .rpc();
+    };
+
+    before(async () => {
+      // SOL feed and mock price account registered by the Oracle Resolution suite
+      [assetFeedPda] = deriveAssetFeedPda(assetId, program.programId);
+      [mockFeedPda] = deriveMockPriceFeedPda(assetId, program.programId);
+
+      await airdrop(provider, bystander);
+      [bystanderAgentPda] = deriveAgentPda(bystander.publicKey, 0, program.programId);
+      await program.methods
+        .registerAgent('Bystander', TEST_CONSTANTS.ENDPOINT_URLS[0])
+        .accounts({
+          agentRegistry: deriveAgentRegistryPda(bystander.publicKey, program.programId)[0],
+          agent: bystanderAgentPda,
+          arena: arenaPda,
+          owner: bystander.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([bystander])
+        .rpc();
+    });
+
+    it('Should let only the authority set the duel fee', async () => {
+      try {
+        await setFee(TEST_CONSTANTS.DUEL_FEE_BPS, player);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+
+      try {
+        await setFee(10001);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidFeeBps);
+      }
+
+      await setFee(TEST_CONSTANTS.DUEL_FEE_BPS);
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arena.duelFeeBps).to.equal(TEST_CONSTANTS.DUEL_FEE_BPS);
+    });
+
+    it('Should reject self-duels and empty stakes', async () => {
+      try {
+        await createDuel(agentPda, STAKE_AMOUNT, 3600);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.SelfDuel);
+      }
+
+      try {
+        await createDuel(agent2Pda, new BN(0), 3600);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidDuelTerms);
+      }
+    });
+
+    it('Should create a duel and escrow the challenger stake', async () => {
+      ({ duel: duelPda, vault: duelVaultPda } = await createDuel(agent2Pda, STAKE_AMOUNT, 3600));
+
+      const duel = await program.account.duel.fetch(duelPda);
+      expect(duel.challenger.toString()).to.equal(agentPda.toString());
+      expect(duel.opponent.toString()).to.equal(agent2Pda.toString());
+      expect(duel.stake.toNumber()).to.equal(STAKE_AMOUNT.toNumber());
+      expect(duel.feeBps).to.equal(TEST_CONSTANTS.DUEL_FEE_BPS);
+      expect(duel.status).to.deep.equal({ open: {} });
+
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(8 + 1);
+      expect(await provider.connection.getBalance(duelVaultPda)).to.equal(rent + STAKE_AMOUNT.toNumber());
+    });
+
+    it('Should only let the invited agent accept', async () => {
+      try {
+        await acceptDuel(duelPda, duelVaultPda, bystanderAgentPda, bystander);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.DuelOpponentMismatch);
+      }
+
+      await acceptDuel(duelPda, duelVaultPda, agent2Pda, player2);
+      const duel = await program.account.duel.fetch(duelPda);
+      expect(duel.status).to.deep.equal({ active: {} });
+      expect(duel.opponentOwner.toString()).to.equal(player2.publicKey.toString());
+      expect(duel.resolveAfter.toNumber()).to.equal(duel.acceptedAt.toNumber() + DUEL_HORIZON);
+      // The horizon is shorter than DUEL_REVEAL_WINDOW, so reveals close at the horizon
+      expect(duel.revealDeadline.toNumber()).to.equal(duel.resolveAfter.toNumber());
+    });
+
+    it('Should verify each side against its commitment', async () => {
+      try {
+        await revealDuel(agentPda, player, challengerPayload, opponentNonce);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.HashMismatch);
+      }
+
+      try {
+        await revealDuel(bystanderAgentPda, bystander, challengerPayload, challengerNonce);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.DuelParticipantMismatch);
+      }
+
+      await revealDuel(agentPda, player, challengerPayload, challengerNonce);
+      const duel = await program.account.duel.fetch(duelPda);
+      expect(duel.challengerRevealed).to.equal(true);
+      expect(duel.challengerTargetPrice.toNumber()).to.equal(150);
+    });
+
+    it('Should wait for the other side while the reveal window is open', async () => {
+      try {
+        await resolveDuel();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.RevealWindowOpen);
+      }
+    });
+
+    it('Should pay the closest target both stakes minus the fee', async () => {
+      await revealDuel(agent2Pda, player2, opponentPayload, opponentNonce);
+      await new Promise(resolve => setTimeout(resolve, (DUEL_HORIZON + 1) * 1000));
+
+      // 155 is 5 from the challenger's 150 and 15 from the opponent's 170
+      await program.methods
+        .setMockPrice(Array.from(assetId), new BN(155_00000000), new BN(1000))
+        .accounts({
+          mockPriceFeed: mockFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const arenaBefore = await program.account.arena.fetch(arenaPda);
+      const playerBefore = await provider.connection.getBalance(player.publicKey);
+      const player2Before = await provider.connection.getBalance(player2.publicKey);
+      const treasuryBefore = await provider.connection.getBalance(arenaBefore.treasury);
+
+      await resolveDuel();
+
+      const pot = STAKE_AMOUNT.toNumber() * 2;
+      const fee = (pot * TEST_CONSTANTS.DUEL_FEE_BPS) / 10000;
+      expect(await provider.connection.getBalance(player.publicKey)).to.equal(playerBefore + pot - fee);
+      expect(await provider.connection.getBalance(player2.publicKey)).to.equal(player2Before);
+      expect(await provider.connection.getBalance(arenaBefore.treasury)).to.equal(treasuryBefore + fee);
+
+      const duel = await program.account.duel.fetch(duelPda);
+      expect(duel.status).to.deep.equal({ resolved: {} });
+      expect(duel.winner.toString()).to.equal(agentPda.toString());
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      expect(arena.totalFeesCollected.toNumber()).to.equal(arenaBefore.totalFeesCollected.toNumber() + fee);
+    });
+
+    it('Should close reveals at the horizon', async () => {
+      ({ duel: duelPda, vault: duelVaultPda } = await createDuel(agent2Pda, STAKE_AMOUNT, 3600));
+      await acceptDuel(duelPda, duelVaultPda, agent2Pda, player2);
+      await revealDuel(agentPda, player, challengerPayload, challengerNonce);
+      await new Promise(resolve => setTimeout(resolve, (DUEL_HORIZON + 1) * 1000));
+
+      // The horizon price may be out; the opponent can no longer pick a side against it
+      try {
+        await revealDuel(agent2Pda, player2, opponentPayload, opponentNonce);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.RevealWindowClosed);
+      }
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      const fee = (STAKE_AMOUNT.toNumber() * 2 * arena.duelFeeBps) / 10000;
+      const playerBefore = await provider.connection.getBalance(player.publicKey);
+      await resolveDuel();
+      expect(await provider.connection.getBalance(player.publicKey)).to.equal(
+        playerBefore + STAKE_AMOUNT.toNumber() * 2 - fee
+      );
+    });
+
+    it('Should refund both sides when the horizon price is never cranked', async () => {
+      const setStaleness = async (seconds: number) => {
+        await program.methods
+          .updateAssetFeed(Array.from(assetId), mockFeedPda, new BN(seconds))
+          .accounts({ assetFeed: assetFeedPda, arena: arenaPda, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+      };
+
+      await setStaleness(1);
+      ({ duel: duelPda, vault: duelVaultPda } = await createDuel(agent2Pda, STAKE_AMOUNT, 3600));
+      await acceptDuel(duelPda, duelVaultPda, agent2Pda, player2);
+      await revealDuel(agentPda, player, challengerPayload, challengerNonce);
+      await revealDuel(agent2Pda, player2, opponentPayload, opponentNonce);
+      await new Promise(resolve => setTimeout(resolve, (DUEL_HORIZON + 3) * 1000));
+
+      const playerBefore = await provider.connection.getBalance(player.publicKey);
+      const player2Before = await provider.connection.getBalance(player2.publicKey);
+      await resolveDuel();
+      await setStaleness(60);
+
+      expect(await provider.connection.getBalance(player.publicKey)).to.equal(
+        playerBefore + STAKE_AMOUNT.toNumber()
+      );
+      expect(await provider.connection.getBalance(player2.publicKey)).to.equal(
+        player2Before + STAKE_AMOUNT.toNumber()
+      );
+      const duel = await program.account.duel.fetch(duelPda);
+      expect(duel.status).to.deep.equal({ resolved: {} });
+      expect(duel.winner.toString()).to.equal(web3.PublicKey.default.toString());
+    });
+
+    it('Should let only the challenger cancel before the deadline', async () => {
+      const { duel, vault } = await createDuel(web3.PublicKey.default, STAKE_AMOUNT, 3600);
+
+      try {
+        await cancelDuel(duel, vault, bystander);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.DuelCancelTooEarly);
+      }
+
+      const before = await provider.connection.getBalance(player.publicKey);
+      await cancelDuel(duel, vault, player);
+      expect(await provider.connection.getBalance(player.publicKey)).to.equal(before + STAKE_AMOUNT.toNumber());
+
+      const cancelled = await program.account.duel.fetch(duel);
+      expect(cancelled.status).to.deep.equal({ cancelled: {} });
+    });
+
+    it('Should close acceptance at the deadline and let anyone cancel', async () => {
+      const { duel, vault } = await createDuel(web3.PublicKey.default, STAKE_AMOUNT, 1);
+      await new Promise(resolve => setTimeout(resolve, 2000));
+
+      try {
+        await acceptDuel(duel, vault, bystanderAgentPda, bystander);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.DuelAcceptWindowClosed);
+      }
+
+      const before = await provider.connection.getBalance(player.publicKey);
+      await cancelDuel(duel, vault, bystander);
+      expect(await provider.connection.getBalance(player.publicKey)).to.equal(before + STAKE_AMOUNT.toNumber());
+    });
+  });
+
+  // ==========================================
+  // Test Suite 30: Elimination Tournaments
+  // ==========================================
+  describe('Elimination Tournaments', () => {
+    const assetId = assetIdFromSymbol('SOL');
+    const rookie = web3.Keypair.generate();
+    const entryFee = new BN(TEST_CONSTANTS.TOURNAMENT_ENTRY_FEE);
+    let rookieAgentPda: web3.PublicKey;
+    let assetFeedPda: web3.PublicKey;
+    let mockFeedPda: web3.PublicKey;
+    let bracketPda: web3.PublicKey;
+    const owners = new Map<string, web3.Keypair>();
+    const nonces = new Map<string, Buffer>();
+    // Longer than the bracket's reveal window, which must close before the horizon
+    const BRACKET_HORIZON = 6;
+
+    const ownerOf = (agent: web3.PublicKey) => owners.get(agent.toString());
+
+    const createTournament = async (
+      format: object,
+      maxEntrants: number,
+      windows: { registration: number; commit: number; reveal: number; horizon: number },
+      prizePoolBps = 9000,
+      operator: web3.Keypair = authority
+    ) => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const [tournament] = deriveTournamentPda(arena.totalTournaments, program.programId);
+      await program.methods
+        .createTournament({
+          format,
+          assetId: Array.from(assetId),
+          horizon: new BN(windows.horizon),
+          entryFee,
+          prizePoolBps,
+          maxEntrants,
+          registrationWindow: new BN(windows.registration),
+          commitWindow: new BN(windows.commit),
+          revealWindow: new BN(windows.reveal),
+        })
+        .accounts({
+          tournament,
+          tournamentVault: deriveTournamentVaultPda(tournament, program.programId)[0],
+          arena: arenaPda,
+          assetFeed: assetFeedPda,
+          seasonOperator: operator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([operator])
+        .rpc();
+      return tournament;
+    };
+
+    const joinTournament = async (tournament: web3.PublicKey, agent: web3.PublicKey) => {
+      await program.methods
+        .joinTournament()
+        .accounts({
+          arena: arenaPda,
+          tournament,
+          tournamentVault: deriveTournamentVaultPda(tournament, program.programId)[0],
+          agent,
+          owner: ownerOf(agent).publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([ownerOf(agent)])
+        .rpc();
+    };
+
+    const leaveTournament = async (tournament: web3.PublicKey, agent: web3.PublicKey) => {
+      await program.methods
+        .leaveTournament()
+        .accounts({
+          tournament,
+          tournamentVault: deriveTournamentVaultPda(tournament, program.programId)[0],
+          agent,
+          owner: ownerOf(agent).publicKey,
+        })
+        .signers([ownerOf(agent)])
+        .rpc();
+    };
+
+    const startTournament = async (tournament: web3.PublicKey) => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      await program.methods
+        .startTournament()
+        .accounts({
+          arena: arenaPda,
+          tournament,
+          tournamentVault: deriveTournamentVaultPda(tournament, program.programId)[0],
+          treasury: arena.treasury,
+          seasonOperator: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    };
+
+    const commit = async (tournament: web3.PublicKey, agent: web3.PublicKey, payload: PredictionPayload) => {
+      const nonce = generateNonce();
+      nonces.set(agent.toString(), nonce);
+      await program.methods
+        .commitTournamentPrediction(Array.from(generatePredictionHash(agent, tournament, nonce, payload)))
+        .accounts({ tournament, agent, owner: ownerOf(agent).publicKey })
+        .signers([ownerOf(agent)])
+        .rpc();
+    };
+
+    const reveal = async (tournament: web3.PublicKey, agent: web3.PublicKey, payload: PredictionPayload) => {
+      await program.methods
+        .revealTournamentPrediction(payload, Array.from(nonces.get(agent.toString())))
+        .accounts({ tournament, agent, owner: ownerOf(agent).publicKey })
+        .signers([ownerOf(agent)])
+        .rpc();
+    };
+
+    const resolveMatch = async (tournament: web3.PublicKey, slot: number) => {
+      await program.methods
+        .resolveTournamentMatch(slot)
+        .accounts({ tournament, assetFeed: assetFeedPda, priceFeed: mockFeedPda })
+        .rpc();
+    };
+
+    const voidTournament = async (tournament: web3.PublicKey, slot: number) => {
+      await program.methods
+        .voidTournament(slot)
+        .accounts({ tournament, assetFeed: assetFeedPda, priceFeed: mockFeedPda })
+        .rpc();
+    };
+
+    const sideAgent = (tournament: any, slot: number, side: number): web3.PublicKey =>
+      tournament.entrants[tournament.matches[slot].sides[side].entrant].agent;
+
+    before(async () => {
+      // SOL feed and mock price account registered by the Oracle Resolution suite
+      [assetFeedPda] = deriveAssetFeedPda(assetId, program.programId);
+      [mockFeedPda] = deriveMockPriceFeedPda(assetId, program.programId);
+
+      await airdrop(provider, rookie);
+      [rookieAgentPda] = deriveAgentPda(rookie.publicKey, 0, program.programId);
+      await program.methods
+        .registerAgent('Rookie', TEST_CONSTANTS.ENDPOINT_URLS[1])
+        .accounts({
+          agentRegistry: deriveAgentRegistryPda(rookie.publicKey, program.programId)[0],
+          agent: rookieAgentPda,
+          arena: arenaPda,
+          owner: rookie.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([rookie])
+        .rpc();
+
+      owners.set(agentPda.toString(), player);
+      owners.set(agent2Pda.toString(), player2);
+      owners.set(rookieAgentPda.toString(), rookie);
+    });
+
+    it('Should let only the season operator open a valid tournament', async () => {
+      const windows = { registration: 3600, commit: 60, reveal: 60, horizon: 120 };
+      try {
+        await createTournament({ singleElimination: {} }, 4, windows, 9000, player);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.toString()).to.include('has_one');
+      }
+
+      try {
+        await createTournament({ singleElimination: {} }, 1, windows);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidTournamentTerms);
+      }
+
+      // Reveals may not run up to or past the horizon
+      try {
+        await createTournament({ singleElimination: {} }, 4, { ...windows, horizon: 60 });
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidTournamentTerms);
+      }
+    });
+
+    it('Should refund entrants who leave or whose tournament is cancelled', async () => {
+      const tournament = await createTournament({ singleElimination: {} }, 2, {
+        registration: 3600,
+        commit: 60,
+        reveal: 60,
+        horizon: 120,
+      });
+
+      await joinTournament(tournament, agentPda);
+      try {
+        await joinTournament(tournament, agentPda);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.AlreadyInTournament);
+      }
+
+      const before = await provider.connection.getBalance(player.publicKey);
+      await leaveTournament(tournament, agentPda);
+      expect(await provider.connection.getBalance(player.publicKey)).to.equal(before + entryFee.toNumber());
+
+      await joinTournament(tournament, agent2Pda);
+      await joinTournament(tournament, agentPda);
+      try {
+        await joinTournament(tournament, rookieAgentPda);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.TournamentFull);
+      }
+
+      await program.methods
+        .cancelTournament()
+        .accounts({ arena: arenaPda, tournament, seasonOperator: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      const player2Before = await provider.connection.getBalance(player2.publicKey);
+      await leaveTournament(tournament, agent2Pda);
+      await leaveTournament(tournament, agentPda);
+      expect(await provider.connection.getBalance(player2.publicKey)).to.equal(
+        player2Before + entryFee.toNumber()
+      );
+
+      const cancelled = await program.account.tournament.fetch(tournament);
+      expect(cancelled.status).to.deep.equal({ cancelled: {} });
+      expect(cancelled.entrants).to.have.length(0);
+    });
+
+    it('Should seed a single-elimination bracket by reputation with a bye', async () => {
+      bracketPda = await createTournament({ singleElimination: {} }, 3, {
+        registration: 3600,
+        commit: 6,
+        reveal: 5,
+        horizon: BRACKET_HORIZON,
+      });
+
+      // The rookie registers first but has the lowest reputation
+      await joinTournament(bracketPda, rookieAgentPda);
+      await joinTournament(bracketPda, agent2Pda);
+      try {
+        await startTournament(bracketPda);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.TournamentRegistrationOpen);
+      }
+      await joinTournament(bracketPda, agentPda);
+
+      const arenaBefore = await program.account.arena.fetch(arenaPda);
+      const treasuryBefore = await provider.connection.getBalance(arenaBefore.treasury);
+      await startTournament(bracketPda);
+
+      const fees = entryFee.toNumber() * 3;
+      const platformFee = (fees * 1000) / 10000;
+      expect(await provider.connection.getBalance(arenaBefore.treasury)).to.equal(treasuryBefore + platformFee);
+
+      const tournament = await program.account.tournament.fetch(bracketPda);
+      expect(tournament.status).to.deep.equal({ active: {} });
+      expect(tournament.round).to.equal(1);
+      expect(tournament.prizePool.toNumber()).to.equal(fees - platformFee);
+
+      const seeds = tournament.entrants.map(e => e.seedScore);
+      expect(seeds).to.deep.equal([...seeds].sort((a, b) => b - a));
+      expect(tournament.entrants[2].agent.toString()).to.equal(rookieAgentPda.toString());
+
+      // Top seed sits out; second meets third
+      expect(tournament.matches).to.have.length(2);
+      expect(tournament.matches[0].resolved).to.equal(true);
+      expect(tournament.matches[0].winner).to.equal(0);
+      expect(tournament.matches[1].sides.map(s => s.entrant)).to.deep.equal([1, 2]);
+    });
+
+    it('Should keep reveals closed until both sides commit', async () => {
+      const tournament = await program.account.tournament.fetch(bracketPda);
+      const high = sideAgent(tournament, 1, 0);
+      const low = sideAgent(tournament, 1, 1);
+      const payload = generatePredictionPayload('SOL', 'up', 150, 7000, BRACKET_HORIZON);
+
+      await commit(bracketPda, high, payload);
+      try {
+        await reveal(bracketPda, high, payload);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.CommitWindowOpen);
+      }
+
+      try {
+        await commit(bracketPda, tournament.entrants[0].agent, payload);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidTournamentMatch);
+      }
+
+      // The commit deadline keeps running while predictions are paused, so commits stay open
+      const setPause = (flags: number) =>
+        program.methods
+          .setPause(flags)
+          .accounts({ arena: arenaPda, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+      await setPause(TEST_CONSTANTS.PAUSE_PREDICTIONS);
+      try {
+        await commit(bracketPda, low, generatePredictionPayload('SOL', 'down', 170, 7000, BRACKET_HORIZON));
+      } finally {
+        await setPause(0);
+      }
+      expect((await program.account.tournament.fetch(bracketPda)).matches[1].sides[1].committed).to.equal(true);
+
+      try {
+        await commit(bracketPda, low, payload);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.MatchSideCommitted);
+      }
+    });
+
+    it('Should advance the closest target and pair the next round', async () => {
+      const tournament = await program.account.tournament.fetch(bracketPda);
+      const high = sideAgent(tournament, 1, 0);
+      const low = sideAgent(tournament, 1, 1);
+
+      await reveal(bracketPda, high, generatePredictionPayload('SOL', 'up', 150, 7000, BRACKET_HORIZON));
+      await reveal(bracketPda, low, generatePredictionPayload('SOL', 'down', 170, 7000, BRACKET_HORIZON));
+      try {
+        await resolveMatch(bracketPda, 1);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.HorizonNotReached);
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 12000));
+      // 155 is 5 from the higher seed's 150 and 15 from the lower seed's 170
+      await program.methods
+        .setMockPrice(Array.from(assetId), new BN(155_00000000), new BN(1000))
+        .accounts({
+          mockPriceFeed: mockFeedPda,
+          arena: arenaPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+      await resolveMatch(bracketPda, 1);
+
+      const next = await program.account.tournament.fetch(bracketPda);
+      expect(next.entrants[2].losses).to.equal(1);
+      expect(next.round).to.equal(2);
+      expect(next.matches).to.have.length(1);
+      expect(next.matches[0].sides.map(s => s.entrant)).to.deep.equal([0, 1]);
+    });
+
+    it('Should crown the champion when the final is decided by a no-show', async () => {
+      const tournament = await program.account.tournament.fetch(bracketPda);
+      const low = sideAgent(tournament, 0, 1);
+      const payload = generatePredictionPayload('SOL', 'up', 160, 7000, BRACKET_HORIZON);
+
+      await commit(bracketPda, low, payload);
+      await new Promise(resolve => setTimeout(resolve, 7000));
+      await reveal(bracketPda, low, payload);
+      try {
+        await resolveMatch(bracketPda, 0);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.RevealWindowOpen);
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+      await resolveMatch(bracketPda, 0);
+
+      const completed = await program.account.tournament.fetch(bracketPda);
+      expect(completed.status).to.deep.equal({ completed: {} });
+      expect(completed.champion.toString()).to.equal(low.toString());
+      expect(completed.entrants[0].losses).to.equal(1);
+    });
+
+    it('Should pay the prize pool to the champion only', async () => {
+      const tournament = await program.account.tournament.fetch(bracketPda);
+      const champion = tournament.champion;
+      const runnerUp = tournament.entrants[0].agent;
+      const claim = (agent: web3.PublicKey) =>
+        program.methods
+          .claimTournamentPrize()
+          .accounts({
+            arena: arenaPda,
+            tournament: bracketPda,
+            tournamentVault: deriveTournamentVaultPda(bracketPda, program.programId)[0],
+            agent,
+            owner: ownerOf(agent).publicKey,
+          })
+          .signers([ownerOf(agent)])
+          .rpc();
+
+      try {
+        await claim(runnerUp);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.NotTournamentChampion);
+      }
+
+      const before = await provider.connection.getBalance(ownerOf(champion).publicKey);
+      await claim(champion);
+      expect(await provider.connection.getBalance(ownerOf(champion).publicKey)).to.equal(
+        before + tournament.prizePool.toNumber()
+      );
+
+      try {
+        await claim(champion);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.PrizeAlreadyClaimed);
+      }
+    });
+
+    it('Should void a started tournament whose match price is never cranked', async () => {
+      const setStaleness = async (seconds: number) => {
+        await program.methods
+          .updateAssetFeed(Array.from(assetId), mockFeedPda, new BN(seconds))
+          .accounts({ assetFeed: assetFeedPda, arena: arenaPda, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+      };
+
+      const tournament = await createTournament({ singleElimination: {} }, 2, {
+        registration: 3600,
+        commit: 4,
+        reveal: 3,
+        horizon: 4,
+      });
+      await joinTournament(tournament, agentPda);
+      await joinTournament(tournament, agent2Pda);
+      await startTournament(tournament);
+
+      const started = await program.account.tournament.fetch(tournament);
+      const high = sideAgent(started, 0, 0);
+      const low = sideAgent(started, 0, 1);
+      const highPayload = generatePredictionPayload('SOL', 'up', 150, 7000, 4);
+      const lowPayload = generatePredictionPayload('SOL', 'down', 170, 7000, 4);
+      await commit(tournament, high, highPayload);
+      await commit(tournament, low, lowPayload);
+      await reveal(tournament, high, highPayload);
+      await reveal(tournament, low, lowPayload);
+
+      try {
+        await voidTournament(tournament, 0);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.ResolutionWindowOpen);
+      }
+
+      await setStaleness(1);
+      try {
+        await new Promise(resolve => setTimeout(resolve, 10000));
+        await voidTournament(tournament, 0);
+      } finally {
+        await setStaleness(60);
+      }
+
+      const voided = await program.account.tournament.fetch(tournament);
+      expect(voided.status).to.deep.equal({ cancelled: {} });
+
+      // The platform share is gone; entrants split the prize pool
+      const share = started.prizePool.toNumber() / 2;
+      const playerBefore = await provider.connection.getBalance(player.publicKey);
+      const player2Before = await provider.connection.getBalance(player2.publicKey);
+      await leaveTournament(tournament, agentPda);
+      await leaveTournament(tournament, agent2Pda);
+      expect(await provider.connection.getBalance(player.publicKey)).to.equal(playerBefore + share);
+      expect(await provider.connection.getBalance(player2.publicKey)).to.equal(player2Before + share);
+      expect((await program.account.tournament.fetch(tournament)).prizePool.toNumber()).to.equal(0);
+    });
+
+    it('Should only eliminate after two losses in double elimination', async () => {
+      const tournament = await createTournament({ doubleElimination: {} }, 2, {
+        registration: 3600,
+        commit: 1,
+        reveal: 1,
+        horizon: 2,
+      });
+      await joinTournament(tournament, agentPda);
+      await joinTournament(tournament, agent2Pda);
+      await startTournament(tournament);
+
+      // Nobody plays: the higher seed takes each match
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      await resolveMatch(tournament, 0);
+
+      const afterFirst = await program.account.tournament.fetch(tournament);
+      expect(afterFirst.status).to.deep.equal({ active: {} });
+      expect(afterFirst.entrants[1].losses).to.equal(1);
+      expect(afterFirst.round).to.equal(2);
+      expect(afterFirst.matches[0].sides.map(s => s.entrant)).to.deep.equal([0, 1]);
+
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      await resolveMatch(tournament, 0);
+
+      const completed = await program.account.tournament.fetch(tournament);
+      expect(completed.status).to.deep.equal({ completed: {} });
+      expect(completed.entrants[1].losses).to.equal(2);
+      expect(completed.champion.toString()).to.equal(completed.entrants[0].agent.toString());
+    });
+  });
+
+  // ==========================================
+  // Test Suite 31: Spectator Betting
+  // ==========================================
+  describe('Spectator Betting', () => {
+    const tipster = web3.Keypair.generate();
+    const alice = web3.Keypair.generate();
+    const bob = web3.Keypair.generate();
+    const carol = web3.Keypair.generate();
+    const BET = new BN(30_000_000);
+    let tipsterAgentPda: web3.PublicKey;
+    let bettingSeasonPda: web3.PublicKey;
+    let bettingEntryPda: web3.PublicKey;
+    let contested: { predPda: web3.PublicKey; nonce: Buffer; payload: PredictionPayload };
+
+    const commit = async (season: web3.PublicKey = bettingSeasonPda, horizonSeconds?: number) => {
+      const agent = await program.account.agent.fetch(tipsterAgentPda);
+      const nonce = generateNonce();
+      const payload = generatePredictionPayload('BTC', 'up', 90000, 6500, horizonSeconds);
+      const [predPda] = derivePredictionPda(tipsterAgentPda, season, agent.predictionsSubmitted, program.programId);
+
+      await program.methods
+        .submitPrediction(Array.from(generatePredictionHash(tipsterAgentPda, season, nonce, payload)), STAKE_AMOUNT)
+        .accounts({
+          season,
+          agent: tipsterAgentPda,
+          arena: arenaPda,
+          prediction: predPda,
+          player: tipster.publicKey,
+          predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([tipster])
+        .rpc();
+      return { predPda, nonce, payload };
+    };
+
+    const reveal = async ({ predPda, nonce, payload }: Awaited<ReturnType<typeof commit>>) => {
+      await program.methods
+        .revealPrediction(payload, Array.from(nonce))
+        .accounts({ prediction: predPda, agent: tipsterAgentPda, player: tipster.publicKey })
+        .signers([tipster])
+        .rpc();
+    };
+
+    const resolve = async (predPda: web3.PublicKey, wasCorrect: boolean) => {
+      await program.methods
+        .resolvePrediction(wasCorrect)
+        .accounts({
+          prediction: predPda,
+          agent: tipsterAgentPda,
+          seasonEntry: bettingEntryPda,
+          resolver: authority.publicKey,
+          predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+          player: tipster.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    };
+
+    const placeBet = async (predPda: web3.PublicKey, bettor: web3.Keypair, side: object, amount: BN) => {
+      const [pool] = deriveBettingPoolPda(predPda, program.programId);
+      await program.methods
+        .placeBet(side, amount)
+        .accounts({
+          arena: arenaPda,
+          prediction: predPda,
+          agent: tipsterAgentPda,
+          bettingPool: pool,
+          bettingVault: deriveBettingVaultPda(pool, program.programId)[0],
+          bet: deriveBetPda(pool, bettor.publicKey, program.programId)[0],
+          bettor: bettor.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([bettor])
+        .rpc();
+    };
+
+    const settlePool = async (predPda: web3.PublicKey) => {
+      const [pool] = deriveBettingPoolPda(predPda, program.programId);
+      const arena = await program.account.arena.fetch(arenaPda);
+      await program.methods
+        .settleBettingPool()
+        .accounts({
+          arena: arenaPda,
+          prediction: predPda,
+          bettingPool: pool,
+          bettingVault: deriveBettingVaultPda(pool, program.programId)[0],
+          treasury: arena.treasury,
+        })
+        .rpc();
+    };
+
+    const claimBet = async (predPda: web3.PublicKey, bettor: web3.Keypair) => {
+      const [pool] = deriveBettingPoolPda(predPda, program.programId);
+      await program.methods
+        .claimBet()
+        .accounts({
+          arena: arenaPda,
+          bettingPool: pool,
+          bettingVault: deriveBettingVaultPda(pool, program.programId)[0],
+          bet: deriveBetPda(pool, bettor.publicKey, program.programId)[0],
+          bettor: bettor.publicKey,
+        })
+        .signers([bettor])
+        .rpc();
+    };
+
+    const closePrediction = async (predPda: web3.PublicKey, withPool: boolean) => {
+      await program.methods
+        .closePrediction()
+        .accounts({
+          agent: tipsterAgentPda,
+          prediction: predPda,
+          predictionVault: derivePredictionVaultPda(predPda, program.programId)[0],
+          rentPayer: tipster.publicKey,
+          owner: tipster.publicKey,
+          arena: arenaPda,
+          ...(withPool ? { bettingPool: deriveBettingPoolPda(predPda, program.programId)[0] } : {}),
+        })
+        .signers([tipster])
+        .rpc();
+    };
+
+    before(async () => {
+      for (const wallet of [tipster, alice, bob, carol]) {
+        await airdrop(provider, wallet, 5);
+      }
+      [tipsterAgentPda] = deriveAgentPda(tipster.publicKey, 0, program.programId);
+
+      await program.methods
+        .registerAgent('Tipster', TEST_CONSTANTS.ENDPOINT_URLS[0])
+        .accounts({
+          agentRegistry: deriveAgentRegistryPda(tipster.publicKey, program.programId)[0],
+          agent: tipsterAgentPda,
+          arena: arenaPda,
+          owner: tipster.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([tipster])
+        .rpc();
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      [bettingSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      [bettingEntryPda] = deriveSeasonEntryPda(bettingSeasonPda, tipsterAgentPda, program.programId);
+
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, REVEAL_WINDOW, { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: bettingSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: bettingSeasonPda,
+          seasonEntry: bettingEntryPda,
+          agent: tipsterAgentPda,
+          arena: arenaPda,
+          player: tipster.publicKey,
+          seasonVault: deriveSeasonVaultPda(bettingSeasonPda, program.programId)[0],
+          treasury: arena.treasury,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([tipster])
+        .rpc();
+
+      await program.methods
+        .setFee({ betting: {} }, TEST_CONSTANTS.BETTING_FEE_BPS)
+        .accounts({ arena: arenaPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      contested = await commit();
+    });
+
+    it('Should keep the agent owner out of its own pool', async () => {
+      try {
+        await placeBet(contested.predPda, tipster, { right: {} }, BET);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InsiderBet);
+      }
+
+      try {
+        await placeBet(contested.predPda, alice, { right: {} }, new BN(0));
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidBetAmount);
+      }
+    });
+
+    it('Should take bets on both sides while the prediction is hidden', async () => {
+      await placeBet(contested.predPda, alice, { right: {} }, BET);
+      await placeBet(contested.predPda, alice, { right: {} }, BET);
+      await placeBet(contested.predPda, carol, { right: {} }, BET);
+      await placeBet(contested.predPda, bob, { wrong: {} }, BET.muln(2));
+
+      try {
+        await placeBet(contested.predPda, alice, { wrong: {} }, BET);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.BetSideMismatch);
+      }
+
+      const [poolPda] = deriveBettingPoolPda(contested.predPda, program.programId);
+      const pool = await program.account.bettingPool.fetch(poolPda);
+      expect(pool.rightTotal.toNumber()).to.equal(BET.toNumber() * 3);
+      expect(pool.wrongTotal.toNumber()).to.equal(BET.toNumber() * 2);
+      expect(pool.feeBps).to.equal(TEST_CONSTANTS.BETTING_FEE_BPS);
+
+      const aliceBet = await program.account.bet.fetch(deriveBetPda(poolPda, alice.publicKey, program.programId)[0]);
+      expect(aliceBet.amount.toNumber()).to.equal(BET.toNumber() * 2);
+
+      const prediction = await program.account.prediction.fetch(contested.predPda);
+      expect(prediction.bettingPool.toString()).to.equal(poolPda.toString());
+    });
+
+    it('Should close betting once the prediction is revealed', async () => {
+      await reveal(contested);
+
+      try {
+        await placeBet(contested.predPda, carol, { right: {} }, BET);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.BettingClosed);
+      }
+
+      try {
+        await settlePool(contested.predPda);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.PredictionNotSettled);
+      }
+    });
+
+    it('Should refuse late bets and void those placed after the horizon', async () => {
+      // Four-second reveal window on a one-second horizon
+      const arena = await program.account.arena.fetch(arenaPda);
+      const [lateSeasonPda] = deriveSeasonPda(arena.totalSeasons, program.programId);
+      const [lateEntryPda] = deriveSeasonEntryPda(lateSeasonPda, tipsterAgentPda, program.programId);
+      const [lateVaultPda] = deriveSeasonVaultPda(lateSeasonPda, program.programId);
+      await program.methods
+        .createSeason(ENTRY_FEE, DURATION_DAYS, PRIZE_POOL_BPS, new BN(4), { treasury: {} }, { payoutBps: PAYOUT_BPS, remainderPolicy: { redistribute: {} } })
+        .accounts({
+          season: lateSeasonPda,
+          arena: arenaPda,
+          seasonOperator: authority.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+      await program.methods
+        .enterSeason()
+        .accounts({
+          season: lateSeasonPda,
+          seasonEntry: lateEntryPda,
+          agent: tipsterAgentPda,
+          arena: arenaPda,
+          player: tipster.publicKey,
+          seasonVault: lateVaultPda,
+          treasury: arena.treasury,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([tipster])
+        .rpc();
+
+      const late = await commit(lateSeasonPda, 1);
+      await new Promise(resolve => setTimeout(resolve, 1500));
+
+      // Past the horizon an insider may know the outcome, but the prediction can no
+      // longer be revealed, so the bet can only be refunded
+      await placeBet(late.predPda, alice, { right: {} }, BET);
+      try {
+        await reveal(late);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.RevealWindowClosed);
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      try {
+        await placeBet(late.predPda, bob, { wrong: {} }, BET);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.BettingClosed);
+      }
+
+      await program.methods
+        .forfeitPrediction()
+        .accounts({
+          prediction: late.predPda,
+          agent: tipsterAgentPda,
+          season: lateSeasonPda,
+          seasonEntry: lateEntryPda,
+          arena: arenaPda,
+          predictionVault: derivePredictionVaultPda(late.predPda, program.programId)[0],
+          seasonVault: lateVaultPda,
+          treasury: arena.treasury,
+          cranker: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+      await settlePool(late.predPda);
+
+      const pool = await program.account.bettingPool.fetch(deriveBettingPoolPda(late.predPda, program.programId)[0]);
+      expect(pool.outcome).to.deep.equal({ void: {} });
+
+      const betRent = await provider.connection.getMinimumBalanceForRentExemption(8 + 32 + 32 + 1 + 8 + 1);
+      const before = await provider.connection.getBalance(alice.publicKey);
+      await claimBet(late.predPda, alice);
+      expect(await provider.connection.getBalance(alice.publicKey)).to.equal(before + BET.toNumber() + betRent);
+    });
+
+    it('Should keep the pool and its prediction open until settlement', async () => {
+      await resolve(contested.predPda, true);
+
+      try {
+        await claimBet(contested.predPda, alice);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.BettingPoolUnsettled);
+      }
+
+      try {
+        await closePrediction(contested.predPda, false);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.BettingPoolUnsettled);
+      }
+    });
+
+    it('Should split the pool among the winning side net of the house cut', async () => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      const treasuryBefore = await provider.connection.getBalance(arena.treasury);
+      await settlePool(contested.predPda);
+
+      const total = BET.toNumber() * 5;
+      const fee = (total * TEST_CONSTANTS.BETTING_FEE_BPS) / 10000;
+      expect(await provider.connection.getBalance(arena.treasury)).to.equal(treasuryBefore + fee);
+
+      const pool = await program.account.bettingPool.fetch(deriveBettingPoolPda(contested.predPda, program.programId)[0]);
+      expect(pool.outcome).to.deep.equal({ right: {} });
+      expect(pool.payoutPool.toNumber()).to.equal(total - fee);
+
+      try {
+        await settlePool(contested.predPda);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.BettingPoolSettled);
+      }
+
+      const betRent = await provider.connection.getMinimumBalanceForRentExemption(8 + 32 + 32 + 1 + 8 + 1);
+      const payouts = [
+        { bettor: alice, payout: ((total - fee) * 2) / 3 },
+        { bettor: carol, payout: (total - fee) / 3 },
+        { bettor: bob, payout: 0 },
+      ];
+      for (const { bettor, payout } of payouts) {
+        const before = await provider.connection.getBalance(bettor.publicKey);
+        await claimBet(contested.predPda, bettor);
+        expect(await provider.connection.getBalance(bettor.publicKey)).to.equal(before + payout + betRent);
+      }
+
+      await closePrediction(contested.predPda, true);
+      expect(await provider.connection.getAccountInfo(contested.predPda)).to.be.null;
+    });
+
+    it('Should refund every bet when one side is empty', async () => {
+      const lopsided = await commit();
+      await placeBet(lopsided.predPda, alice, { right: {} }, BET);
+      await reveal(lopsided);
+      await resolve(lopsided.predPda, false);
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      const treasuryBefore = await provider.connection.getBalance(arena.treasury);
+      await settlePool(lopsided.predPda);
+      expect(await provider.connection.getBalance(arena.treasury)).to.equal(treasuryBefore);
+
+      const pool = await program.account.bettingPool.fetch(deriveBettingPoolPda(lopsided.predPda, program.programId)[0]);
+      expect(pool.outcome).to.deep.equal({ void: {} });
+
+      const betRent = await provider.connection.getMinimumBalanceForRentExemption(8 + 32 + 32 + 1 + 8 + 1);
+      const before = await provider.connection.getBalance(alice.publicKey);
+      await claimBet(lopsided.predPda, alice);
+      expect(await provider.connection.getBalance(alice.publicKey)).to.equal(before + BET.toNumber() + betRent);
+    });
+  });
+
+  // ==========================================
+  // Test Suite 32: Agent Subscriptions
+  // ==========================================
+  describe('Agent Subscriptions', () => {
+    const seller = web3.Keypair.generate();
+    const fan = web3.Keypair.generate();
+    const PRICE = new BN(10_000_000);
+    const PERIOD = new BN(2);
+    let sellerAgentPda: web3.PublicKey;
+    let planPda: web3.PublicKey;
+    let revenueVaultPda: web3.PublicKey;
+    let subscriptionPda: web3.PublicKey;
+
+    const setPlan = async (price: BN, period: BN, owner: web3.Keypair = seller) => {
+      await program.methods
+        .setSubscriptionPlan(price, period)
+        .accounts({
+          agent: sellerAgentPda,
+          subscriptionPlan: planPda,
+          revenueVault: revenueVaultPda,
+          owner: owner.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+    };
+
+    const subscribe = async (periods: number, maxPrice: BN = PRICE) => {
+      const arena = await program.account.arena.fetch(arenaPda);
+      await program.methods
+        .subscribe(periods, maxPrice)
+        .accounts({
+          arena: arenaPda,
+          agent: sellerAgentPda,
+          subscriptionPlan: planPda,
+          revenueVault: revenueVaultPda,
+          subscription: subscriptionPda,
+          treasury: arena.treasury,
+          subscriber: fan.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([fan])
+        .rpc();
+    };
+
+    const verify = async () => {
+      await program.methods.verifySubscription().accounts({ subscription: subscriptionPda }).rpc();
+    };
+
+    const withdraw = async (amount: BN, owner: web3.Keypair = seller) => {
+      await program.methods
+        .withdrawAgentRevenue(amount)
+        .accounts({
+          agent: sellerAgentPda,
+          revenueVault: revenueVaultPda,
+          owner: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+    };
+
+    const closeSubscription = async () => {
+      await program.methods
+        .closeSubscription()
+        .accounts({ subscription: subscriptionPda, subscriber: fan.publicKey })
+        .signers([fan])
+        .rpc();
+    };
+
+    before(async () => {
+      await airdrop(provider, seller);
+      await airdrop(provider, fan);
+      [sellerAgentPda] = deriveAgentPda(seller.publicKey, 0, program.programId);
+      [planPda] = deriveSubscriptionPlanPda(sellerAgentPda, program.programId);
+      [revenueVaultPda] = deriveAgentRevenueVaultPda(sellerAgentPda, program.programId);
+      [subscriptionPda] = deriveSubscriptionPda(sellerAgentPda, fan.publicKey, program.programId);
+
+      await program.methods
+        .registerAgent('SignalSeller', TEST_CONSTANTS.ENDPOINT_URLS[2])
+        .accounts({
+          agentRegistry: deriveAgentRegistryPda(seller.publicKey, program.programId)[0],
+          agent: sellerAgentPda,
+          arena: arenaPda,
+          owner: seller.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([seller])
+        .rpc();
+
+      await program.methods
+        .setFee({ subscription: {} }, TEST_CONSTANTS.SUBSCRIPTION_FEE_BPS)
+        .accounts({ arena: arenaPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    });
+
+    it('Should let only the owner publish a valid plan', async () => {
+      try {
+        await setPlan(PRICE, PERIOD, fan);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.UnauthorizedAgent);
+      }
+
+      try {
+        await setPlan(PRICE, new BN(0));
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InvalidSubscriptionPlan);
+      }
+    });
+
+    it('Should refuse sales while the price is zero', async () => {
+      await setPlan(new BN(0), PERIOD);
+      try {
+        await subscribe(1);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.SubscriptionsClosed);
+      }
+
+      await setPlan(PRICE, PERIOD);
+      const plan = await program.account.subscriptionPlan.fetch(planPda);
+      expect(plan.price.toNumber()).to.equal(PRICE.toNumber());
+      expect(plan.period.toNumber()).to.equal(PERIOD.toNumber());
+    });
+
+    it('Should split a payment between the revenue vault and the treasury', async () => {
+      try {
+        await subscribe(1, PRICE.subn(1));
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.SubscriptionPriceAboveLimit);
+      }
+
+      const arena = await program.account.arena.fetch(arenaPda);
+      const treasuryBefore = await provider.connection.getBalance(arena.treasury);
+      const vaultBefore = await provider.connection.getBalance(revenueVaultPda);
+      await subscribe(3);
+
+      const paid = PRICE.toNumber() * 3;
+      const fee = (paid * TEST_CONSTANTS.SUBSCRIPTION_FEE_BPS) / 10000;
+      expect(await provider.connection.getBalance(arena.treasury)).to.equal(treasuryBefore + fee);
+      expect(await provider.connection.getBalance(revenueVaultPda)).to.equal(vaultBefore + paid - fee);
+
+      const vault = await program.account.agentRevenueVault.fetch(revenueVaultPda);
+      expect(vault.totalEarned.toNumber()).to.equal(paid - fee);
+
+      const subscription = await program.account.subscription.fetch(subscriptionPda);
+      expect(subscription.subscriber.toString()).to.equal(fan.publicKey.toString());
+      expect(subscription.expiresAt.toNumber()).to.equal(subscription.startedAt.toNumber() + PERIOD.toNumber() * 3);
+      expect(subscription.totalPaid.toNumber()).to.equal(paid);
+
+      await verify();
+    });
+
+    it('Should extend an active subscription from its expiry', async () => {
+      const before = await program.account.subscription.fetch(subscriptionPda);
+      await subscribe(1);
+
+      const after = await program.account.subscription.fetch(subscriptionPda);
+      expect(after.startedAt.toNumber()).to.equal(before.startedAt.toNumber());
+      expect(after.expiresAt.toNumber()).to.equal(before.expiresAt.toNumber() + PERIOD.toNumber());
+
+      try {
+        await closeSubscription();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.SubscriptionActive);
+      }
+    });
+
+    it('Should let only the owner withdraw revenue', async () => {
+      const vault = await program.account.agentRevenueVault.fetch(revenueVaultPda);
+      const available = vault.totalEarned.toNumber();
+
+      try {
+        await withdraw(new BN(available), fan);
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.UnauthorizedAgent);
+      }
+
+      try {
+        await withdraw(new BN(available + 1));
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.InsufficientRevenue);
+      }
+
+      const before = await provider.connection.getBalance(seller.publicKey);
+      await withdraw(new BN(available));
+      expect(await provider.connection.getBalance(seller.publicKey)).to.equal(before + available);
+
+      const drained = await program.account.agentRevenueVault.fetch(revenueVaultPda);
+      expect(drained.totalWithdrawn.toNumber()).to.equal(available);
+    });
+
+    it('Should fail verification once expired and let the subscriber close it', async () => {
+      await new Promise(resolve => setTimeout(resolve, 9000));
+
+      try {
+        await verify();
+        assert.fail('Should have thrown an error');
+      } catch (error) {
+        expect(error.error.errorMessage).to.include(ERROR_CODES.SubscriptionExpired);
+      }
+
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(8 + 32 + 32 + 8 + 8 + 8 + 1);
+      const before = await provider.connection.getBalance(fan.publicKey);
+      await closeSubscription();
+      expect(await provider.connection.getBalance(fan.publicKey)).to.equal(before + rent);
+    });
+  });
 });
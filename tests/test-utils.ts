@@ -1,6 +1,6 @@
 import * as anchor from '@coral-xyz/anchor';
 import { PublicKey, BN } from '@coral-xyz/anchor';
-import { createHash } from 'crypto';
+import { createHash, randomBytes } from 'crypto';
 
 /**
  * Test Utilities for Signal Wars
@@ -18,12 +18,36 @@ export function deriveArenaPda(programId: PublicKey): [PublicKey, number] {
   );
 }
 
+export function deriveAgentRegistryPda(
+  owner: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('agent_registry'), owner.toBuffer()],
+    programId
+  );
+}
+
 export function deriveAgentPda(
   owner: PublicKey,
+  index: number,
   programId: PublicKey
 ): [PublicKey, number] {
+  const indexBuffer = Buffer.alloc(2);
+  indexBuffer.writeUInt16LE(index);
   return PublicKey.findProgramAddressSync(
-    [Buffer.from('agent'), owner.toBuffer()],
+    [Buffer.from('agent'), owner.toBuffer(), indexBuffer],
+    programId
+  );
+}
+
+export function deriveAgentDelegatePda(
+  agent: PublicKey,
+  key: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('delegate'), agent.toBuffer(), key.toBuffer()],
     programId
   );
 }
@@ -49,10 +73,11 @@ export function deriveSeasonEntryPda(
   );
 }
 
+// `predictionsSubmitted` is the agent's submit counter at the time of submission
 export function derivePredictionPda(
   agent: PublicKey,
   season: PublicKey,
-  predictionCount: BN,
+  predictionsSubmitted: BN,
   programId: PublicKey
 ): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
@@ -60,7 +85,7 @@ export function derivePredictionPda(
       Buffer.from('prediction'),
       agent.toBuffer(),
       season.toBuffer(),
-      predictionCount.toArrayLike(Buffer, 'le', 8),
+      predictionsSubmitted.toArrayLike(Buffer, 'le', 8),
     ],
     programId
   );
@@ -76,6 +101,23 @@ export function deriveSeasonVaultPda(
   );
 }
 
+export function deriveTreasuryPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('treasury')],
+    programId
+  );
+}
+
+export function deriveLeaderboardPda(
+  season: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('leaderboard'), season.toBuffer()],
+    programId
+  );
+}
+
 export function derivePredictionVaultPda(
   prediction: PublicKey,
   programId: PublicKey
@@ -86,6 +128,46 @@ export function derivePredictionVaultPda(
   );
 }
 
+export function deriveSeasonTokenVaultPda(
+  season: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('season_token_vault'), season.toBuffer()],
+    programId
+  );
+}
+
+export function derivePredictionTokenVaultPda(
+  prediction: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('prediction_token_vault'), prediction.toBuffer()],
+    programId
+  );
+}
+
+export function deriveTreasuryTokenVaultPda(
+  mint: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('treasury_token_vault'), mint.toBuffer()],
+    programId
+  );
+}
+
+export function deriveTreasuryTokenLedgerPda(
+  mint: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('treasury_token_ledger'), mint.toBuffer()],
+    programId
+  );
+}
+
 export function deriveAchievementPda(
   agent: PublicKey,
   reputationScore: BN,
@@ -101,67 +183,236 @@ export function deriveAchievementPda(
   );
 }
 
+export function deriveAssetFeedPda(
+  assetId: Buffer,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('asset_feed'), assetId],
+    programId
+  );
+}
+
+export function deriveMockPriceFeedPda(
+  assetId: Buffer,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('mock_price_feed'), assetId],
+    programId
+  );
+}
+
+export function deriveDuelPda(
+  duelId: BN,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('duel'), duelId.toArrayLike(Buffer, 'le', 8)],
+    programId
+  );
+}
+
+export function deriveDuelVaultPda(
+  duel: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('duel_vault'), duel.toBuffer()],
+    programId
+  );
+}
+
+export function deriveTournamentPda(
+  tournamentId: BN,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('tournament'), tournamentId.toArrayLike(Buffer, 'le', 8)],
+    programId
+  );
+}
+
+export function deriveTournamentVaultPda(
+  tournament: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('tournament_vault'), tournament.toBuffer()],
+    programId
+  );
+}
+
+export function deriveBettingPoolPda(
+  prediction: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('betting_pool'), prediction.toBuffer()],
+    programId
+  );
+}
+
+export function deriveBettingVaultPda(
+  pool: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('betting_vault'), pool.toBuffer()],
+    programId
+  );
+}
+
+export function deriveBetPda(
+  pool: PublicKey,
+  bettor: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('bet'), pool.toBuffer(), bettor.toBuffer()],
+    programId
+  );
+}
+
+export function deriveSubscriptionPlanPda(
+  agent: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('subscription_plan'), agent.toBuffer()],
+    programId
+  );
+}
+
+export function deriveAgentRevenueVaultPda(
+  agent: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('agent_revenue_vault'), agent.toBuffer()],
+    programId
+  );
+}
+
+export function deriveSubscriptionPda(
+  agent: PublicKey,
+  subscriber: PublicKey,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('subscription'), agent.toBuffer(), subscriber.toBuffer()],
+    programId
+  );
+}
+
 // ==========================================
 // Prediction Data Utilities
 // ==========================================
 
 export interface PredictionPayload {
-  asset: string;
-  direction: 'up' | 'down';
-  targetPrice: number;
-  confidence: number;
-  timestamp?: number;
+  assetId: number[];
+  direction: { up: {} } | { down: {} };
+  targetPrice: BN;
+  targetExpo: number;
+  horizon: BN;
+  confidenceBps: number;
 }
 
-/**
- * Generate prediction hash (SHA-256)
- */
-export function generatePredictionHash(predictionData: string): Buffer {
-  const hash = createHash('sha256').update(predictionData).digest();
-  return Buffer.from(hash);
-}
+export const DEFAULT_HORIZON_SECONDS = 3600;
 
 /**
- * Generate prediction data JSON string
+ * Encode an asset symbol as the fixed 8-byte asset id used on-chain
  */
-export function generatePredictionData(
-  asset: string,
-  direction: 'up' | 'down',
-  targetPrice: number,
-  confidence: number
-): string {
-  return JSON.stringify({
-    asset,
-    direction,
-    targetPrice,
-    confidence,
-    timestamp: Date.now(),
-  });
+export function assetIdFromSymbol(symbol: string): Buffer {
+  const id = Buffer.alloc(8);
+  id.write(symbol, 'ascii');
+  return id;
 }
 
 /**
- * Generate complete prediction payload object
+ * Generate a typed prediction payload (price = targetPrice * 10^targetExpo)
  */
 export function generatePredictionPayload(
   asset: string,
   direction: 'up' | 'down',
   targetPrice: number,
-  confidence: number
+  confidenceBps: number,
+  horizonSeconds: number = DEFAULT_HORIZON_SECONDS,
+  targetExpo: number = 0
 ): PredictionPayload {
   return {
-    asset,
-    direction,
-    targetPrice,
-    confidence,
-    timestamp: Date.now(),
+    assetId: Array.from(assetIdFromSymbol(asset)),
+    direction: direction === 'up' ? { up: {} } : { down: {} },
+    targetPrice: new BN(targetPrice),
+    targetExpo,
+    horizon: new BN(horizonSeconds),
+    confidenceBps,
   };
 }
 
 /**
- * Parse prediction data from JSON string
+ * Borsh-encode a prediction payload exactly as the program does
+ */
+export function serializePredictionPayload(payload: PredictionPayload): Buffer {
+  const buf = Buffer.alloc(8 + 1 + 8 + 4 + 8 + 2);
+  Buffer.from(payload.assetId).copy(buf, 0);
+  buf.writeUInt8('up' in payload.direction ? 0 : 1, 8);
+  buf.writeBigInt64LE(BigInt(payload.targetPrice.toString()), 9);
+  buf.writeInt32LE(payload.targetExpo, 17);
+  buf.writeBigInt64LE(BigInt(payload.horizon.toString()), 21);
+  buf.writeUInt16LE(payload.confidenceBps, 29);
+  return buf;
+}
+
+export const PREDICTION_COMMITMENT_DOMAIN = Buffer.from('signal-wars:prediction:v1');
+
+/**
+ * Generate a fresh 32-byte commitment nonce (keep it secret until reveal)
+ */
+export function generateNonce(): Buffer {
+  return randomBytes(32);
+}
+
+/**
+ * Generate prediction commitment, matching `prediction_commitment` on-chain:
+ * sha256(domain || agent || season || nonce || borsh(payload))
+ */
+export function generatePredictionHash(
+  agent: PublicKey,
+  season: PublicKey,
+  nonce: Buffer,
+  payload: PredictionPayload
+): Buffer {
+  const hash = createHash('sha256')
+    .update(PREDICTION_COMMITMENT_DOMAIN)
+    .update(agent.toBuffer())
+    .update(season.toBuffer())
+    .update(nonce)
+    .update(serializePredictionPayload(payload))
+    .digest();
+  return Buffer.from(hash);
+}
+
+export const DUEL_COMMITMENT_DOMAIN = Buffer.from('signal-wars:duel:v1');
+
+/**
+ * Generate one side's duel commitment, matching `duel_commitment` on-chain:
+ * sha256(domain || duel || agent || nonce || borsh(payload))
  */
-export function parsePredictionData(data: string): PredictionPayload {
-  return JSON.parse(data);
+export function generateDuelHash(
+  duel: PublicKey,
+  agent: PublicKey,
+  nonce: Buffer,
+  payload: PredictionPayload
+): Buffer {
+  const hash = createHash('sha256')
+    .update(DUEL_COMMITMENT_DOMAIN)
+    .update(duel.toBuffer())
+    .update(agent.toBuffer())
+    .update(nonce)
+    .update(serializePredictionPayload(payload))
+    .digest();
+  return Buffer.from(hash);
 }
 
 // ==========================================
@@ -177,6 +428,31 @@ export const TEST_CONSTANTS = {
   // Test durations
   DURATION_DAYS_DEFAULT: 7,
   DURATION_DAYS_SHORT: 0,
+  REVEAL_WINDOW_SECONDS: 3600,
+  PAYOUT_BPS_DEFAULT: [5000, 3000, 2000],
+  
+  // SPL token seasons (6-decimal mint)
+  TOKEN_DECIMALS: 6,
+  TOKEN_ENTRY_FEE: 10_000_000,
+  TOKEN_STAKE_AMOUNT: 5_000_000,
+  TRANSFER_FEE_BPS: 100, // Token-2022 transfer-fee mint (1%)
+  
+  // Arena pause flags (match PAUSE_* in the program)
+  PAUSE_ENTRIES: 1,
+  PAUSE_PREDICTIONS: 2,
+  PAUSE_PAYOUTS: 4,
+  PAUSE_ALL: 7,
+  
+  // Agent delegate scopes (match DELEGATE_* in the program)
+  DELEGATE_SUBMIT: 1,
+  DELEGATE_REVEAL: 2,
+  DELEGATE_ALL: 3,
+  
+  // Duels
+  DUEL_FEE_BPS: 500, // 5% of the pot
+  TOURNAMENT_ENTRY_FEE: 100_000_000, // 0.1 SOL
+  BETTING_FEE_BPS: 200, // 2% house cut
+  SUBSCRIPTION_FEE_BPS: 1000, // 10% of subscription revenue
   
   // Basis points
   PRIZE_POOL_BPS_DEFAULT: 9000, // 90%
@@ -270,6 +546,89 @@ export const ERROR_CODES = {
   InvalidSeasonStatus: 'Invalid season status',
   InvalidPredictionStatus: 'Invalid prediction status',
   HashMismatch: 'Hash mismatch',
+  InvalidConfidence: 'Confidence must be at most 10000 bps',
+  HorizonNotReached: 'Prediction horizon has not been reached',
+  StalePrice: 'Price is stale for this resolution',
+  ResolutionWindowMissed: 'Price was published too long after the horizon',
+  AssetHasPriceFeed: 'Asset has a registered price feed; resolve it from the oracle',
+  ResolutionWindowOpen: 'Oracle resolution window is still open',
+  RevealWindowClosed: 'Reveal window has closed',
+  RevealWindowOpen: 'Reveal window is still open',
+  UnauthorizedAgent: 'Unauthorized - not the agent owner',
+  PredictionAgentMismatch: 'Agent does not match the prediction',
+  EntrySeasonMismatch: 'Season entry belongs to a different season',
+  EntryAgentMismatch: 'Season entry belongs to a different agent',
+  PlayerNotAgentOwner: 'Player is not the agent owner',
+  PlayerNotEntrant: 'Player does not match the season entry',
+  PrizeRecipientMismatch: 'Prize recipient does not match the leaderboard',
+  InvalidPayoutSchedule: 'Payout schedule must have 1-10 non-zero shares summing to 10000 bps',
+  InvalidRecipientCount: 'Expected one prize recipient per paid place',
+  InsufficientFunds: 'Insufficient funds in treasury',
+  SeasonCancelled: 'Season has been cancelled',
+  SeasonNotCancelled: 'Season has not been cancelled',
+  AlreadyRefunded: 'Refund already claimed',
+  MissingTokenAccount: "Token account required for this season's mint",
+  TokenMintMismatch: 'Token account does not match the season mint',
+  NoPendingAuthority: 'No authority transfer is pending',
+  NotPendingAuthority: 'Signer is not the pending authority',
+  ArenaPaused: 'Arena is paused for this instruction',
+  InvalidPauseFlags: 'Unknown pause flags',
+  EmptyAgentUpdate: 'Agent update must change the name or endpoint',
+  AgentUpdateCooldown: 'Agent was updated too recently',
+  AgentHasOpenPredictions: 'Agent has unresolved predictions',
+  TooManyAgents: 'Owner has reached the agent limit',
+  InvalidDelegateScopes: 'Unknown or empty delegate scopes',
+  InvalidDelegateExpiry: 'Delegate expiry must be in the future',
+  DelegateScopeMissing: 'Delegate is not scoped for this instruction',
+  DelegateExpired: 'Delegate has expired',
+  DelegateStakeCapExceeded: "Stake exceeds the delegate's cap",
+  StaleDelegate: 'Delegate was granted by a previous owner',
+  InvalidAgentRecipient: 'Invalid agent transfer recipient',
+  NoPendingAgentTransfer: 'No agent transfer is pending',
+  NotPendingAgentOwner: "Signer is not the agent's pending owner",
+  AgentRetired: 'Agent is retired',
+  AgentNotRetired: 'Agent is not retired',
+  PredictionNotSettled: 'Prediction is not resolved or expired',
+  SeasonEntryInUse: 'Season entry is still needed by its season',
+  InvalidFeeBps: 'Fee must be at most 10000 bps',
+  InvalidDuelTerms: 'Invalid duel stake, horizon or acceptance window',
+  SelfDuel: 'An agent cannot duel itself',
+  InvalidDuelStatus: 'Invalid duel status',
+  DuelAcceptWindowClosed: 'Duel acceptance deadline has passed',
+  DuelOpponentMismatch: 'Agent is not the invited opponent',
+  DuelParticipantMismatch: 'Agent is not in this duel',
+  DuelAlreadyRevealed: 'Duel side already revealed',
+  DuelPayloadMismatch: 'Revealed asset or horizon differs from the duel',
+  DuelCancelTooEarly: 'Only the challenger can cancel before the acceptance deadline',
+  InvalidTournamentTerms: 'Invalid tournament size, horizon, windows or prize split',
+  InvalidTournamentStatus: 'Invalid tournament status',
+  TournamentRegistrationClosed: 'Tournament registration has closed',
+  TournamentRegistrationOpen: 'Tournament registration is still open',
+  TournamentFull: 'Tournament is full',
+  AlreadyInTournament: 'Agent already entered this tournament',
+  NotInTournament: 'Agent is not in this tournament',
+  NotEnoughEntrants: 'A tournament needs at least two entrants',
+  InvalidTournamentMatch: 'No unresolved match for this slot or agent',
+  CommitWindowClosed: 'Commit window has closed',
+  CommitWindowOpen: 'Opponent can still commit',
+  MatchSideCommitted: 'Match side already committed',
+  MatchSideNotCommitted: 'Match side has not committed',
+  MatchSideRevealed: 'Match side already revealed',
+  TournamentPayloadMismatch: 'Revealed asset or horizon differs from the tournament',
+  NotTournamentChampion: 'Agent is not the tournament champion',
+  PrizeAlreadyClaimed: 'Prize already claimed',
+  InvalidBetAmount: 'Bet amount must be greater than zero',
+  BettingClosed: 'Betting has closed for this prediction',
+  InsiderBet: 'The agent owner and submitter cannot bet on their own prediction',
+  BetSideMismatch: 'Bet must stay on the side already backed',
+  BettingPoolUnsettled: 'Betting pool has not been settled',
+  BettingPoolSettled: 'Betting pool already settled',
+  InvalidSubscriptionPlan: 'Subscription period or count out of range',
+  SubscriptionsClosed: 'Agent is not selling subscriptions',
+  SubscriptionPriceAboveLimit: "Subscription price is above the subscriber's limit",
+  SubscriptionExpired: 'Subscription has expired',
+  SubscriptionActive: 'Subscription is still active',
+  InsufficientRevenue: 'Insufficient revenue in the agent vault',
 } as const;
 
 // ==========================================
@@ -313,13 +672,16 @@ export async function airdrop(
 // ==========================================
 
 /**
- * Verify prediction hash matches data
+ * Verify prediction hash matches payload
  */
 export function verifyPredictionHash(
-  predictionData: string,
+  agent: PublicKey,
+  season: PublicKey,
+  nonce: Buffer,
+  payload: PredictionPayload,
   expectedHash: Buffer
 ): boolean {
-  const computedHash = generatePredictionHash(predictionData);
+  const computedHash = generatePredictionHash(agent, season, nonce, payload);
   return computedHash.equals(expectedHash);
 }
 